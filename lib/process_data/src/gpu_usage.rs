@@ -0,0 +1,204 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    ffi::OsString,
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use glob::glob;
+use serde::{Deserialize, Serialize};
+
+/// The GPU usage of a process on a single GPU as reported by the DRM
+/// subsystem in `/proc/<pid>/fdinfo/<fd>`, see
+/// <https://docs.kernel.org/gpu/drm-usage-stats.html>.
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpuUsageStats {
+    /// The time in nanoseconds each engine has been busy with the process'
+    /// work, already divided by the engine's capacity
+    pub engines: BTreeMap<String, u64>,
+    /// The busy cycles and the total cycles of each engine, reported by
+    /// drivers like `xe` instead of the busy time
+    pub cycles: BTreeMap<String, (u64, u64)>,
+    /// The video memory used by the process in bytes
+    pub memory: u64,
+}
+
+impl GpuUsageStats {
+    /// Reads the GPU usage of the process at `proc_path`, keyed by the PCI
+    /// slot of the GPU (e. g. `0000:03:00.0`). DRM clients that are shared
+    /// across multiple file descriptors are only counted once.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file descriptors of the process could not be
+    /// traversed
+    pub async fn all_of(proc_path: &Path) -> Result<BTreeMap<String, Self>> {
+        let drm_fds = Self::drm_fds(proc_path).await?;
+        Ok(Self::of_fds(proc_path, &drm_fds).await)
+    }
+
+    /// Returns the file descriptors of the process at `proc_path` that point
+    /// to a DRM device, i. e. the ones worth passing to
+    /// [`GpuUsageStats::of_fds`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file descriptors of the process could not be
+    /// traversed
+    pub async fn drm_fds(proc_path: &Path) -> Result<Vec<OsString>> {
+        let mut drm_fds = Vec::new();
+
+        for fd_path in glob(&proc_path.join("fd/[0-9]*").to_string_lossy())
+            .context("unable to glob")?
+            .flatten()
+        {
+            let Ok(link) = async_std::fs::read_link(&fd_path).await else {
+                continue;
+            };
+
+            if let Some(fd) = fd_path
+                .file_name()
+                .filter(|_| link.starts_with("/dev/dri/"))
+            {
+                drm_fds.push(fd.to_os_string());
+            }
+        }
+
+        Ok(drm_fds)
+    }
+
+    /// Like [`GpuUsageStats::all_of`], but only reads the `fdinfo` of the
+    /// given file descriptors. File descriptors that have been closed or
+    /// reused for something else in the meantime are skipped.
+    pub async fn of_fds(proc_path: &Path, fds: &[OsString]) -> BTreeMap<String, Self> {
+        let mut seen_clients = HashSet::new();
+        let mut stats: BTreeMap<String, Self> = BTreeMap::new();
+
+        for fd in fds {
+            let Ok(fdinfo) = async_std::fs::read_to_string(proc_path.join("fdinfo").join(fd)).await
+            else {
+                continue;
+            };
+
+            if let Some((pdev, client_id, client_stats)) = Self::parse_fdinfo(&fdinfo) {
+                if seen_clients.insert((pdev.clone(), client_id)) {
+                    stats.entry(pdev).or_default().add(&client_stats);
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Parses the contents of the `/proc/<pid>/fdinfo/<fd>` file of a DRM
+    /// file descriptor into the PCI slot of the GPU, the DRM client ID and the
+    /// usage of that client. Returns `None` if the file descriptor does not
+    /// belong to a DRM client.
+    #[must_use]
+    pub fn parse_fdinfo(fdinfo: &str) -> Option<(String, u64, Self)> {
+        let mut pdev = None;
+        let mut client_id = None;
+
+        let mut engines = BTreeMap::new();
+        let mut capacities = BTreeMap::new();
+        let mut busy_cycles = BTreeMap::new();
+        let mut total_cycles = BTreeMap::new();
+        let mut memory_regions: BTreeMap<&str, u64> = BTreeMap::new();
+
+        for (key, value) in fdinfo
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim(), value.trim()))
+        {
+            if key == "drm-pdev" {
+                pdev = Some(value.to_string());
+            } else if key == "drm-client-id" {
+                client_id = value.parse::<u64>().ok();
+            } else if let Some(engine) = key.strip_prefix("drm-engine-capacity-") {
+                if let Ok(capacity) = value.parse::<u64>() {
+                    capacities.insert(engine, capacity.max(1));
+                }
+            } else if let Some(engine) = key.strip_prefix("drm-engine-") {
+                if let Some(Ok(time)) = value.strip_suffix(" ns").map(str::parse::<u64>) {
+                    engines.insert(engine, time);
+                }
+            } else if let Some(engine) = key.strip_prefix("drm-total-cycles-") {
+                if let Ok(cycles) = value.parse::<u64>() {
+                    total_cycles.insert(engine, cycles);
+                }
+            } else if let Some(engine) = key.strip_prefix("drm-cycles-") {
+                if let Ok(cycles) = value.parse::<u64>() {
+                    busy_cycles.insert(engine, cycles);
+                }
+            } else if let Some(region) = ["drm-memory-", "drm-resident-", "drm-total-"]
+                .iter()
+                .find_map(|prefix| key.strip_prefix(prefix))
+            {
+                // only count dedicated video memory, `amdgpu` calls it `vram`,
+                // `i915` calls it `local`
+                if region.starts_with("vram") || region.starts_with("local") {
+                    if let Some(bytes) = Self::parse_memory(value) {
+                        let entry = memory_regions.entry(region).or_default();
+                        *entry = (*entry).max(bytes);
+                    }
+                }
+            }
+        }
+
+        let engines = engines
+            .into_iter()
+            .map(|(engine, time)| {
+                (
+                    engine.to_string(),
+                    time / capacities.get(engine).copied().unwrap_or(1),
+                )
+            })
+            .collect();
+
+        let cycles = busy_cycles
+            .into_iter()
+            .filter_map(|(engine, busy)| {
+                total_cycles
+                    .get(engine)
+                    .map(|total| (engine.to_string(), (busy, *total)))
+            })
+            .collect();
+
+        Some((
+            pdev?,
+            client_id?,
+            Self {
+                engines,
+                cycles,
+                memory: memory_regions.values().sum(),
+            },
+        ))
+    }
+
+    /// Parses memory values like `1024 KiB`, which may also be given in bytes
+    /// or MiB
+    fn parse_memory(value: &str) -> Option<u64> {
+        let mut split = value.split_whitespace();
+        let amount = split.next()?.parse::<u64>().ok()?;
+        match split.next() {
+            None => Some(amount),
+            Some("KiB") => Some(amount * 1024),
+            Some("MiB") => Some(amount * 1024 * 1024),
+            Some(_) => None,
+        }
+    }
+
+    fn add(&mut self, other: &Self) {
+        for (engine, time) in &other.engines {
+            *self.engines.entry(engine.clone()).or_default() += time;
+        }
+        for (engine, (busy, total)) in &other.cycles {
+            let entry = self.cycles.entry(engine.clone()).or_default();
+            entry.0 += busy;
+            // the total cycles are those of the whole GPU and not of the
+            // client, so they must not be summed up
+            entry.1 = entry.1.max(*total);
+        }
+        self.memory += other.memory;
+    }
+}
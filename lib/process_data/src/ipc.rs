@@ -0,0 +1,103 @@
+use std::io::{ErrorKind, Read, Write};
+
+use anyhow::{bail, Context, Result};
+use async_std::io::{ReadExt, WriteExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::ScanOptions;
+
+/// A request to `resources-processes` running as a persistent helper (i. e.
+/// with `--persistent`). Every message in either direction is encoded using
+/// msgpack and prefixed by its length as a little-endian `u32`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Request {
+    /// Answered with a `Vec<ProcessData>` of all processes, see
+    /// [`ProcessScanner::scan`](crate::ProcessScanner::scan)
+    Processes(ScanOptions),
+    /// Answered with a `Result<Vec<OpenFile>, String>` for the process with
+    /// the given PID
+    OpenFiles(i32),
+}
+
+/// The length of the longest message that is accepted, anything longer is
+/// considered garbage rather than allocating as much as the prefix claims
+pub const MAX_MESSAGE_LENGTH: usize = 256 * 1024 * 1024;
+
+fn decode_length(length: [u8; 4]) -> Result<usize> {
+    let length = u32::from_le_bytes(length) as usize;
+    if length > MAX_MESSAGE_LENGTH {
+        bail!("message of {length} bytes is too long");
+    }
+    Ok(length)
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let body = rmp_serde::to_vec(value)?;
+    let mut message = u32::try_from(body.len())
+        .context("message too long")?
+        .to_le_bytes()
+        .to_vec();
+    message.extend(body);
+    Ok(message)
+}
+
+/// Writes `value` as a length-prefixed msgpack message to `writer`.
+///
+/// # Errors
+///
+/// Will return `Err` if `value` could not be encoded or written
+pub fn write_message<W: Write, T: Serialize>(writer: &mut W, value: &T) -> Result<()> {
+    writer.write_all(&encode(value)?)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a length-prefixed msgpack message from `reader`, returns `None` if
+/// `reader` has been closed before a new message started.
+///
+/// # Errors
+///
+/// Will return `Err` if the message could not be read or decoded
+pub fn read_message<R: Read, T: DeserializeOwned>(reader: &mut R) -> Result<Option<T>> {
+    let mut length = [0; 4];
+    match reader.read_exact(&mut length) {
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        result => result?,
+    }
+
+    let mut body = vec![0; decode_length(length)?];
+    reader.read_exact(&mut body)?;
+
+    Ok(Some(rmp_serde::from_slice(&body)?))
+}
+
+/// Async version of [`write_message`].
+///
+/// # Errors
+///
+/// Will return `Err` if `value` could not be encoded or written
+pub async fn write_message_async<W: async_std::io::Write + Unpin, T: Serialize>(
+    writer: &mut W,
+    value: &T,
+) -> Result<()> {
+    writer.write_all(&encode(value)?).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Async version of [`read_message`], a closed `reader` is an error here.
+///
+/// # Errors
+///
+/// Will return `Err` if the message could not be read or decoded
+pub async fn read_message_async<R: async_std::io::Read + Unpin, T: DeserializeOwned>(
+    reader: &mut R,
+) -> Result<T> {
+    let mut length = [0; 4];
+    reader.read_exact(&mut length).await?;
+
+    let mut body = vec![0; decode_length(length)?];
+    reader.read_exact(&mut body).await?;
+
+    Ok(rmp_serde::from_slice(&body)?)
+}
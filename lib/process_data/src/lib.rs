@@ -1,20 +1,551 @@
-use anyhow::{anyhow, Context, Result};
+pub mod gpu_usage;
+pub mod ipc;
+pub mod open_files;
+
+use anyhow::{anyhow, bail, Context, Result};
 use async_std::sync::Arc;
+use futures_util::future::join_all;
+use glob::glob;
+use gpu_usage::GpuUsageStats;
 use nparse::KVStrToJson;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, time::SystemTime};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ffi::OsString,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// How long the DRM file descriptors of a process are reused before all of
+/// its file descriptors are looked through again, resolving every one of
+/// them on every refresh is too expensive for processes with many files open
+const DRM_FDS_MAX_AGE: Duration = Duration::from_secs(10);
 
 static PAGESIZE: Lazy<usize> = Lazy::new(sysconf::pagesize);
 
 static UID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Uid:\s*(\d+)").unwrap());
 
+static ROOT: Lazy<PathBuf> = Lazy::new(|| {
+    std::env::var_os("RESOURCES_ROOT").map_or_else(|| PathBuf::from("/"), PathBuf::from)
+});
+
+/// Returns the directory that all procfs and sysfs paths are resolved against.
+///
+/// This is `/` unless the `RESOURCES_ROOT` environment variable is set, in which
+/// case the collectors read from e. g. `$RESOURCES_ROOT/proc` and
+/// `$RESOURCES_ROOT/sys` instead. This allows running Resources against a
+/// captured fixture tree from another machine.
+#[must_use]
+pub fn root() -> &'static Path {
+    ROOT.as_path()
+}
+
+/// Resolves an absolute path like `/proc/stat` against [`root()`].
+#[must_use]
+pub fn rooted<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    root().join(path.strip_prefix("/").unwrap_or(path))
+}
+
+/// Exit code of `resources-kill` and `resources-adjust` if the PID belongs to a
+/// different process than the one that was meant, i. e. its start time doesn't
+/// match anymore
+pub const IDENTITY_CHANGED_STATUS_CODE: i32 = 252;
+
+/// Reads the start time (field 22 of `/proc/<pid>/stat`) of the process with
+/// the PID `pid`
+#[must_use]
+pub fn starttime(pid: i32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    Stat::parse(&stat).ok().map(|stat| stat.starttime)
+}
+
+/// Maps an errno to the exit code that `resources-kill` and
+/// `resources-adjust` report for it
+#[must_use]
+pub fn errno_status(errno: i32) -> i32 {
+    match errno {
+        0 => 253,
+        _ => errno,
+    }
+}
+
+/// Returns the time of the monotonic clock in milliseconds. Unlike the wall
+/// clock it can't jump, so it's used for the timestamps of CPU time readings.
+/// It's shared by all processes on the system, which means that timestamps
+/// taken by `resources-processes` and by Resources itself are comparable.
+#[must_use]
+pub fn monotonic_millis() -> u64 {
+    let mut timespec = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut timespec) };
+    timespec.tv_sec as u64 * 1000 + timespec.tv_nsec as u64 / 1_000_000
+}
+
+/// Parses a list of CPUs like `0-3,6` as used in `/proc/<pid>/status` or
+/// `/sys/devices/system/cpu/online`.
+///
+/// # Errors
+///
+/// Will return `Err` if the list is malformed
+pub fn parse_cpu_list<S: AsRef<str>>(cpu_list: S) -> Result<Vec<usize>> {
+    let mut cpus = Vec::new();
+
+    for range in cpu_list
+        .as_ref()
+        .trim()
+        .split(',')
+        .filter(|s| !s.is_empty())
+    {
+        if let Some((start, end)) = range.split_once('-') {
+            let (start, end) = (start.trim().parse::<usize>()?, end.trim().parse::<usize>()?);
+            if start > end {
+                bail!("invalid CPU range {range}");
+            }
+            cpus.extend(start..=end);
+        } else {
+            cpus.push(range.trim().parse()?);
+        }
+    }
+
+    Ok(cpus)
+}
+
+/// Formats `cpus` as a list of CPUs like `0-3,6`, the reverse of
+/// [`parse_cpu_list`].
+#[must_use]
+pub fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut cpus = cpus.to_vec();
+    cpus.sort_unstable();
+    cpus.dedup();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The way a process is containerized. Apart from Flatpaks, every variant
+/// carries the identifier of the app or container the process belongs to.
 #[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Containerization {
     #[default]
     None,
     Flatpak,
+    /// The name of the snap
+    Snap(String),
+    /// The file name of the AppImage
+    AppImage(String),
+    /// The ID of the Docker container
+    Docker(String),
+    /// The ID of the Podman container
+    Podman(String),
+    /// The ID of the containerd container
+    Containerd(String),
+    /// The name of the LXC container
+    Lxc(String),
+    /// The name of the systemd-nspawn machine
+    Nspawn(String),
+}
+
+impl Containerization {
+    /// Detects container engines and snaps from the contents of
+    /// `/proc/<pid>/cgroup`, the innermost match wins for nested containers.
+    #[must_use]
+    pub fn from_cgroup<S: AsRef<str>>(cgroup: S) -> Option<Self> {
+        let cgroups_v2_line = cgroup.as_ref().lines().find(|s| s.starts_with("0::"))?;
+        Self::from_cgroup_path(&cgroups_v2_line[3..])
+    }
+
+    /// Like [`Containerization::from_cgroup`], but takes the path of the
+    /// process' cgroup v2 relative to the cgroup root.
+    #[must_use]
+    pub fn from_cgroup_path<S: AsRef<str>>(cgroup_path: S) -> Option<Self> {
+        let segments = cgroup_path
+            .as_ref()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+
+        segments.iter().enumerate().rev().find_map(|(i, segment)| {
+            let next_segment = segments.get(i + 1).map(|s| (*s).to_string());
+
+            if let Some(id) = segment
+                .strip_prefix("docker-")
+                .and_then(|s| s.strip_suffix(".scope"))
+            {
+                Some(Self::Docker(id.to_string()))
+            } else if let Some(id) = segment
+                .strip_prefix("libpod-")
+                .and_then(|s| s.strip_suffix(".scope"))
+                .filter(|id| !id.starts_with("conmon-"))
+            {
+                Some(Self::Podman(id.to_string()))
+            } else if let Some(id) = segment
+                .strip_prefix("cri-containerd-")
+                .and_then(|s| s.strip_suffix(".scope"))
+            {
+                Some(Self::Containerd(id.to_string()))
+            } else if let Some(name) = segment.strip_prefix("lxc.payload.") {
+                Some(Self::Lxc(name.to_string()))
+            } else if let Some(name) = segment
+                .strip_prefix("systemd-nspawn@")
+                .and_then(|s| s.strip_suffix(".service"))
+            {
+                Some(Self::Nspawn(
+                    unescape::unescape(name).unwrap_or_else(|| name.to_string()),
+                ))
+            } else if let Some(name) = segment
+                .strip_prefix("machine-")
+                .and_then(|s| s.strip_suffix(".scope"))
+                // libvirt registers its virtual machines the same way
+                .filter(|name| !name.starts_with("qemu"))
+            {
+                Some(Self::Nspawn(
+                    unescape::unescape(name).unwrap_or_else(|| name.to_string()),
+                ))
+            } else if let Some(name) = segment
+                .strip_prefix("snap.")
+                .and_then(|s| s.split('.').next())
+            {
+                Some(Self::Snap(name.to_string()))
+            } else {
+                // cgroupfs drivers don't use systemd units but plain
+                // directories named after the engine
+                match *segment {
+                    "docker" => next_segment.map(Self::Docker),
+                    "lxc" => next_segment.map(Self::Lxc),
+                    _ => None,
+                }
+            }
+        })
+    }
+
+    /// Detects snaps and AppImages from the contents of
+    /// `/proc/<pid>/environ`.
+    #[must_use]
+    pub fn from_environ<S: AsRef<str>>(environ: S) -> Option<Self> {
+        environ.as_ref().split('\0').find_map(|variable| {
+            if let Some(name) = variable.strip_prefix("SNAP_NAME=") {
+                Some(Self::Snap(name.to_string()))
+            } else {
+                variable
+                    .strip_prefix("APPIMAGE=")
+                    .and_then(|path| Path::new(path).file_name())
+                    .map(|file_name| Self::AppImage(file_name.to_string_lossy().to_string()))
+            }
+        })
+    }
+
+    /// Detects AppImages from the target of `/proc/<pid>/exe`, AppImages are
+    /// mounted at `/tmp/.mount_<NAME><RANDOM>` while they're running. Only the
+    /// first six characters of the file name make it into the mount point.
+    #[must_use]
+    pub fn from_exe<P: AsRef<Path>>(exe: P) -> Option<Self> {
+        exe.as_ref().components().find_map(|component| {
+            let name = component.as_os_str().to_str()?.strip_prefix(".mount_")?;
+            // the random suffix is always six characters long
+            let name = name.get(..name.len().checked_sub(6)?)?;
+            Some(Self::AppImage(name.to_string()))
+        })
+    }
+
+    /// Whether the process runs inside of a container rather than being
+    /// part of a packaged app like a Flatpak, a snap or an AppImage.
+    #[must_use]
+    pub fn is_container(&self) -> bool {
+        matches!(
+            self,
+            Self::Docker(_)
+                | Self::Podman(_)
+                | Self::Containerd(_)
+                | Self::Lxc(_)
+                | Self::Nspawn(_)
+        )
+    }
+
+    /// Returns the identifier of the app or container, if any.
+    #[must_use]
+    pub fn identifier(&self) -> Option<&str> {
+        match self {
+            Self::None | Self::Flatpak => None,
+            Self::Snap(id)
+            | Self::AppImage(id)
+            | Self::Docker(id)
+            | Self::Podman(id)
+            | Self::Containerd(id)
+            | Self::Lxc(id)
+            | Self::Nspawn(id) => Some(id),
+        }
+    }
+}
+
+/// Detailed memory usage of a process in bytes, gathered from
+/// `/proc/<pid>/smaps_rollup`.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryDetails {
+    /// Resident set size, all memory of the process that is currently in RAM
+    pub rss: usize,
+    /// Proportional set size, shared memory is split evenly between all
+    /// processes that are sharing it
+    pub pss: usize,
+    /// Unique set size, memory that is private to this process
+    pub uss: usize,
+    /// Resident memory that is shared with other processes
+    pub shared: usize,
+    /// Memory of the process that has been swapped out
+    pub swap: usize,
+}
+
+impl MemoryDetails {
+    /// Parses the contents of a `/proc/<pid>/smaps_rollup` file.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the RSS or PSS are missing or if a value could
+    /// not be parsed
+    pub fn from_smaps_rollup<S: AsRef<str>>(smaps_rollup: S) -> Result<Self> {
+        let (mut rss, mut pss) = (None, None);
+        let mut details = Self::default();
+
+        for line in smaps_rollup.as_ref().lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            // values are always given in kB
+            let parse_value = || -> Result<usize> {
+                Ok(value
+                    .split_whitespace()
+                    .next()
+                    .with_context(|| format!("no value for {key}"))?
+                    .parse::<usize>()?
+                    * 1024)
+            };
+
+            match key {
+                "Rss" => rss = Some(parse_value()?),
+                "Pss" => pss = Some(parse_value()?),
+                "Shared_Clean" | "Shared_Dirty" => details.shared += parse_value()?,
+                "Private_Clean" | "Private_Dirty" => details.uss += parse_value()?,
+                "Swap" => details.swap = parse_value()?,
+                _ => (),
+            }
+        }
+
+        let (Some(rss), Some(pss)) = (rss, pss) else {
+            bail!("smaps_rollup is missing Rss or Pss");
+        };
+        details.rss = rss;
+        details.pss = pss;
+
+        Ok(details)
+    }
+}
+
+impl std::ops::Add for MemoryDetails {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            rss: self.rss + rhs.rss,
+            pss: self.pss + rhs.pss,
+            uss: self.uss + rhs.uss,
+            shared: self.shared + rhs.shared,
+            swap: self.swap + rhs.swap,
+        }
+    }
+}
+
+/// The I/O scheduling class of a process, see `ioprio_set(2)`.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IoPriorityClass {
+    /// No class has been set explicitly, the process is treated as
+    /// `BestEffort` with a level derived from its nice value
+    #[default]
+    None,
+    RealTime,
+    BestEffort,
+    Idle,
+}
+
+/// The I/O priority of a process, consisting of a class and a level from 0
+/// (highest) to 7 (lowest). The level is meaningless for the `Idle` class.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoPriority {
+    pub class: IoPriorityClass,
+    pub level: u8,
+}
+
+impl IoPriority {
+    const CLASS_SHIFT: i32 = 13;
+    const LEVEL_MASK: i32 = (1 << Self::CLASS_SHIFT) - 1;
+
+    /// Decodes an I/O priority as returned by `ioprio_get(2)`.
+    #[must_use]
+    pub fn from_raw(raw: i32) -> Self {
+        let class = match raw >> Self::CLASS_SHIFT {
+            1 => IoPriorityClass::RealTime,
+            2 => IoPriorityClass::BestEffort,
+            3 => IoPriorityClass::Idle,
+            _ => IoPriorityClass::None,
+        };
+
+        Self {
+            class,
+            level: (raw & Self::LEVEL_MASK) as u8,
+        }
+    }
+
+    /// Encodes this I/O priority for use with `ioprio_set(2)`.
+    #[must_use]
+    pub fn to_raw(self) -> i32 {
+        let class = match self.class {
+            IoPriorityClass::None => 0,
+            IoPriorityClass::RealTime => 1,
+            IoPriorityClass::BestEffort => 2,
+            IoPriorityClass::Idle => 3,
+        };
+
+        (class << Self::CLASS_SHIFT) | (i32::from(self.level) & Self::LEVEL_MASK)
+    }
+
+    /// Returns the I/O priority of the process with the PID `pid`, `None` if
+    /// it could not be determined. This can't be read from procfs, so it is
+    /// never available when reading from a fixture tree.
+    #[must_use]
+    pub fn of(pid: i32) -> Option<Self> {
+        if root() != Path::new("/") {
+            return None;
+        }
+
+        // IOPRIO_WHO_PROCESS
+        let raw = unsafe { libc::syscall(libc::SYS_ioprio_get, 1, pid) };
+        (raw >= 0).then(|| Self::from_raw(raw as i32))
+    }
+}
+
+/// The fields of a `/proc/<pid>/stat` or `/proc/<pid>/task/<tid>/stat` file
+/// that are used by Resources, see `proc(5)`.
+struct Stat {
+    comm: String,
+    state: char,
+    parent_pid: i32,
+    /// The time spent in user and kernel mode in clock ticks
+    cpu_time: u64,
+    nice: i8,
+    thread_count: u32,
+    starttime: u64,
+    last_cpu: u32,
+    scheduling_policy: u32,
+}
+
+impl Stat {
+    fn parse(stat: &str) -> Result<Self> {
+        // the comm inside of the parentheses may contain spaces and
+        // parentheses itself, so only split what comes after it
+        let (pid_and_comm, fields) = stat.rsplit_once(')').context("malformed stat")?;
+        let comm = pid_and_comm
+            .split_once('(')
+            .context("no comm in stat")?
+            .1
+            .to_string();
+
+        // `fields[0]` is the third field as counted by proc(5)
+        let fields = fields.split_whitespace().collect::<Vec<_>>();
+        let field = |index: usize| {
+            fields
+                .get(index)
+                .copied()
+                .with_context(|| format!("no field {} in stat", index + 3))
+        };
+
+        Ok(Self {
+            comm,
+            state: field(0)?.chars().next().context("no state in stat")?,
+            parent_pid: field(1)?.parse()?,
+            cpu_time: field(11)?.parse::<u64>()? + field(12)?.parse::<u64>()?,
+            nice: field(16)?.parse()?,
+            thread_count: field(17)?.parse()?,
+            starttime: field(19)?.parse()?,
+            last_cpu: field(36)?.parse()?,
+            scheduling_policy: field(38)?.parse()?,
+        })
+    }
+}
+
+/// Returns the CPUs the process with the PID `pid` is allowed to run on, see
+/// `sched_getaffinity(2)`
+fn affinity_of(pid: i32) -> Option<Vec<usize>> {
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let result = unsafe {
+        libc::sched_getaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &mut cpu_set)
+    };
+    (result == 0).then(|| {
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &cpu_set) })
+            .collect()
+    })
+}
+
+/// Data about a single thread of a process, gathered from
+/// `/proc/<pid>/task/<tid>/`.
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadData {
+    pub tid: i32,
+    pub comm: String,
+    pub state: char,
+    pub cpu_time: u64,
+    pub cpu_time_timestamp: u64,
+    /// The CPU the thread has last been executed on
+    pub last_cpu: u32,
+}
+
+impl ThreadData {
+    /// Reads the data of the thread whose task directory is `task_path`, e. g.
+    /// `/proc/1234/task/1235`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the thread has exited or if its `stat` could not
+    /// be parsed
+    pub async fn try_from_path(task_path: PathBuf) -> Result<Self> {
+        let stat = Stat::parse(&async_std::fs::read_to_string(task_path.join("stat")).await?)?;
+
+        let tid = task_path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .context("invalid task path")?
+            .parse()?;
+
+        Ok(Self {
+            tid,
+            comm: stat.comm,
+            state: stat.state,
+            cpu_time: stat.cpu_time,
+            cpu_time_timestamp: monotonic_millis(),
+            last_cpu: stat.last_cpu,
+        })
+    }
 }
 
 /// Data that could be transferred using `resources-processes`, separated from
@@ -23,23 +554,61 @@ pub enum Containerization {
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProcessData {
     pub pid: i32,
+    pub parent_pid: i32,
     pub uid: u32,
     proc_path: PathBuf,
     pub comm: String,
     pub commandline: String,
+    /// The state as found in `/proc/<pid>/stat`, e. g. `R` for running or `Z`
+    /// for zombies
+    pub state: char,
     pub cpu_time: u64,
     pub cpu_time_timestamp: u64,
+    /// The time the process has been started at in clock ticks after boot,
+    /// together with the PID this identifies a process
+    pub starttime: u64,
+    /// The nice value from -20 (highest priority) to 19 (lowest priority)
+    pub nice: i8,
+    /// The scheduling policy, e. g. `SCHED_OTHER` (0) or `SCHED_BATCH` (3)
+    pub scheduling_policy: u32,
+    /// `None` if the I/O priority could not be determined
+    pub io_priority: Option<IoPriority>,
+    /// The CPU the process has last been executed on
+    pub last_cpu: u32,
+    /// The CPUs the process is allowed to run on, i. e. its affinity mask as
+    /// returned by `sched_getaffinity(2)`, `None` if it could not be read
+    pub affinity: Option<Vec<usize>>,
     pub memory_usage: usize,
+    /// `None` if `smaps_rollup` is not available, we lack the permission to
+    /// read it or it hasn't been asked for, see [`ScanOptions`]
+    pub memory_details: Option<MemoryDetails>,
     pub cgroup: Option<String>,
+    /// The path of the process' cgroup v2 relative to the cgroup root, e. g.
+    /// `/user.slice/user-1000.slice/user@1000.service/app.slice/foo.scope`
+    pub cgroup_path: Option<String>,
     pub containerization: Containerization,
     pub read_bytes: Option<u64>,
     pub read_bytes_timestamp: Option<u64>,
     pub write_bytes: Option<u64>,
     pub write_bytes_timestamp: Option<u64>,
+    /// The number of threads as found in `/proc/<pid>/stat`
+    pub thread_count: u32,
+    /// Only read if asked for, see [`ScanOptions::thread_pids`]
+    pub threads: Vec<ThreadData>,
+    /// The usage of every GPU the process uses, keyed by the GPU's PCI slot
+    pub gpu_usage_stats: BTreeMap<String, GpuUsageStats>,
+    pub gpu_usage_stats_timestamp: u64,
+    /// The DRM file descriptors of the process and when they've been looked
+    /// for, see [`DRM_FDS_MAX_AGE`]
+    #[serde(skip)]
+    drm_fds: Option<(Instant, Vec<OsString>)>,
 }
 
 impl ProcessData {
-    fn sanitize_cgroup<S: AsRef<str>>(cgroup: S) -> Option<String> {
+    /// Extracts the name of the app (or service) from the contents of a
+    /// `/proc/<pid>/cgroup` file, if the process lives in a systemd scope or
+    /// service.
+    pub fn sanitize_cgroup<S: AsRef<str>>(cgroup: S) -> Option<String> {
         let cgroups_v2_line = cgroup.as_ref().split('\n').find(|s| s.starts_with("0::"))?;
         if cgroups_v2_line.ends_with(".scope") {
             let cgroups_segments: Vec<&str> = cgroups_v2_line.split('-').collect();
@@ -72,9 +641,55 @@ impl ProcessData {
         }
     }
 
-    async fn get_uid(proc_path: &PathBuf) -> Result<u32> {
-        let status = async_std::fs::read_to_string(proc_path.join("status")).await?;
-        if let Some(captures) = UID_REGEX.captures(&status) {
+    /// Returns a `Vec` containing the `ProcessData` of every process that can
+    /// be found in the procfs below [`root()`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if procfs could not be traversed
+    pub async fn all() -> Result<Vec<Self>> {
+        let mut handles = vec![];
+        for entry in glob(&rooted("/proc/[0-9]*/").to_string_lossy())
+            .context("unable to glob")?
+            .flatten()
+        {
+            handles.push(async_std::task::spawn(Self::try_from_path(entry)));
+        }
+
+        Ok(join_all(handles).await.into_iter().flatten().collect())
+    }
+
+    /// Reads the memory details of the process from `smaps_rollup`, they're
+    /// left out by [`ProcessData::refresh_volatile`] since the kernel has to
+    /// walk all memory mappings of the process to produce them.
+    pub async fn refresh_memory_details(&mut self) {
+        self.memory_details = async_std::fs::read_to_string(self.proc_path.join("smaps_rollup"))
+            .await
+            .ok()
+            .and_then(|smaps_rollup| MemoryDetails::from_smaps_rollup(smaps_rollup).ok());
+    }
+
+    /// Reads the threads of the process, they're left out by
+    /// [`ProcessData::refresh_volatile`] since reading them is expensive.
+    pub async fn refresh_threads(&mut self) {
+        self.threads = Self::get_threads(&self.proc_path).await.unwrap_or_default();
+    }
+
+    async fn get_threads(proc_path: &Path) -> Result<Vec<ThreadData>> {
+        let mut handles = vec![];
+        for entry in glob(&proc_path.join("task/[0-9]*/").to_string_lossy())
+            .context("unable to glob")?
+            .flatten()
+        {
+            handles.push(async_std::task::spawn(ThreadData::try_from_path(entry)));
+        }
+
+        // threads that have exited in the meantime are skipped
+        Ok(join_all(handles).await.into_iter().flatten().collect())
+    }
+
+    fn get_uid(status: &str) -> Result<u32> {
+        if let Some(captures) = UID_REGEX.captures(status) {
             let first_num_str = captures.get(1).context("no uid found")?;
             first_num_str
                 .as_str()
@@ -85,24 +700,28 @@ impl ProcessData {
         }
     }
 
+    /// Reads all data of the process at `proc_path`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the process has exited or if one of its essential
+    /// files could not be parsed
     pub async fn try_from_path(proc_path: PathBuf) -> Result<Self> {
-        // Stat
-        let shared_proc_path = Arc::new(proc_path.clone());
-        let stat = async_std::task::spawn(async move {
-            async_std::fs::read_to_string(shared_proc_path.join("stat")).await
-        });
-
-        // Statm
-        let shared_proc_path = Arc::new(proc_path.clone());
-        let statm = async_std::task::spawn(async move {
-            async_std::fs::read_to_string(shared_proc_path.join("statm")).await
-        });
+        let mut process_data = Self::read(proc_path).await?;
+        process_data.refresh_threads().await;
+        process_data.refresh_memory_details().await;
+        Ok(process_data)
+    }
 
-        // Comm
-        let shared_proc_path = Arc::new(proc_path.clone());
-        let comm = async_std::task::spawn(async move {
-            async_std::fs::read_to_string(shared_proc_path.join("comm")).await
-        });
+    /// Like [`ProcessData::try_from_path`], but leaves out the threads and
+    /// the memory details.
+    async fn read(proc_path: PathBuf) -> Result<Self> {
+        let pid = proc_path
+            .file_name()
+            .ok_or_else(|| anyhow!(""))?
+            .to_str()
+            .ok_or_else(|| anyhow!(""))?
+            .parse()?;
 
         // Cmdline
         let shared_proc_path = Arc::new(proc_path.clone());
@@ -116,54 +735,145 @@ impl ProcessData {
             async_std::fs::read_to_string(shared_proc_path.join("cgroup")).await
         });
 
-        // IO
+        // Status
         let shared_proc_path = Arc::new(proc_path.clone());
+        let status = async_std::task::spawn(async move {
+            async_std::fs::read_to_string(shared_proc_path.join("status")).await
+        });
+
+        let commandline = commandline.await?;
+        let cgroup = cgroup.await?;
+        let status = status.await?;
+
+        let mut process_data = Self {
+            pid,
+            uid: Self::get_uid(&status)?,
+            commandline,
+            affinity: status
+                .lines()
+                .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+                .and_then(|cpu_list| parse_cpu_list(cpu_list).ok()),
+            cgroup: Self::sanitize_cgroup(&cgroup),
+            cgroup_path: cgroup
+                .lines()
+                .find_map(|line| line.strip_prefix("0::"))
+                .map(str::to_string),
+            proc_path,
+            ..Default::default()
+        };
+
+        process_data.refresh_volatile().await?;
+
+        process_data.containerization = process_data.detect_containerization().await;
+
+        Ok(process_data)
+    }
+
+    /// Detects the containerization of the process, `environ` and `exe` are
+    /// only looked at if neither Flatpak nor the cgroup give it away.
+    async fn detect_containerization(&self) -> Containerization {
+        let root = async_std::path::PathBuf::from(self.proc_path.join("root"));
+        if root.join(".flatpak-info").exists().await {
+            return Containerization::Flatpak;
+        }
+
+        if let Some(containerization) = self
+            .cgroup_path
+            .as_ref()
+            .and_then(Containerization::from_cgroup_path)
+        {
+            return containerization;
+        }
+
+        // kernel threads have neither an environment nor an executable
+        if self.commandline.is_empty() {
+            return Containerization::None;
+        }
+
+        if let Some(containerization) =
+            async_std::fs::read_to_string(self.proc_path.join("environ"))
+                .await
+                .ok()
+                .and_then(Containerization::from_environ)
+        {
+            return containerization;
+        }
+
+        async_std::fs::read_link(self.proc_path.join("exe"))
+            .await
+            .ok()
+            .and_then(Containerization::from_exe)
+            .unwrap_or_default()
+    }
+
+    /// Re-reads the data of the process that changes during its lifetime,
+    /// i. e. everything found in `stat`, `statm` and `io` as well as its GPU
+    /// usage, I/O priority and CPU affinity. Everything else, e. g. its
+    /// cgroup and with it its containerization, is only read once by
+    /// [`ProcessData::try_from_path`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the process has exited or if its `stat` or
+    /// `statm` could not be read
+    pub async fn refresh_volatile(&mut self) -> Result<()> {
+        // Stat
+        let shared_proc_path = Arc::new(self.proc_path.clone());
+        let stat = async_std::task::spawn(async move {
+            async_std::fs::read_to_string(shared_proc_path.join("stat")).await
+        });
+
+        // Statm
+        let shared_proc_path = Arc::new(self.proc_path.clone());
+        let statm = async_std::task::spawn(async move {
+            async_std::fs::read_to_string(shared_proc_path.join("statm")).await
+        });
+
+        // IO
+        let shared_proc_path = Arc::new(self.proc_path.clone());
         let io = async_std::task::spawn(async move {
             async_std::fs::read_to_string(shared_proc_path.join("io")).await
         });
 
-        let stat = stat.await?;
+        let stat = Stat::parse(&stat.await?)?;
         let statm = statm.await?;
-        let comm = comm.await?;
-        let commandline = commandline.await?;
-        let cgroup = cgroup.await?;
 
-        let pid = proc_path
-            .file_name()
-            .ok_or_else(|| anyhow!(""))?
-            .to_str()
-            .ok_or_else(|| anyhow!(""))?
-            .parse()?;
+        let drm_fds = match self.drm_fds.take() {
+            Some((found_at, drm_fds)) if found_at.elapsed() < DRM_FDS_MAX_AGE => {
+                Some((found_at, drm_fds))
+            }
+            _ => GpuUsageStats::drm_fds(&self.proc_path)
+                .await
+                .ok()
+                .map(|drm_fds| (Instant::now(), drm_fds)),
+        };
 
-        let uid = Self::get_uid(&proc_path).await?;
+        let gpu_usage_stats = match &drm_fds {
+            Some((_, drm_fds)) => GpuUsageStats::of_fds(&self.proc_path, drm_fds).await,
+            None => BTreeMap::new(),
+        };
 
-        let stat = stat
-            .split(' ')
-            .map(std::string::ToString::to_string)
-            .collect::<Vec<_>>();
+        let gpu_usage_stats_timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_millis() as u64;
 
         let statm = statm
             .split(' ')
             .map(std::string::ToString::to_string)
             .collect::<Vec<_>>();
 
-        let comm = comm.replace('\n', "");
+        let cpu_time_timestamp = monotonic_millis();
 
-        let cpu_time = stat[13].parse::<u64>()? + stat[14].parse::<u64>()?;
+        let io_priority = IoPriority::of(self.pid);
 
-        let cpu_time_timestamp = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)?
-            .as_millis() as u64;
+        // the affinity can be changed by other processes at any time, reading
+        // it through a syscall spares us from reading `status` again
+        if self.proc_path.starts_with("/proc") {
+            self.affinity = affinity_of(self.pid);
+        }
 
         let memory_usage = (statm[1].parse::<usize>()? - statm[2].parse::<usize>()?) * *PAGESIZE;
 
-        let cgroup = Self::sanitize_cgroup(cgroup);
-
-        let containerization = match &proc_path.join("root").join(".flatpak-info").exists() {
-            true => Containerization::Flatpak,
-            false => Containerization::None,
-        };
-
         let (mut read_bytes, mut read_bytes_timestamp, mut write_bytes, mut write_bytes_timestamp) =
             (None, None, None, None);
 
@@ -205,21 +915,143 @@ impl ProcessData {
             };
         }
 
-        Ok(Self {
-            pid,
-            uid,
-            comm,
-            commandline,
-            cpu_time,
-            cpu_time_timestamp,
-            memory_usage,
-            cgroup,
-            proc_path,
-            containerization,
-            read_bytes,
-            read_bytes_timestamp,
-            write_bytes,
-            write_bytes_timestamp,
-        })
+        self.parent_pid = stat.parent_pid;
+        self.comm = stat.comm;
+        self.state = stat.state;
+        self.cpu_time = stat.cpu_time;
+        self.cpu_time_timestamp = cpu_time_timestamp;
+        self.starttime = stat.starttime;
+        self.nice = stat.nice;
+        self.scheduling_policy = stat.scheduling_policy;
+        self.io_priority = io_priority;
+        self.last_cpu = stat.last_cpu;
+        self.thread_count = stat.thread_count;
+        self.memory_usage = memory_usage;
+        self.read_bytes = read_bytes;
+        self.read_bytes_timestamp = read_bytes_timestamp;
+        self.write_bytes = write_bytes;
+        self.write_bytes_timestamp = write_bytes_timestamp;
+        self.gpu_usage_stats = gpu_usage_stats;
+        self.drm_fds = drm_fds;
+        self.gpu_usage_stats_timestamp = gpu_usage_stats_timestamp;
+
+        Ok(())
+    }
+}
+
+/// The data that [`ProcessScanner::scan`] reads only for some processes
+/// because it's expensive to read for all of them.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanOptions {
+    /// The processes whose threads are read, e. g. because they're shown in
+    /// a dialog
+    pub thread_pids: HashSet<i32>,
+    /// Whether the memory details of all processes are read, e. g. because
+    /// their PSS is shown
+    pub memory_details: bool,
+    /// The processes whose memory details are read even if `memory_details`
+    /// is `false`
+    pub memory_details_pids: HashSet<i32>,
+}
+
+/// Gathers the `ProcessData` of all processes like [`ProcessData::all`], but
+/// keeps the data of known processes between scans so that only their
+/// volatile files have to be read again, see
+/// [`ProcessData::refresh_volatile`]. Threads and memory details are only
+/// read if asked for, see [`ScanOptions`]. A process is known if its PID, start
+/// time and `comm` are unchanged, so reused PIDs and processes that have
+/// called `exec` are read in full again.
+#[derive(Debug, Default)]
+pub struct ProcessScanner {
+    cache: HashMap<i32, ProcessData>,
+}
+
+impl ProcessScanner {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a `Vec` containing the `ProcessData` of every process that can
+    /// be found in the procfs below [`root()`]. Threads and memory details
+    /// are only read for the processes `options` asks for.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if procfs could not be traversed
+    pub async fn scan(&mut self, options: &ScanOptions) -> Result<Vec<ProcessData>> {
+        let mut handles = vec![];
+        for entry in glob(&rooted("/proc/[0-9]*/").to_string_lossy())
+            .context("unable to glob")?
+            .flatten()
+        {
+            let Some(pid) = entry
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .and_then(|pid| pid.parse::<i32>().ok())
+            else {
+                continue;
+            };
+            let cached = self.cache.remove(&pid);
+            let threads = options.thread_pids.contains(&pid);
+            let memory_details =
+                options.memory_details || options.memory_details_pids.contains(&pid);
+            handles.push(async_std::task::spawn(Self::scan_process(
+                entry,
+                cached,
+                threads,
+                memory_details,
+            )));
+        }
+
+        let processes: Vec<ProcessData> = join_all(handles).await.into_iter().flatten().collect();
+
+        // processes that have exited in the meantime are dropped from the
+        // cache here
+        self.cache = processes
+            .iter()
+            .map(|process| (process.pid, process.clone()))
+            .collect();
+
+        Ok(processes)
+    }
+
+    async fn scan_process(
+        proc_path: PathBuf,
+        cached: Option<ProcessData>,
+        threads: bool,
+        memory_details: bool,
+    ) -> Result<ProcessData> {
+        let mut process_data = match cached {
+            Some(cached) => match Self::refresh_cached(cached).await {
+                Some(process_data) => process_data,
+                None => ProcessData::read(proc_path).await?,
+            },
+            None => ProcessData::read(proc_path).await?,
+        };
+
+        if threads {
+            process_data.refresh_threads().await;
+        } else {
+            process_data.threads.clear();
+        }
+
+        if memory_details {
+            process_data.refresh_memory_details().await;
+        } else {
+            process_data.memory_details = None;
+        }
+
+        Ok(process_data)
+    }
+
+    /// Refreshes the volatile data of a known process, returns `None` if it
+    /// has ended or its PID now belongs to another process
+    async fn refresh_cached(mut process_data: ProcessData) -> Option<ProcessData> {
+        let (starttime, comm) = (process_data.starttime, process_data.comm.clone());
+        (process_data.refresh_volatile().await.is_ok()
+            && process_data.starttime == starttime
+            && process_data.comm == comm)
+            .then_some(process_data)
     }
 }
@@ -0,0 +1,309 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use glob::glob;
+use serde::{Deserialize, Serialize};
+
+use crate::rooted;
+
+/// What a file descriptor of a process refers to.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileDescriptorTarget {
+    /// A regular file, a directory or a device
+    Path(String),
+    /// A socket, `None` if it could not be found in the socket tables of the
+    /// process' network namespace
+    Socket(u64, Option<SocketInfo>),
+    /// A pipe with the given inode
+    Pipe(u64),
+    /// Anything else, e. g. `anon_inode:[eventfd]`
+    Other(String),
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SocketProtocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl SocketProtocol {
+    const ALL: [Self; 5] = [Self::Tcp, Self::Tcp6, Self::Udp, Self::Udp6, Self::Unix];
+
+    fn file_name(self) -> &'static str {
+        match self {
+            SocketProtocol::Tcp => "tcp",
+            SocketProtocol::Tcp6 => "tcp6",
+            SocketProtocol::Udp => "udp",
+            SocketProtocol::Udp6 => "udp6",
+            SocketProtocol::Unix => "unix",
+        }
+    }
+}
+
+/// A socket as found in `/proc/<pid>/net/{tcp,tcp6,udp,udp6,unix}`.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocketInfo {
+    pub protocol: SocketProtocol,
+    /// The local address and port of internet sockets, the path of Unix
+    /// sockets (empty for unnamed ones)
+    pub local_address: String,
+    /// The remote address and port of internet sockets, empty for Unix sockets
+    pub remote_address: String,
+    /// The state of the socket in the naming of the kernel, e. g. `LISTEN`
+    pub state: String,
+}
+
+/// A file descriptor of a process as found in `/proc/<pid>/fd` and
+/// `/proc/<pid>/fdinfo`.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenFile {
+    pub fd: i32,
+    pub target: FileDescriptorTarget,
+    /// The file offset, `None` if `fdinfo` could not be read
+    pub position: Option<u64>,
+    /// The flags the file has been opened with (like `O_RDWR`), `None` if
+    /// `fdinfo` could not be read
+    pub flags: Option<u32>,
+}
+
+impl OpenFile {
+    /// Returns all open file descriptors of the process with the PID `pid`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the process does not exist or if we're not
+    /// permitted to look at its file descriptors
+    pub async fn all_of(pid: i32) -> Result<Vec<Self>> {
+        let proc_path = rooted(format!("/proc/{pid}"));
+
+        // glob silently skips directories it can't read, so make sure that we
+        // are actually permitted to look at the file descriptors
+        async_std::fs::read_dir(proc_path.join("fd"))
+            .await
+            .map(drop)
+            .with_context(|| format!("unable to read file descriptors of {pid}"))?;
+
+        let sockets = Self::sockets(&proc_path).await;
+
+        let mut open_files = Vec::new();
+        for fd_path in glob(&proc_path.join("fd/[0-9]*").to_string_lossy())
+            .context("unable to glob")?
+            .flatten()
+        {
+            // file descriptors that have been closed in the meantime are
+            // skipped
+            if let Ok(open_file) = Self::try_from_path(&fd_path, &sockets).await {
+                open_files.push(open_file);
+            }
+        }
+
+        open_files.sort_by_key(|open_file| open_file.fd);
+
+        Ok(open_files)
+    }
+
+    async fn try_from_path(fd_path: &Path, sockets: &HashMap<u64, SocketInfo>) -> Result<Self> {
+        let fd = fd_path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .context("invalid fd path")?
+            .parse::<i32>()?;
+
+        let link = async_std::fs::read_link(fd_path)
+            .await?
+            .to_string_lossy()
+            .to_string();
+
+        let target = Self::parse_target(&link, sockets);
+
+        let fdinfo_path: PathBuf = fd_path
+            .parent()
+            .and_then(Path::parent)
+            .context("invalid fd path")?
+            .join("fdinfo")
+            .join(fd.to_string());
+
+        let (position, flags) = match async_std::fs::read_to_string(fdinfo_path).await {
+            Ok(fdinfo) => Self::parse_fdinfo(&fdinfo),
+            Err(_) => (None, None),
+        };
+
+        Ok(Self {
+            fd,
+            target,
+            position,
+            flags,
+        })
+    }
+
+    fn parse_target(link: &str, sockets: &HashMap<u64, SocketInfo>) -> FileDescriptorTarget {
+        let inode = |prefix: &str| {
+            link.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|inode| inode.parse::<u64>().ok())
+        };
+
+        if let Some(inode) = inode("socket:[") {
+            FileDescriptorTarget::Socket(inode, sockets.get(&inode).cloned())
+        } else if let Some(inode) = inode("pipe:[") {
+            FileDescriptorTarget::Pipe(inode)
+        } else if link.starts_with('/') {
+            FileDescriptorTarget::Path(link.to_string())
+        } else {
+            FileDescriptorTarget::Other(link.to_string())
+        }
+    }
+
+    /// Parses the position and flags out of the contents of a
+    /// `/proc/<pid>/fdinfo/<fd>` file.
+    #[must_use]
+    pub fn parse_fdinfo(fdinfo: &str) -> (Option<u64>, Option<u32>) {
+        let (mut position, mut flags) = (None, None);
+
+        for line in fdinfo.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                match key {
+                    "pos" => position = value.trim().parse().ok(),
+                    // flags are given in octal
+                    "flags" => flags = u32::from_str_radix(value.trim(), 8).ok(),
+                    _ => (),
+                }
+            }
+        }
+
+        (position, flags)
+    }
+
+    /// Reads all sockets in the network namespace of the process at
+    /// `proc_path`, keyed by their inode. Socket tables that can't be read are
+    /// skipped.
+    async fn sockets(proc_path: &Path) -> HashMap<u64, SocketInfo> {
+        let mut sockets = HashMap::new();
+
+        for protocol in SocketProtocol::ALL {
+            let Ok(table) =
+                async_std::fs::read_to_string(proc_path.join("net").join(protocol.file_name()))
+                    .await
+            else {
+                continue;
+            };
+
+            sockets.extend(parse_socket_table(&table, protocol));
+        }
+
+        sockets
+    }
+}
+
+/// Parses the contents of one of the `/proc/net/{tcp,tcp6,udp,udp6,unix}`
+/// files into a `Vec` of inodes and their sockets. Malformed lines are
+/// skipped.
+#[must_use]
+pub fn parse_socket_table(table: &str, protocol: SocketProtocol) -> Vec<(u64, SocketInfo)> {
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            if protocol == SocketProtocol::Unix {
+                parse_unix_socket_line(line).ok()
+            } else {
+                parse_inet_socket_line(line, protocol).ok()
+            }
+        })
+        .collect()
+}
+
+fn parse_inet_socket_line(line: &str, protocol: SocketProtocol) -> Result<(u64, SocketInfo)> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+
+    let local_address = parse_inet_address(fields.get(1).context("no local address")?)?;
+    let remote_address = parse_inet_address(fields.get(2).context("no remote address")?)?;
+
+    let state = match u8::from_str_radix(fields.get(3).context("no state")?, 16)? {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        // unconnected UDP sockets are in this state, too
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        0x0C => "NEW_SYN_RECV",
+        _ => "UNKNOWN",
+    };
+
+    let inode = fields.get(9).context("no inode")?.parse()?;
+
+    Ok((
+        inode,
+        SocketInfo {
+            protocol,
+            local_address: local_address.to_string(),
+            remote_address: remote_address.to_string(),
+            state: state.to_string(),
+        },
+    ))
+}
+
+/// Parses addresses like `0100007F:0277` or
+/// `00000000000000000000000001000000:0277`. The kernel prints the address in
+/// 32 bit words in host byte order and the port in network byte order.
+fn parse_inet_address(address: &str) -> Result<SocketAddr> {
+    let (ip, port) = address.split_once(':').context("no port in address")?;
+    let port = u16::from_str_radix(port, 16)?;
+
+    let mut bytes = Vec::with_capacity(16);
+    for word in ip.as_bytes().chunks(8) {
+        let word = u32::from_str_radix(std::str::from_utf8(word)?, 16)?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    let ip = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(bytes.as_slice())?)),
+        _ => bail!("invalid address length"),
+    };
+
+    Ok(SocketAddr::new(ip, port))
+}
+
+fn parse_unix_socket_line(line: &str) -> Result<(u64, SocketInfo)> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+
+    // __SO_ACCEPTCON
+    let listening = u32::from_str_radix(fields.get(3).context("no flags")?, 16)? & 0x0001_0000 != 0;
+
+    let state = match u8::from_str_radix(fields.get(5).context("no state")?, 16)? {
+        _ if listening => "LISTEN",
+        0x01 => "UNCONNECTED",
+        0x02 => "CONNECTING",
+        0x03 => "CONNECTED",
+        0x04 => "DISCONNECTING",
+        _ => "UNKNOWN",
+    };
+
+    let inode = fields.get(6).context("no inode")?.parse()?;
+
+    let path = fields.get(7).copied().unwrap_or_default();
+
+    Ok((
+        inode,
+        SocketInfo {
+            protocol: SocketProtocol::Unix,
+            local_address: path.to_string(),
+            remote_address: String::new(),
+            state: state.to_string(),
+        },
+    ))
+}
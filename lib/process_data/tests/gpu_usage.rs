@@ -0,0 +1,40 @@
+use process_data::gpu_usage::GpuUsageStats;
+
+#[test]
+fn i915_fdinfo() {
+    let fdinfo = "pos:\t0\nflags:\t02100002\ndrm-driver:\ti915\ndrm-pdev:\t0000:00:02.0\n\
+                  drm-client-id:\t7\ndrm-engine-render:\t9288864723 ns\n\
+                  drm-engine-video:\t2000000 ns\ndrm-engine-capacity-video:\t2\n\
+                  drm-total-local0:\t64 MiB\ndrm-resident-local0:\t32 MiB\n\
+                  drm-total-system0:\t16 MiB\n";
+
+    let (pdev, client_id, stats) = GpuUsageStats::parse_fdinfo(fdinfo).unwrap();
+
+    assert_eq!(pdev, "0000:00:02.0");
+    assert_eq!(client_id, 7);
+    assert_eq!(stats.engines.get("render"), Some(&9_288_864_723));
+    assert_eq!(stats.engines.get("video"), Some(&1_000_000));
+    assert!(stats.cycles.is_empty());
+    assert_eq!(stats.memory, 64 * 1024 * 1024);
+}
+
+#[test]
+fn xe_fdinfo() {
+    let fdinfo = "drm-driver:\txe\ndrm-pdev:\t0000:03:00.0\ndrm-client-id:\t12\n\
+                  drm-total-vram0:\t2048 KiB\ndrm-cycles-rcs:\t28257900\n\
+                  drm-total-cycles-rcs:\t7655183225\ndrm-cycles-bcs:\t0\n";
+
+    let (_, _, stats) = GpuUsageStats::parse_fdinfo(fdinfo).unwrap();
+
+    assert_eq!(stats.cycles.get("rcs"), Some(&(28_257_900, 7_655_183_225)));
+    assert_eq!(stats.cycles.get("bcs"), None);
+    assert_eq!(stats.memory, 2048 * 1024);
+}
+
+#[test]
+fn non_drm_fdinfo() {
+    assert_eq!(
+        GpuUsageStats::parse_fdinfo("pos:\t0\nflags:\t02100002\nmnt_id:\t25\n"),
+        None
+    );
+}
@@ -0,0 +1,46 @@
+use process_data::{
+    ipc::{self, Request},
+    ScanOptions,
+};
+
+#[test]
+fn message_round_trip() {
+    let mut buffer = Vec::new();
+    ipc::write_message(&mut buffer, &Request::OpenFiles(2417)).unwrap();
+    let options = ScanOptions {
+        thread_pids: [2417].into(),
+        memory_details: true,
+        ..Default::default()
+    };
+    ipc::write_message(&mut buffer, &Request::Processes(options.clone())).unwrap();
+
+    let mut reader = buffer.as_slice();
+    assert_eq!(
+        ipc::read_message::<_, Request>(&mut reader).unwrap(),
+        Some(Request::OpenFiles(2417))
+    );
+    assert_eq!(
+        ipc::read_message::<_, Request>(&mut reader).unwrap(),
+        Some(Request::Processes(options))
+    );
+    assert_eq!(ipc::read_message::<_, Request>(&mut reader).unwrap(), None);
+}
+
+#[async_std::test]
+async fn async_message_round_trip() {
+    let mut buffer = Vec::new();
+    ipc::write_message(&mut buffer, &Ok::<_, String>(vec![1_u64, 2, 3])).unwrap();
+
+    let mut reader = buffer.as_slice();
+    let message: Result<Vec<u64>, String> = ipc::read_message_async(&mut reader).await.unwrap();
+    assert_eq!(message, Ok(vec![1, 2, 3]));
+    assert!(ipc::read_message_async::<_, Request>(&mut reader)
+        .await
+        .is_err());
+}
+
+#[test]
+fn overlong_message_is_rejected() {
+    let mut reader: &[u8] = &[0xff, 0xff, 0xff, 0xff];
+    assert!(ipc::read_message::<_, Request>(&mut reader).is_err());
+}
@@ -0,0 +1,106 @@
+use process_data::open_files::{
+    parse_socket_table, FileDescriptorTarget, OpenFile, SocketInfo, SocketProtocol,
+};
+
+#[test]
+fn fdinfo() {
+    assert_eq!(
+        OpenFile::parse_fdinfo("pos:\t32768\nflags:\t02100002\nmnt_id:\t31\n"),
+        (Some(32768), Some(0o2_100_002))
+    );
+    assert_eq!(OpenFile::parse_fdinfo(""), (None, None));
+}
+
+#[test]
+fn tcp_socket_table() {
+    let table = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n   \
+                 0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1\n";
+
+    assert_eq!(
+        parse_socket_table(table, SocketProtocol::Tcp),
+        vec![(
+            23456,
+            SocketInfo {
+                protocol: SocketProtocol::Tcp,
+                local_address: "127.0.0.1:631".into(),
+                remote_address: "0.0.0.0:0".into(),
+                state: "LISTEN".into(),
+            }
+        )]
+    );
+}
+
+// `RESOURCES_ROOT` is only evaluated once per process, so everything that
+// depends on it lives in this test binary and is set up in a single test.
+#[async_std::test]
+async fn open_files_below_root() {
+    let fixture_root = concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/fixtures/desktop");
+    std::env::set_var("RESOURCES_ROOT", fixture_root);
+
+    let open_files = OpenFile::all_of(2417).await.unwrap();
+
+    let targets: Vec<(i32, FileDescriptorTarget)> = open_files
+        .iter()
+        .map(|open_file| (open_file.fd, open_file.target.clone()))
+        .collect();
+
+    assert_eq!(
+        targets,
+        vec![
+            (0, FileDescriptorTarget::Path("/dev/null".into())),
+            (
+                3,
+                FileDescriptorTarget::Path(
+                    "/home/user/.mozilla/firefox/abcd1234.default-release/places.sqlite".into()
+                )
+            ),
+            (
+                4,
+                FileDescriptorTarget::Socket(
+                    40001,
+                    Some(SocketInfo {
+                        protocol: SocketProtocol::Tcp,
+                        local_address: "192.168.1.20:51234".into(),
+                        remote_address: "93.184.216.34:443".into(),
+                        state: "ESTABLISHED".into(),
+                    })
+                )
+            ),
+            (
+                5,
+                FileDescriptorTarget::Socket(
+                    40002,
+                    Some(SocketInfo {
+                        protocol: SocketProtocol::Unix,
+                        local_address: "/run/user/1000/bus".into(),
+                        remote_address: String::new(),
+                        state: "CONNECTED".into(),
+                    })
+                )
+            ),
+            (6, FileDescriptorTarget::Pipe(40003)),
+            (
+                7,
+                FileDescriptorTarget::Other("anon_inode:[eventfd]".into())
+            ),
+            (
+                8,
+                FileDescriptorTarget::Socket(
+                    40004,
+                    Some(SocketInfo {
+                        protocol: SocketProtocol::Tcp6,
+                        local_address: "[::1]:6000".into(),
+                        remote_address: "[::]:0".into(),
+                        state: "LISTEN".into(),
+                    })
+                )
+            ),
+            (9, FileDescriptorTarget::Path("/dev/dri/renderD128".into())),
+        ]
+    );
+
+    assert_eq!(open_files[1].position, Some(32768));
+    assert_eq!(open_files[1].flags, Some(0o2_100_002));
+
+    assert!(OpenFile::all_of(1).await.is_err());
+}
@@ -0,0 +1,275 @@
+use std::path::PathBuf;
+
+use process_data::{
+    format_cpu_list, parse_cpu_list, Containerization, IoPriority, IoPriorityClass, MemoryDetails,
+    ProcessData,
+};
+
+fn fixture_proc(pid: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/desktop/proc")
+        .join(pid)
+}
+
+#[test]
+fn sanitize_cgroup_app_scope() {
+    assert_eq!(
+        ProcessData::sanitize_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-flatpak-org.mozilla.firefox-12345.scope\n"
+        )
+        .as_deref(),
+        Some("org.mozilla.firefox")
+    );
+}
+
+#[test]
+fn sanitize_cgroup_service() {
+    assert_eq!(
+        ProcessData::sanitize_cgroup("0::/system.slice/NetworkManager.service\n").as_deref(),
+        Some("NetworkManager")
+    );
+}
+
+#[test]
+fn sanitize_cgroup_dbus_service() {
+    assert_eq!(
+        ProcessData::sanitize_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/dbus-:1.2-org.gnome.Nautilus@0.service\n"
+        )
+        .as_deref(),
+        Some("org.gnome.Nautilus")
+    );
+}
+
+#[test]
+fn sanitize_cgroup_unknown() {
+    assert_eq!(ProcessData::sanitize_cgroup("0::/init.scope\n"), None);
+    assert_eq!(ProcessData::sanitize_cgroup("1:name=systemd:/\n"), None);
+    assert_eq!(ProcessData::sanitize_cgroup(""), None);
+}
+
+#[test]
+fn containerization_from_cgroup() {
+    assert_eq!(
+        Containerization::from_cgroup("0::/system.slice/docker-4f1c2e3d5a6b.scope\n"),
+        Some(Containerization::Docker("4f1c2e3d5a6b".to_string()))
+    );
+    assert_eq!(
+        Containerization::from_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-9e8d7c6b.scope/container\n"
+        ),
+        Some(Containerization::Podman("9e8d7c6b".to_string()))
+    );
+    assert_eq!(
+        Containerization::from_cgroup("0::/machine.slice/libpod-conmon-9e8d7c6b.scope\n"),
+        None
+    );
+    assert_eq!(
+        Containerization::from_cgroup(
+            "0::/kubepods.slice/kubepods-besteffort.slice/cri-containerd-1a2b3c.scope\n"
+        ),
+        Some(Containerization::Containerd("1a2b3c".to_string()))
+    );
+    assert_eq!(
+        Containerization::from_cgroup("0::/lxc.payload.webserver/system.slice/nginx.service\n"),
+        Some(Containerization::Lxc("webserver".to_string()))
+    );
+    assert_eq!(
+        Containerization::from_cgroup("0::/machine.slice/systemd-nspawn@debian.service/payload\n"),
+        Some(Containerization::Nspawn("debian".to_string()))
+    );
+    assert_eq!(
+        Containerization::from_cgroup(
+            "0::/machine.slice/machine-qemu\\x2d1\\x2dwin10.scope/libvirt/emulator\n"
+        ),
+        None
+    );
+    assert_eq!(
+        Containerization::from_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/snap.firefox.firefox-5f4e3d2c.scope\n"
+        ),
+        Some(Containerization::Snap("firefox".to_string()))
+    );
+    // a Docker container running inside of an LXC container
+    assert_eq!(
+        Containerization::from_cgroup("0::/lxc.payload.ci/system.slice/docker-77aa.scope\n"),
+        Some(Containerization::Docker("77aa".to_string()))
+    );
+    assert_eq!(
+        Containerization::from_cgroup("0::/system.slice/NetworkManager.service\n"),
+        None
+    );
+}
+
+#[test]
+fn containerization_from_environ_and_exe() {
+    assert_eq!(
+        Containerization::from_environ("HOME=/home/user\0SNAP_NAME=spotify\0"),
+        Some(Containerization::Snap("spotify".to_string()))
+    );
+    assert_eq!(
+        Containerization::from_environ("APPIMAGE=/home/user/Apps/Obsidian-1.4.16.AppImage\0"),
+        Some(Containerization::AppImage(
+            "Obsidian-1.4.16.AppImage".to_string()
+        ))
+    );
+    assert_eq!(Containerization::from_environ("HOME=/home/user\0"), None);
+    assert_eq!(
+        Containerization::from_exe("/tmp/.mount_ObsidiAbC123/obsidian"),
+        Some(Containerization::AppImage("Obsidi".to_string()))
+    );
+    assert_eq!(Containerization::from_exe("/usr/bin/bash"), None);
+}
+
+#[async_std::test]
+async fn flatpak_process() {
+    let data = ProcessData::try_from_path(fixture_proc("2417"))
+        .await
+        .unwrap();
+
+    assert_eq!(data.pid, 2417);
+    assert_eq!(data.parent_pid, 2400);
+    assert_eq!(data.uid, 1000);
+    assert_eq!(data.comm, "firefox");
+    assert_eq!(data.commandline, "/app/lib/firefox/firefox\0-new-window\0");
+    assert_eq!(data.state, 'S');
+    assert_eq!(data.cpu_time, 15000 + 3000);
+    assert_eq!(data.starttime, 5432);
+    assert_eq!(data.nice, 5);
+    assert_eq!(data.last_cpu, 3);
+    assert_eq!(data.affinity, Some(vec![0, 1, 2, 3, 6]));
+    assert_eq!(data.scheduling_policy, 0);
+    assert_eq!(data.memory_usage, (50000 - 20000) * sysconf::pagesize());
+    assert_eq!(data.cgroup.as_deref(), Some("org.mozilla.firefox"));
+    assert_eq!(
+        data.cgroup_path.as_deref(),
+        Some("/user.slice/user-1000.slice/user@1000.service/app.slice/app-flatpak-org.mozilla.firefox-12345.scope")
+    );
+    assert_eq!(data.containerization, Containerization::Flatpak);
+    assert_eq!(data.read_bytes, Some(104_857_600));
+    assert_eq!(data.write_bytes, Some(52_428_800));
+    assert_eq!(
+        data.memory_details,
+        Some(MemoryDetails {
+            rss: 409_600 * 1024,
+            pss: 250_000 * 1024,
+            uss: (50_000 + 199_600) * 1024,
+            shared: (120_000 + 40_000) * 1024,
+            swap: 12_288 * 1024,
+        })
+    );
+
+    let gpu_usage_stats = data.gpu_usage_stats.get("0000:03:00.0").unwrap();
+    assert_eq!(gpu_usage_stats.engines.get("gfx"), Some(&1_500_000_000));
+    assert_eq!(gpu_usage_stats.memory, 262_144 * 1024);
+}
+
+#[async_std::test]
+async fn process_without_io() {
+    let data = ProcessData::try_from_path(fixture_proc("1337"))
+        .await
+        .unwrap();
+
+    assert_eq!(data.pid, 1337);
+    assert_eq!(data.parent_pid, 1);
+    assert_eq!(data.uid, 0);
+    assert_eq!(data.cgroup.as_deref(), Some("NetworkManager"));
+    assert_eq!(data.containerization, Containerization::None);
+    assert_eq!(data.read_bytes, None);
+    assert_eq!(data.read_bytes_timestamp, None);
+    assert_eq!(data.write_bytes, None);
+    assert_eq!(data.memory_details, None);
+    assert!(data.gpu_usage_stats.is_empty());
+}
+
+#[test]
+fn cpu_list_round_trip() {
+    assert_eq!(parse_cpu_list("0-3,6\n").unwrap(), vec![0, 1, 2, 3, 6]);
+    assert_eq!(parse_cpu_list("").unwrap(), Vec::<usize>::new());
+    assert!(parse_cpu_list("3-1").is_err());
+    assert_eq!(format_cpu_list(&[6, 0, 2, 1, 3]), "0-3,6");
+}
+
+#[test]
+fn io_priority_round_trip() {
+    // IOPRIO_PRIO_VALUE(IOPRIO_CLASS_BE, 4)
+    let io_priority = IoPriority::from_raw((2 << 13) | 4);
+
+    assert_eq!(io_priority.class, IoPriorityClass::BestEffort);
+    assert_eq!(io_priority.level, 4);
+    assert_eq!(io_priority.to_raw(), (2 << 13) | 4);
+    assert_eq!(IoPriority::from_raw(0).class, IoPriorityClass::None);
+}
+
+#[test]
+fn smaps_rollup_without_pss() {
+    assert!(MemoryDetails::from_smaps_rollup("Rss:  1024 kB\nSwap:  0 kB\n").is_err());
+}
+
+#[async_std::test]
+async fn process_threads() {
+    let data = ProcessData::try_from_path(fixture_proc("2417"))
+        .await
+        .unwrap();
+
+    let mut threads = data.threads;
+    threads.sort_by_key(|thread| thread.tid);
+
+    assert_eq!(threads.len(), 2);
+
+    assert_eq!(threads[0].tid, 2417);
+    assert_eq!(threads[0].comm, "firefox");
+    assert_eq!(threads[0].state, 'S');
+    assert_eq!(threads[0].cpu_time, 9000 + 2000);
+    assert_eq!(threads[0].last_cpu, 3);
+
+    assert_eq!(threads[1].tid, 2431);
+    assert_eq!(threads[1].comm, "IPC I/O Parent");
+    assert_eq!(threads[1].state, 'R');
+    assert_eq!(threads[1].cpu_time, 6000 + 1000);
+    assert_eq!(threads[1].last_cpu, 1);
+}
+
+#[async_std::test]
+async fn missing_process() {
+    assert!(ProcessData::try_from_path(fixture_proc("4242"))
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+async fn refresh_volatile_keeps_static_data() {
+    // the PID is taken from the directory name
+    let temp_root = std::env::temp_dir().join(format!("resources-refresh-{}", std::process::id()));
+    let proc_path = temp_root.join("2417");
+    std::fs::create_dir_all(&proc_path).unwrap();
+    for file in ["cgroup", "cmdline", "io", "stat", "statm", "status"] {
+        std::fs::copy(fixture_proc("2417").join(file), proc_path.join(file)).unwrap();
+    }
+
+    let mut data = ProcessData::try_from_path(proc_path.clone()).await.unwrap();
+    assert_eq!(data.containerization, Containerization::None);
+
+    // the process has used more CPU time and has been moved into a container
+    // since the first read
+    let stat = std::fs::read_to_string(proc_path.join("stat"))
+        .unwrap()
+        .replace(" 15000 3000 ", " 16000 3500 ");
+    std::fs::write(proc_path.join("stat"), stat).unwrap();
+    std::fs::write(
+        proc_path.join("cgroup"),
+        "0::/system.slice/docker-4f1c2e3d5a6b.scope\n",
+    )
+    .unwrap();
+
+    data.refresh_volatile().await.unwrap();
+    std::fs::remove_dir_all(&temp_root).unwrap();
+
+    assert_eq!(data.comm, "firefox");
+    assert_eq!(data.cpu_time, 16000 + 3500);
+    // the cgroup is only read once, together with the containerization that
+    // is derived from it
+    assert_eq!(data.cgroup.as_deref(), Some("org.mozilla.firefox"));
+    assert_eq!(data.containerization, Containerization::None);
+    assert_eq!(data.affinity, Some(vec![0, 1, 2, 3, 6]));
+}
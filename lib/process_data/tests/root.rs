@@ -0,0 +1,62 @@
+use process_data::{root, rooted, ProcessData, ProcessScanner, ScanOptions};
+
+// `RESOURCES_ROOT` is only evaluated once per process, so everything that
+// depends on it lives in this test binary and is set up in a single test.
+#[async_std::test]
+async fn all_processes_below_root() {
+    let fixture_root = concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/fixtures/desktop");
+    std::env::set_var("RESOURCES_ROOT", fixture_root);
+
+    assert_eq!(root().to_str(), Some(fixture_root));
+    assert_eq!(
+        rooted("/proc/stat").to_str(),
+        Some(format!("{fixture_root}/proc/stat").as_str())
+    );
+
+    let mut pids: Vec<i32> = ProcessData::all()
+        .await
+        .unwrap()
+        .iter()
+        .map(|process| process.pid)
+        .collect();
+    pids.sort_unstable();
+
+    assert_eq!(pids, vec![1, 1337, 2417]);
+
+    // the second scan only refreshes the volatile data of the cached processes
+    let mut scanner = ProcessScanner::new();
+    let first_scan = scanner.scan(&ScanOptions::default()).await.unwrap();
+    let options = ScanOptions {
+        thread_pids: [2417].into(),
+        memory_details_pids: [2417].into(),
+        ..Default::default()
+    };
+    let second_scan = scanner.scan(&options).await.unwrap();
+
+    for scan in [&first_scan, &second_scan] {
+        let mut pids: Vec<i32> = scan.iter().map(|process| process.pid).collect();
+        pids.sort_unstable();
+        assert_eq!(pids, vec![1, 1337, 2417]);
+    }
+
+    let firefox = second_scan
+        .iter()
+        .find(|process| process.pid == 2417)
+        .unwrap();
+    assert_eq!(firefox.comm, "firefox");
+    assert_eq!(firefox.starttime, 5432);
+    assert_eq!(firefox.parent_pid, 2400);
+    assert_eq!(firefox.thread_count, 120);
+
+    // threads and memory details are only read for the processes that ask
+    // for them
+    assert!(first_scan
+        .iter()
+        .all(|process| process.threads.is_empty() && process.memory_details.is_none()));
+    assert_eq!(firefox.threads.len(), 2);
+    assert!(firefox.memory_details.is_some());
+    assert!(second_scan
+        .iter()
+        .filter(|process| process.pid != 2417)
+        .all(|process| process.threads.is_empty()));
+}
@@ -0,0 +1,202 @@
+use std::env;
+
+use nix::{errno::Errno, libc};
+use process_data::{
+    errno_status, parse_cpu_list, starttime, IoPriority, IoPriorityClass,
+    IDENTITY_CHANGED_STATUS_CODE,
+};
+
+/// The nice value, the scheduling policy, the I/O priority and the CPU
+/// affinity are per thread on Linux, so `adjust` is called for every thread
+/// of the process with the PID `pid`
+fn adjust_threads<F: Fn(i32) -> Result<(), Errno>>(pid: i32, adjust: F) -> i32 {
+    let Ok(tasks) = std::fs::read_dir(format!("/proc/{pid}/task")) else {
+        return errno_status(Errno::ESRCH as i32);
+    };
+
+    let tids = tasks
+        .flatten()
+        .filter_map(|task| task.file_name().to_str().and_then(|tid| tid.parse().ok()));
+
+    for tid in tids {
+        match adjust(tid) {
+            // threads that have exited in the meantime don't matter
+            Ok(()) | Err(Errno::ESRCH) => (),
+            Err(err) => return errno_status(err as i32),
+        }
+    }
+
+    0
+}
+
+enum Adjustment {
+    Nice(i32),
+    /// The policy and the static priority, which is only meaningful for the
+    /// realtime policies
+    SchedulingPolicy(i32, i32),
+    IoPriority(IoPriority),
+    Affinity(libc::cpu_set_t),
+}
+
+fn parse_cpu_set(arg: &str) -> Option<libc::cpu_set_t> {
+    let cpus = parse_cpu_list(arg).ok()?;
+    if cpus.is_empty() || cpus.iter().any(|cpu| *cpu >= libc::CPU_SETSIZE as usize) {
+        return None;
+    }
+
+    let mut cpu_set = unsafe { std::mem::zeroed::<libc::cpu_set_t>() };
+    for cpu in cpus {
+        libc::CPU_SET(cpu, &mut cpu_set);
+    }
+    Some(cpu_set)
+}
+
+fn parse_scheduling_policy(arg: &str) -> Option<i32> {
+    match arg {
+        "other" => Some(libc::SCHED_OTHER),
+        "fifo" => Some(libc::SCHED_FIFO),
+        "rr" => Some(libc::SCHED_RR),
+        "batch" => Some(libc::SCHED_BATCH),
+        "idle" => Some(libc::SCHED_IDLE),
+        _ => None,
+    }
+}
+
+fn parse_io_priority_class(arg: &str) -> Option<IoPriorityClass> {
+    match arg {
+        "none" => Some(IoPriorityClass::None),
+        "realtime" => Some(IoPriorityClass::RealTime),
+        "best-effort" => Some(IoPriorityClass::BestEffort),
+        "idle" => Some(IoPriorityClass::Idle),
+        _ => None,
+    }
+}
+
+/// Usage:
+///
+/// `resources-adjust nice <NICE> <PID> [<STARTTIME>]` sets the nice value of
+/// all threads of a process.
+///
+/// `resources-adjust sched <POLICY> <PID> [<STARTTIME>]` sets the scheduling
+/// policy of all threads of a process, `<POLICY>` is one of `other`, `fifo`,
+/// `rr`, `batch` and `idle`. The realtime policies `fifo` and `rr` are set
+/// with the lowest static priority.
+///
+/// `resources-adjust ioprio <CLASS> <LEVEL> <PID> [<STARTTIME>]` sets the I/O
+/// priority of all threads of a process, `<CLASS>` is one of `none`,
+/// `realtime`, `best-effort` and `idle`.
+///
+/// `resources-adjust affinity <CPU LIST> <PID> [<STARTTIME>]` sets the CPU
+/// affinity of all threads of a process, `<CPU LIST>` is e. g. `0-3,6`.
+///
+/// The exit code is either 0, an errno (e. g. 1 for `EPERM` or 13 for `EACCES`
+/// when lowering the nice value without privileges), 252 if the start time of
+/// the process doesn't match, 253 for unknown errnos or 255 for invalid
+/// arguments.
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+
+    let (adjustment, process_args) = match args.get(1).map(String::as_str) {
+        Some("nice") => {
+            let Some(nice) = args
+                .get(2)
+                .and_then(|nice| nice.trim().parse::<i32>().ok())
+                .filter(|nice| (-20..=19).contains(nice))
+            else {
+                std::process::exit(255);
+            };
+            (Adjustment::Nice(nice), args.get(3..).unwrap_or_default())
+        }
+        Some("sched") => {
+            let Some(policy) = args
+                .get(2)
+                .and_then(|policy| parse_scheduling_policy(policy))
+            else {
+                std::process::exit(255);
+            };
+            let priority = if policy == libc::SCHED_FIFO || policy == libc::SCHED_RR {
+                unsafe { libc::sched_get_priority_min(policy) }
+            } else {
+                0
+            };
+            (
+                Adjustment::SchedulingPolicy(policy, priority),
+                args.get(3..).unwrap_or_default(),
+            )
+        }
+        Some("ioprio") => {
+            let Some(class) = args.get(2).and_then(|class| parse_io_priority_class(class)) else {
+                std::process::exit(255);
+            };
+            let Some(level) = args
+                .get(3)
+                .and_then(|level| level.trim().parse::<u8>().ok())
+                .filter(|level| *level <= 7)
+            else {
+                std::process::exit(255);
+            };
+            (
+                Adjustment::IoPriority(IoPriority { class, level }),
+                args.get(4..).unwrap_or_default(),
+            )
+        }
+        Some("affinity") => {
+            let Some(cpu_set) = args.get(2).and_then(|cpu_list| parse_cpu_set(cpu_list)) else {
+                std::process::exit(255);
+            };
+            (
+                Adjustment::Affinity(cpu_set),
+                args.get(3..).unwrap_or_default(),
+            )
+        }
+        _ => std::process::exit(255),
+    };
+
+    let Some(pid) = process_args
+        .first()
+        .and_then(|pid| pid.trim().parse::<i32>().ok())
+    else {
+        std::process::exit(255);
+    };
+
+    if let Some(expected_starttime) = process_args.get(1) {
+        let Ok(expected_starttime) = expected_starttime.trim().parse::<u64>() else {
+            std::process::exit(255);
+        };
+        // unlike signals, priorities can't be changed through a pidfd, so this
+        // check leaves a tiny window for PID reuse
+        match starttime(pid) {
+            Some(starttime) if starttime == expected_starttime => (),
+            Some(_) => std::process::exit(IDENTITY_CHANGED_STATUS_CODE),
+            None => std::process::exit(errno_status(Errno::ESRCH as i32)),
+        }
+    }
+
+    let status = match adjustment {
+        Adjustment::Nice(nice) => adjust_threads(pid, |tid| {
+            Errno::result(unsafe { libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, nice) })
+                .map(drop)
+        }),
+        Adjustment::SchedulingPolicy(policy, priority) => adjust_threads(pid, |tid| {
+            let param = libc::sched_param {
+                sched_priority: priority,
+            };
+            Errno::result(unsafe { libc::sched_setscheduler(tid, policy, &param) }).map(drop)
+        }),
+        Adjustment::IoPriority(io_priority) => adjust_threads(pid, |tid| {
+            // IOPRIO_WHO_PROCESS
+            Errno::result(unsafe {
+                libc::syscall(libc::SYS_ioprio_set, 1, tid, io_priority.to_raw())
+            })
+            .map(drop)
+        }),
+        Adjustment::Affinity(cpu_set) => adjust_threads(pid, |tid| {
+            Errno::result(unsafe {
+                libc::sched_setaffinity(tid, std::mem::size_of::<libc::cpu_set_t>(), &cpu_set)
+            })
+            .map(drop)
+        }),
+    };
+
+    std::process::exit(status);
+}
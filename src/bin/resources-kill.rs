@@ -1,26 +1,144 @@
 use std::env;
 
-use nix::{sys::signal, unistd::Pid};
+use nix::{errno::Errno, libc, sys::signal::Signal};
+use process_data::{errno_status, starttime, IDENTITY_CHANGED_STATUS_CODE};
 
-fn main() {
-    if let Some(arg) = env::args().nth(1) {
-        if let Some(pid) = env::args().nth(2).and_then(|s| s.trim().parse().ok()) {
-            let signal = match arg.as_str() {
-                "STOP" => signal::Signal::SIGSTOP,
-                "CONT" => signal::Signal::SIGCONT,
-                "TERM" => signal::Signal::SIGTERM,
-                "KILL" => signal::Signal::SIGKILL,
-                _ => std::process::exit(254),
-            };
-            let result = signal::kill(Pid::from_raw(pid), Some(signal));
-            if let Err(err) = result {
-                match err {
-                    nix::errno::Errno::UnknownErrno => std::process::exit(253),
-                    _ => std::process::exit(err as i32),
-                };
+fn parse_signal(arg: &str) -> Option<i32> {
+    let signal = match arg {
+        "STOP" => Signal::SIGSTOP as i32,
+        "CONT" => Signal::SIGCONT as i32,
+        "TERM" => Signal::SIGTERM as i32,
+        "KILL" => Signal::SIGKILL as i32,
+        "HUP" => Signal::SIGHUP as i32,
+        "INT" => Signal::SIGINT as i32,
+        "QUIT" => Signal::SIGQUIT as i32,
+        "USR1" => Signal::SIGUSR1 as i32,
+        "USR2" => Signal::SIGUSR2 as i32,
+        _ => {
+            let signal = arg
+                .strip_prefix("RTMIN+")
+                .and_then(|offset| offset.parse::<u8>().ok())
+                .map(|offset| libc::SIGRTMIN() + i32::from(offset))?;
+            if signal > libc::SIGRTMAX() {
+                return None;
             }
-            std::process::exit(0);
+            signal
+        }
+    };
+    Some(signal)
+}
+
+/// Sends `signal` to the process with the PID `pid` if its start time matches
+/// `expected_starttime` and returns the status code for it
+fn send_signal(pid: i32, signal: i32, expected_starttime: Option<u64>) -> i32 {
+    // open the pidfd *before* verifying the start time: if the start time
+    // still matches afterwards, the pidfd is guaranteed to refer to the
+    // process that was meant, even if it ends right after
+    let pidfd = match Errno::result(unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) }) {
+        Ok(pidfd) => Some(pidfd as i32),
+        // kernels older than 5.3 don't support pidfds, fall back to kill()
+        Err(Errno::ENOSYS) => None,
+        Err(err) => return errno_status(err as i32),
+    };
+
+    let status = match expected_starttime.map(|expected| (expected, starttime(pid))) {
+        Some((expected, Some(starttime))) if starttime != expected => IDENTITY_CHANGED_STATUS_CODE,
+        Some((_, None)) => errno_status(Errno::ESRCH as i32),
+        _ => {
+            // nix' `Signal` doesn't cover realtime signals, so call the
+            // syscalls directly
+            let result = if let Some(pidfd) = pidfd {
+                Errno::result(unsafe {
+                    libc::syscall(
+                        libc::SYS_pidfd_send_signal,
+                        pidfd,
+                        signal,
+                        std::ptr::null::<libc::siginfo_t>(),
+                        0,
+                    )
+                })
+                .map(drop)
+            } else {
+                Errno::result(unsafe { libc::kill(pid, signal) }).map(drop)
+            };
+
+            result.map_or_else(|err| errno_status(err as i32), |()| 0)
         }
+    };
+
+    if let Some(pidfd) = pidfd {
+        unsafe { libc::close(pidfd) };
     }
-    std::process::exit(255);
+
+    status
+}
+
+/// Parses a process given as `<PID>` or `<PID>:<STARTTIME>`
+fn parse_process(arg: &str) -> Option<(i32, Option<u64>)> {
+    match arg.trim().split_once(':') {
+        Some((pid, starttime)) => Some((pid.parse().ok()?, Some(starttime.parse().ok()?))),
+        None => Some((arg.trim().parse().ok()?, None)),
+    }
+}
+
+/// Usage:
+///
+/// `resources-kill <SIGNAL> <PID> [<STARTTIME>]` signals a single process, the
+/// exit code is the status code for it.
+///
+/// `resources-kill <SIGNAL> --batch <PID>[:<STARTTIME>]...` signals all of the
+/// given processes and prints a JSON array of `[<PID>, <STATUS CODE>]` pairs
+/// to stdout, the exit code is 0 if the arguments were valid.
+///
+/// Status codes are either an errno (e. g. 1 for `EPERM` or 3 for `ESRCH`), 252
+/// if the start time of the process doesn't match, 253 for unknown errnos, 254
+/// for invalid signals and 255 for invalid arguments.
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+
+    let Some(signal_arg) = args.get(1) else {
+        std::process::exit(255);
+    };
+
+    if args.get(2).map(String::as_str) == Some("--batch") {
+        let Some(processes) = args[3..]
+            .iter()
+            .map(|arg| parse_process(arg))
+            .collect::<Option<Vec<_>>>()
+        else {
+            std::process::exit(255);
+        };
+
+        let Some(signal) = parse_signal(signal_arg) else {
+            std::process::exit(254);
+        };
+
+        let results = processes
+            .into_iter()
+            .map(|(pid, starttime)| (pid, send_signal(pid, signal, starttime)))
+            .collect::<Vec<_>>();
+
+        if serde_json::to_writer(std::io::stdout().lock(), &results).is_err() {
+            std::process::exit(255);
+        }
+        std::process::exit(0);
+    }
+
+    let Some(pid) = args.get(2).and_then(|s| s.trim().parse().ok()) else {
+        std::process::exit(255);
+    };
+
+    let expected_starttime = match args.get(3) {
+        Some(s) => match s.trim().parse::<u64>() {
+            Ok(starttime) => Some(starttime),
+            Err(_) => std::process::exit(255),
+        },
+        None => None,
+    };
+
+    let Some(signal) = parse_signal(signal_arg) else {
+        std::process::exit(254);
+    };
+
+    std::process::exit(send_signal(pid, signal, expected_starttime));
 }
@@ -1,33 +1,60 @@
 use anyhow::{Context, Result};
-use async_std::sync::Arc;
-use async_std::sync::Mutex;
-use futures_util::future::join_all;
-use glob::glob;
-use process_data::ProcessData;
+use process_data::{
+    ipc::{self, Request},
+    open_files::OpenFile,
+    ProcessData, ProcessScanner,
+};
 
 #[async_std::main]
 async fn main() -> Result<()> {
-    let return_vec = Arc::new(Mutex::new(Vec::new()));
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    // `resources-processes --persistent` keeps running and answers the
+    // requests coming in through stdin until stdin is closed
+    if std::env::args().nth(1).as_deref() == Some("--persistent") {
+        let stdin = std::io::stdin();
+        let mut input = stdin.lock();
 
-    let mut handles = vec![];
-    for entry in glob("/proc/[0-9]*/").context("unable to glob")?.flatten() {
-        let return_vec = Arc::clone(&return_vec);
+        // only re-read what changes for processes that are already known
+        let mut scanner = ProcessScanner::new();
 
-        let handle = async_std::task::spawn(async move {
-            if let Ok(process_data) = ProcessData::try_from_path(entry).await {
-                return_vec.lock().await.push(process_data);
+        while let Some(request) = ipc::read_message::<_, Request>(&mut input)? {
+            match request {
+                Request::Processes(options) => {
+                    // always answer, otherwise the app would wait forever
+                    let processes = scanner.scan(&options).await.unwrap_or_default();
+                    ipc::write_message(&mut handle, &processes)?;
+                }
+                Request::OpenFiles(pid) => {
+                    let open_files = OpenFile::all_of(pid)
+                        .await
+                        .map_err(|error| format!("{error:#}"));
+                    ipc::write_message(&mut handle, &open_files)?;
+                }
             }
-        });
+        }
 
-        handles.push(handle);
+        return Ok(());
     }
-    join_all(handles).await;
 
-    let stdout = std::io::stdout();
-    let mut handle = stdout.lock();
+    // `resources-processes --open-files <pid>` lists the open files of a
+    // single process, otherwise all processes are listed
+    if std::env::args().nth(1).as_deref() == Some("--open-files") {
+        let pid = std::env::args()
+            .nth(2)
+            .and_then(|pid| pid.trim().parse().ok())
+            .context("no valid pid given")?;
+
+        let return_vec = OpenFile::all_of(pid).await?;
+        rmp_serde::encode::write(&mut handle, &return_vec).unwrap();
+
+        return Ok(());
+    }
+
+    let return_vec = ProcessData::all().await?;
 
-    let return_vec = return_vec.lock().await;
-    rmp_serde::encode::write(&mut handle, &*return_vec).unwrap();
+    rmp_serde::encode::write(&mut handle, &return_vec).unwrap();
 
     Ok(())
 }
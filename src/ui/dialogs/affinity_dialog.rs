@@ -0,0 +1,110 @@
+use std::{num::NonZeroUsize, rc::Rc};
+
+use adw::{prelude::*, ResponseAppearance};
+use anyhow::Context;
+use gtk::glib::{clone, MainContext};
+
+use crate::i18n::{i18n, i18n_f};
+use crate::utils::cpu;
+
+/// Lets the user choose the logical CPUs `name` is allowed to run on.
+/// `on_apply` is called with the chosen CPUs once the user confirms.
+pub fn show_affinity_dialog<W, F>(
+    parent: &W,
+    name: &str,
+    last_cpu: u32,
+    affinity: Option<Vec<usize>>,
+    on_apply: F,
+) where
+    W: IsA<gtk::Window>,
+    F: Fn(Vec<usize>) + 'static,
+{
+    let parent = parent.clone().upcast::<gtk::Window>();
+    let name = name.to_string();
+
+    MainContext::default().spawn_local(async move {
+        // enumerate the CPUs the same way the Processor page does, but don't
+        // leave the user without any CPUs to choose from if that fails
+        let logical_cpus = cpu::cpu_info()
+            .await
+            .context("unable to get CPUInfo")
+            .unwrap_or_default()
+            .logical_cpus
+            .or_else(|| {
+                std::thread::available_parallelism()
+                    .ok()
+                    .map(NonZeroUsize::get)
+            })
+            .unwrap_or(1);
+
+        let flow_box = gtk::FlowBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .homogeneous(true)
+            .min_children_per_line(4)
+            .max_children_per_line(8)
+            .build();
+
+        let check_buttons: Vec<gtk::CheckButton> = (0..logical_cpus)
+            .map(|i| {
+                let check_button = gtk::CheckButton::builder()
+                    .label(i18n_f("CPU {}", &[&(i + 1).to_string()]))
+                    .active(affinity.as_ref().map_or(true, |cpus| cpus.contains(&i)))
+                    .build();
+                flow_box.append(&check_button);
+                check_button
+            })
+            .collect();
+
+        let scrolled_window = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(320)
+            .child(&flow_box)
+            .build();
+
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&parent)
+            .modal(true)
+            .heading(i18n_f("CPU Affinity of {}", &[&name]))
+            .body(i18n_f(
+                "Choose the CPUs this process is allowed to run on. It has last been running on CPU {}.",
+                &[&(last_cpu + 1).to_string()],
+            ))
+            .extra_child(&scrolled_window)
+            .build();
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.add_response("yes", &i18n("Change Affinity"));
+        dialog.set_response_appearance("yes", ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("yes"));
+        dialog.set_close_response("no");
+
+        // a process has to be allowed to run on at least one CPU
+        let update_sensitivity = Rc::new(clone!(@weak dialog, @strong check_buttons => move || {
+            dialog.set_response_enabled(
+                "yes",
+                check_buttons.iter().any(|check_button| check_button.is_active()),
+            );
+        }));
+        update_sensitivity();
+        for check_button in &check_buttons {
+            check_button.connect_toggled(clone!(@strong update_sensitivity => move |_| {
+                update_sensitivity();
+            }));
+        }
+
+        dialog.connect_response(None, move |_, response| {
+            if response == "yes" {
+                let cpus = check_buttons
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, check_button)| check_button.is_active())
+                    .map(|(i, _)| i)
+                    .collect();
+                on_apply(cpus);
+            }
+        });
+
+        dialog.show();
+    });
+}
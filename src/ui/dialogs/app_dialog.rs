@@ -1,14 +1,20 @@
 use adw::{prelude::*, subclass::prelude::*};
-use gtk::glib;
-use process_data::Containerization;
+use gtk::glib::{self, clone};
 
 use crate::config::PROFILE;
-use crate::i18n::i18n;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::widgets::history_group::ResHistoryGroup;
 use crate::ui::window::MainWindow;
-use crate::utils::app::AppItem;
+use crate::utils::app::{AppItem, UsageHistory};
+use crate::utils::cgroup::CgroupLimits;
+use crate::utils::process::containerization_description;
 use crate::utils::units::{convert_speed, convert_storage};
 
+/// Memory limits are entered in MiB
+const MIB: u64 = 1024 * 1024;
+
 mod imp {
+    use std::cell::Cell;
 
     use super::*;
 
@@ -28,6 +34,16 @@ mod imp {
         #[template_child]
         pub memory_usage: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub memory_rss: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_pss: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_uss: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_shared: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_swap: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub drive_read_speed: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub drive_read_total: TemplateChild<adw::ActionRow>,
@@ -36,11 +52,28 @@ mod imp {
         #[template_child]
         pub drive_write_total: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub history: TemplateChild<ResHistoryGroup>,
+        #[template_child]
         pub id: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub processes_amount: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub limits_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub apply_limits_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub cpu_weight: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub memory_high: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub memory_max: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub io_weight: TemplateChild<adw::SpinRow>,
+        /// The limits the rows have been set to when the dialog was opened,
+        /// only limits that differ from these are applied
+        pub shown_limits: Cell<CgroupLimits>,
     }
 
     #[glib::object_subclass]
@@ -92,9 +125,60 @@ impl ResAppDialog {
     }
 
     pub fn setup_widgets(&self, app: &AppItem) {
+        self.setup_limits(app);
         self.update(app);
     }
 
+    /// Shows the limits currently in effect for `app`, combined across all of
+    /// its cgroups. Only apps running in cgroups of their own can be limited.
+    fn setup_limits(&self, app: &AppItem) {
+        let imp = self.imp();
+
+        let Some(limits) = CgroupLimits::of_all(&app.cgroup_paths) else {
+            imp.limits_group.set_visible(false);
+            return;
+        };
+
+        imp.cpu_weight.set_value(limits.cpu_weight as f64);
+        imp.memory_high
+            .set_value(limits.memory_high.map_or(0.0, |bytes| (bytes / MIB) as f64));
+        imp.memory_max
+            .set_value(limits.memory_max.map_or(0.0, |bytes| (bytes / MIB) as f64));
+        imp.io_weight.set_value(limits.io_weight as f64);
+        // the rows round memory limits to MiB
+        imp.shown_limits.set(self.limits());
+    }
+
+    /// Returns the limits that have been entered, a memory limit of 0 means
+    /// that there is no limit
+    fn limits(&self) -> CgroupLimits {
+        let imp = self.imp();
+        let memory_limit =
+            |row: &adw::SpinRow| Some(row.value() as u64 * MIB).filter(|bytes| *bytes > 0);
+
+        CgroupLimits {
+            cpu_weight: imp.cpu_weight.value() as u64,
+            memory_high: memory_limit(&imp.memory_high),
+            memory_max: memory_limit(&imp.memory_max),
+            io_weight: imp.io_weight.value() as u64,
+        }
+    }
+
+    /// Calls `f` with the initially shown and the entered limits once the
+    /// user wants to apply them.
+    pub fn connect_apply_limits<F: Fn(CgroupLimits, CgroupLimits) + 'static>(&self, f: F) {
+        self.imp()
+            .apply_limits_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                f(this.imp().shown_limits.get(), this.limits());
+            }));
+    }
+
+    /// Plots the usage during the last refreshes
+    pub fn update_history(&self, history: &UsageHistory) {
+        self.imp().history.update(history);
+    }
+
     pub fn update(&self, app: &AppItem) {
         let imp = self.imp();
 
@@ -120,6 +204,22 @@ impl ResAppDialog {
         imp.memory_usage
             .set_subtitle(&convert_storage(app.memory_usage as f64, false));
 
+        let memory_details = app.memory_details;
+        for (row, value) in [
+            (&imp.memory_rss, memory_details.map(|details| details.rss)),
+            (&imp.memory_pss, memory_details.map(|details| details.pss)),
+            (&imp.memory_uss, memory_details.map(|details| details.uss)),
+            (
+                &imp.memory_shared,
+                memory_details.map(|details| details.shared),
+            ),
+            (&imp.memory_swap, memory_details.map(|details| details.swap)),
+        ] {
+            row.set_subtitle(
+                &value.map_or_else(|| i18n("N/A"), |value| convert_storage(value as f64, false)),
+            );
+        }
+
         imp.drive_read_speed
             .set_subtitle(&convert_speed(app.read_speed, false));
 
@@ -135,10 +235,10 @@ impl ResAppDialog {
         imp.processes_amount
             .set_subtitle(&app.processes_amount.to_string());
 
-        let containerized = match app.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
-        };
+        let containerized = containerization_description(&app.containerization).map_or_else(
+            || i18n("No"),
+            |description| i18n_f("Yes ({})", &[&description]),
+        );
         imp.containerized.set_subtitle(&containerized);
     }
 }
@@ -1,3 +1,6 @@
+pub mod affinity_dialog;
 pub mod app_dialog;
+pub mod priority_dialog;
 pub mod process_dialog;
 pub mod settings_dialog;
+pub mod signal_dialog;
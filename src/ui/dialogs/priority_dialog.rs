@@ -0,0 +1,163 @@
+use adw::{prelude::*, ResponseAppearance};
+use gtk::glib::clone;
+use process_data::{IoPriority, IoPriorityClass};
+
+use crate::i18n::{i18n, i18n_f};
+use crate::utils::process::{scheduling_policy_name, ProcessAdjustment, SCHEDULING_POLICIES};
+
+/// The I/O priority classes in the order they're offered in
+const IO_PRIORITY_CLASSES: [IoPriorityClass; 4] = [
+    IoPriorityClass::None,
+    IoPriorityClass::RealTime,
+    IoPriorityClass::BestEffort,
+    IoPriorityClass::Idle,
+];
+
+/// The level is meaningless for classes other than realtime and best effort,
+/// so it is ignored when comparing I/O priorities
+fn normalized(io_priority: IoPriority) -> IoPriority {
+    match io_priority.class {
+        IoPriorityClass::RealTime | IoPriorityClass::BestEffort => io_priority,
+        IoPriorityClass::None | IoPriorityClass::Idle => IoPriority {
+            class: io_priority.class,
+            level: 0,
+        },
+    }
+}
+
+/// Lets the user change the nice value, scheduling policy and I/O priority of
+/// `name`. `on_apply` is called with the values that have been changed once
+/// the user confirms.
+pub fn show_priority_dialog<W, F>(
+    parent: &W,
+    name: &str,
+    nice: i8,
+    scheduling_policy: u32,
+    io_priority: Option<IoPriority>,
+    on_apply: F,
+) where
+    W: IsA<gtk::Window>,
+    F: Fn(Vec<ProcessAdjustment>) + 'static,
+{
+    let nice_row = adw::SpinRow::with_range(-20.0, 19.0, 1.0);
+    nice_row.set_title(&i18n("Nice"));
+    nice_row.set_subtitle(&i18n("Lower values mean a higher priority"));
+    nice_row.set_value(f64::from(nice));
+
+    let policy_names = SCHEDULING_POLICIES
+        .iter()
+        .map(|policy| scheduling_policy_name(*policy))
+        .collect::<Vec<_>>();
+    let policy_row = adw::ComboRow::builder()
+        .title(i18n("Scheduling Policy"))
+        .model(&gtk::StringList::new(
+            &policy_names.iter().map(String::as_str).collect::<Vec<_>>(),
+        ))
+        .build();
+    // policies that can't be set, e. g. deadline, leave the row empty
+    policy_row.set_selected(
+        SCHEDULING_POLICIES
+            .iter()
+            .position(|policy| *policy == scheduling_policy)
+            .map_or(gtk::INVALID_LIST_POSITION, |position| position as u32),
+    );
+
+    let io_class_row = adw::ComboRow::builder()
+        .title(i18n("I/O Priority"))
+        .model(&gtk::StringList::new(&[
+            i18n("Default").as_str(),
+            i18n("Realtime").as_str(),
+            i18n("Best Effort").as_str(),
+            i18n("Idle").as_str(),
+        ]))
+        .build();
+    let io_class = io_priority
+        .map(|io_priority| io_priority.class)
+        .unwrap_or_default();
+    io_class_row.set_selected(
+        IO_PRIORITY_CLASSES
+            .iter()
+            .position(|class| *class == io_class)
+            .unwrap_or_default() as u32,
+    );
+
+    let io_level_row = adw::SpinRow::with_range(0.0, 7.0, 1.0);
+    io_level_row.set_title(&i18n("I/O Priority Level"));
+    io_level_row.set_subtitle(&i18n("Lower values mean a higher priority"));
+    io_level_row.set_value(f64::from(
+        io_priority.map_or(4, |io_priority| io_priority.level),
+    ));
+
+    // only the realtime and best effort classes have levels
+    let update_level_visibility = clone!(@weak io_level_row => move |io_class_row: &adw::ComboRow| {
+        io_level_row.set_visible(matches!(
+            IO_PRIORITY_CLASSES.get(io_class_row.selected() as usize),
+            Some(IoPriorityClass::RealTime | IoPriorityClass::BestEffort)
+        ));
+    });
+    update_level_visibility(&io_class_row);
+    io_class_row.connect_selected_notify(update_level_visibility);
+
+    let list_box = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    list_box.add_css_class("boxed-list");
+    list_box.append(&nice_row);
+    list_box.append(&policy_row);
+    list_box.append(&io_class_row);
+    list_box.append(&io_level_row);
+
+    let dialog = adw::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .heading(i18n_f("Change Priority of {}", &[name]))
+        .body(i18n(
+            "Raising the priority of a process or changing the priority of other users' processes requires authentication.",
+        ))
+        .extra_child(&list_box)
+        .build();
+
+    dialog.add_response("no", &i18n("Cancel"));
+    dialog.add_response("yes", &i18n("Change Priority"));
+    dialog.set_response_appearance("yes", ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("yes"));
+    dialog.set_close_response("no");
+
+    dialog.connect_response(None, move |_, response| {
+        if response != "yes" {
+            return;
+        }
+
+        let mut adjustments = Vec::new();
+
+        let new_nice = nice_row.value() as i8;
+        if new_nice != nice {
+            adjustments.push(ProcessAdjustment::Nice(new_nice));
+        }
+
+        if let Some(new_policy) = SCHEDULING_POLICIES
+            .get(policy_row.selected() as usize)
+            .copied()
+            .filter(|new_policy| *new_policy != scheduling_policy)
+        {
+            adjustments.push(ProcessAdjustment::SchedulingPolicy(new_policy));
+        }
+
+        let new_io_priority = normalized(IoPriority {
+            class: IO_PRIORITY_CLASSES
+                .get(io_class_row.selected() as usize)
+                .copied()
+                .unwrap_or_default(),
+            level: io_level_row.value() as u8,
+        });
+        if Some(new_io_priority) != io_priority.map(normalized) {
+            adjustments.push(ProcessAdjustment::IoPriority(new_io_priority));
+        }
+
+        if !adjustments.is_empty() {
+            on_apply(adjustments);
+        }
+    });
+
+    dialog.show();
+}
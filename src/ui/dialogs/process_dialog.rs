@@ -1,15 +1,21 @@
 use adw::{prelude::*, subclass::prelude::*};
-use gtk::glib;
-use process_data::Containerization;
+use gtk::glib::{clone, MainContext};
+use gtk::{gio, glib};
+use process_data::format_cpu_list;
+use process_data::open_files::{FileDescriptorTarget, OpenFile, SocketProtocol};
 
 use crate::config::PROFILE;
-use crate::i18n::i18n;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::widgets::history_group::ResHistoryGroup;
 use crate::ui::window::MainWindow;
-use crate::utils::process::ProcessItem;
+use crate::utils::app::UsageHistory;
+use crate::utils::process::{self, containerization_description, Process, ProcessItem, ThreadItem};
 use crate::utils::units::{convert_speed, convert_storage};
 
 mod imp {
 
+    use std::cell::{Cell, OnceCell};
+
     use super::*;
 
     use gtk::CompositeTemplate;
@@ -24,6 +30,16 @@ mod imp {
         #[template_child]
         pub memory_usage: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub memory_rss: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_pss: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_uss: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_shared: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_swap: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub drive_read_speed: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub drive_read_total: TemplateChild<adw::ActionRow>,
@@ -32,6 +48,8 @@ mod imp {
         #[template_child]
         pub drive_write_total: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub history: TemplateChild<ResHistoryGroup>,
+        #[template_child]
         pub pid: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub commandline: TemplateChild<adw::ActionRow>,
@@ -41,6 +59,35 @@ mod imp {
         pub cgroup: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub nice: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub scheduling_policy: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub io_priority: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub last_cpu: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub affinity: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub change_priority_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub change_affinity_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub threads_column_view: TemplateChild<gtk::ColumnView>,
+        #[template_child]
+        pub view_stack: TemplateChild<adw::ViewStack>,
+        #[template_child]
+        pub open_files_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub open_files_column_view: TemplateChild<gtk::ColumnView>,
+        #[template_child]
+        pub open_files_status_page: TemplateChild<adw::StatusPage>,
+
+        pub threads_store: OnceCell<gio::ListStore>,
+        pub open_files_store: OnceCell<gio::ListStore>,
+        pub process_pid: Cell<i32>,
+        pub open_files_loading: Cell<bool>,
     }
 
     #[glib::object_subclass]
@@ -96,9 +143,144 @@ impl ResProcessDialog {
 
         imp.user.set_subtitle(user);
 
+        let threads_store = gio::ListStore::new::<glib::BoxedAnyObject>();
+        imp.threads_column_view
+            .set_model(Some(&gtk::NoSelection::new(Some(threads_store.clone()))));
+        let _ = imp.threads_store.set(threads_store);
+
+        let threads_column_view = &imp.threads_column_view;
+        append_column(
+            threads_column_view,
+            &i18n("Thread ID"),
+            false,
+            |thread: &ThreadItem| thread.tid.to_string(),
+        );
+        append_column(
+            threads_column_view,
+            &i18n("Name"),
+            true,
+            |thread: &ThreadItem| thread.name.clone(),
+        );
+        append_column(
+            threads_column_view,
+            &i18n("State"),
+            false,
+            |thread: &ThreadItem| process::state_name(thread.state),
+        );
+        append_column(
+            threads_column_view,
+            &i18n("Processor"),
+            false,
+            |thread: &ThreadItem| format!("{:.1} %", thread.cpu_time_ratio * 100.0),
+        );
+        append_column(
+            threads_column_view,
+            &i18n("Last CPU"),
+            false,
+            |thread: &ThreadItem| thread.last_cpu.to_string(),
+        );
+
+        let open_files_store = gio::ListStore::new::<glib::BoxedAnyObject>();
+        imp.open_files_column_view
+            .set_model(Some(&gtk::NoSelection::new(Some(open_files_store.clone()))));
+        let _ = imp.open_files_store.set(open_files_store);
+
+        let open_files_column_view = &imp.open_files_column_view;
+        append_column(
+            open_files_column_view,
+            &i18n("FD"),
+            false,
+            |open_file: &OpenFile| open_file.fd.to_string(),
+        );
+        append_column(open_files_column_view, &i18n("Type"), false, open_file_type);
+        append_column(open_files_column_view, &i18n("Name"), true, open_file_name);
+        append_column(
+            open_files_column_view,
+            &i18n("State"),
+            false,
+            |open_file: &OpenFile| match &open_file.target {
+                FileDescriptorTarget::Socket(_, Some(socket)) => socket.state.clone(),
+                _ => String::new(),
+            },
+        );
+        append_column(open_files_column_view, &i18n("Mode"), false, open_file_mode);
+        append_column(
+            open_files_column_view,
+            &i18n("Position"),
+            false,
+            |open_file: &OpenFile| match (&open_file.target, open_file.position) {
+                (FileDescriptorTarget::Path(_), Some(position)) => position.to_string(),
+                _ => String::new(),
+            },
+        );
+
+        imp.view_stack
+            .connect_visible_child_name_notify(clone!(@weak self as this => move |_| {
+                this.refresh_open_files();
+            }));
+
         self.update(process);
     }
 
+    /// Reads the open files of the process again if they are currently being
+    /// shown
+    fn refresh_open_files(&self) {
+        let imp = self.imp();
+
+        if imp.view_stack.visible_child_name().as_deref() != Some("open_files")
+            || imp.open_files_loading.replace(true)
+        {
+            return;
+        }
+
+        let pid = imp.process_pid.get();
+        MainContext::default().spawn_local(clone!(@weak self as this => async move {
+            let imp = this.imp();
+
+            match Process::open_files(pid).await {
+                Ok(open_files) => {
+                    let open_files: Vec<glib::BoxedAnyObject> = open_files
+                        .into_iter()
+                        .map(glib::BoxedAnyObject::new)
+                        .collect();
+                    if let Some(open_files_store) = imp.open_files_store.get() {
+                        open_files_store.splice(0, open_files_store.n_items(), &open_files);
+                    }
+                    imp.open_files_stack.set_visible_child_name("open_files");
+                }
+                Err(_) => {
+                    imp.open_files_status_page.set_description(Some(&i18n(
+                        "Resources is not permitted to inspect the open files of this process, usually because it belongs to another user",
+                    )));
+                    imp.open_files_stack.set_visible_child_name("error");
+                }
+            }
+
+            imp.open_files_loading.set(false);
+        }));
+    }
+
+    /// Calls `f` with the PID of the shown process once the user wants to
+    /// change its priority.
+    pub fn connect_change_priority<F: Fn(i32) + 'static>(&self, f: F) {
+        self.imp().change_priority_button.connect_clicked(
+            clone!(@weak self as this => move |_| f(this.imp().process_pid.get())),
+        );
+    }
+
+    /// Calls `f` with the PID of the shown process once the user wants to
+    /// change its CPU affinity.
+    pub fn connect_change_affinity<F: Fn(i32) + 'static>(&self, f: F) {
+        self.imp().change_affinity_button.connect_clicked(
+            clone!(@weak self as this => move |_| f(this.imp().process_pid.get())),
+        );
+    }
+
+    /// Plots the usage during the last refreshes
+    pub fn update_history(&self, history: &UsageHistory) {
+        self.imp().history.update(history);
+    }
+
     pub fn update(&self, process: &ProcessItem) {
         let imp = self.imp();
 
@@ -110,6 +292,22 @@ impl ResProcessDialog {
         imp.memory_usage
             .set_subtitle(&convert_storage(process.memory_usage as f64, false));
 
+        let memory_details = process.memory_details;
+        for (row, value) in [
+            (&imp.memory_rss, memory_details.map(|details| details.rss)),
+            (&imp.memory_pss, memory_details.map(|details| details.pss)),
+            (&imp.memory_uss, memory_details.map(|details| details.uss)),
+            (
+                &imp.memory_shared,
+                memory_details.map(|details| details.shared),
+            ),
+            (&imp.memory_swap, memory_details.map(|details| details.swap)),
+        ] {
+            row.set_subtitle(
+                &value.map_or_else(|| i18n("N/A"), |value| convert_storage(value as f64, false)),
+            );
+        }
+
         if let Some(read_speed) = process.read_speed {
             imp.drive_read_speed
                 .set_subtitle(&convert_speed(read_speed, false));
@@ -139,6 +337,7 @@ impl ResProcessDialog {
         }
 
         imp.pid.set_subtitle(&process.pid.to_string());
+        imp.process_pid.set(process.pid);
 
         imp.commandline.set_subtitle(&process.commandline);
         imp.commandline.set_tooltip_text(Some(&process.commandline));
@@ -148,10 +347,109 @@ impl ResProcessDialog {
         imp.cgroup
             .set_tooltip_text(Some(&process.cgroup.clone().unwrap_or_else(|| i18n("N/A"))));
 
-        let containerized = match process.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
-        };
+        let containerized = containerization_description(&process.containerization).map_or_else(
+            || i18n("No"),
+            |description| i18n_f("Yes ({})", &[&description]),
+        );
         imp.containerized.set_subtitle(&containerized);
+
+        imp.nice.set_subtitle(&process.nice.to_string());
+        imp.scheduling_policy
+            .set_subtitle(&process::scheduling_policy_name(process.scheduling_policy));
+        imp.io_priority
+            .set_subtitle(&process::io_priority_name(process.io_priority));
+
+        // CPUs are counted from 1 in the UI, just like on the Processor page
+        imp.last_cpu
+            .set_subtitle(&i18n_f("CPU {}", &[&(process.last_cpu + 1).to_string()]));
+        imp.affinity
+            .set_subtitle(&process.affinity.as_ref().map_or_else(
+                || i18n("N/A"),
+                |cpus| {
+                    let cpus: Vec<usize> = cpus.iter().map(|cpu| cpu + 1).collect();
+                    i18n_f("CPU {}", &[&format_cpu_list(&cpus)])
+                },
+            ));
+
+        // busy threads first, that's usually what one is looking for
+        let mut threads = process.threads.clone();
+        threads.sort_by(|a, b| {
+            b.cpu_time_ratio
+                .total_cmp(&a.cpu_time_ratio)
+                .then(a.tid.cmp(&b.tid))
+        });
+        let threads: Vec<glib::BoxedAnyObject> =
+            threads.into_iter().map(glib::BoxedAnyObject::new).collect();
+        if let Some(threads_store) = imp.threads_store.get() {
+            threads_store.splice(0, threads_store.n_items(), &threads);
+        }
+
+        self.refresh_open_files();
+    }
+}
+
+/// Appends a column to a `gtk::ColumnView` whose model contains
+/// `glib::BoxedAnyObject`s of `T`
+fn append_column<T: 'static, F: Fn(&T) -> String + 'static>(
+    column_view: &gtk::ColumnView,
+    title: &str,
+    expand: bool,
+    text: F,
+) {
+    let factory = gtk::SignalListItemFactory::new();
+    factory.connect_setup(|_factory, item| {
+        let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+        item.set_child(Some(&gtk::Inscription::new(None)));
+    });
+    factory.connect_bind(move |_factory, item| {
+        let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+        let inscription = item.child().and_downcast::<gtk::Inscription>().unwrap();
+        let object = item.item().and_downcast::<glib::BoxedAnyObject>().unwrap();
+        inscription.set_text(Some(&text(&object.borrow::<T>())));
+    });
+
+    let column = gtk::ColumnViewColumn::new(Some(title), Some(factory));
+    column.set_resizable(true);
+    column.set_expand(expand);
+    column_view.append_column(&column);
+}
+
+fn open_file_type(open_file: &OpenFile) -> String {
+    match &open_file.target {
+        FileDescriptorTarget::Path(_) => i18n("File"),
+        FileDescriptorTarget::Socket(_, Some(socket)) => match socket.protocol {
+            SocketProtocol::Tcp => "TCP".to_string(),
+            SocketProtocol::Tcp6 => "TCPv6".to_string(),
+            SocketProtocol::Udp => "UDP".to_string(),
+            SocketProtocol::Udp6 => "UDPv6".to_string(),
+            SocketProtocol::Unix => "Unix".to_string(),
+        },
+        FileDescriptorTarget::Socket(_, None) => i18n("Socket"),
+        FileDescriptorTarget::Pipe(_) => i18n("Pipe"),
+        FileDescriptorTarget::Other(_) => i18n("Other"),
+    }
+}
+
+fn open_file_name(open_file: &OpenFile) -> String {
+    match &open_file.target {
+        FileDescriptorTarget::Path(path) | FileDescriptorTarget::Other(path) => path.clone(),
+        FileDescriptorTarget::Socket(_, Some(socket)) if socket.state == "ESTABLISHED" => {
+            format!("{} → {}", socket.local_address, socket.remote_address)
+        }
+        FileDescriptorTarget::Socket(_, Some(socket)) if !socket.local_address.is_empty() => {
+            socket.local_address.clone()
+        }
+        FileDescriptorTarget::Socket(inode, _) => format!("socket:[{inode}]"),
+        FileDescriptorTarget::Pipe(inode) => format!("pipe:[{inode}]"),
+    }
+}
+
+fn open_file_mode(open_file: &OpenFile) -> String {
+    // the access mode is stored in the lowest two bits of the flags
+    match open_file.flags.map(|flags| flags & 0o3) {
+        Some(0) => i18n("Read"),
+        Some(1) => i18n("Write"),
+        Some(2) => i18n("Read and Write"),
+        _ => i18n("N/A"),
     }
 }
@@ -3,7 +3,7 @@ use gtk::glib;
 
 use crate::{
     config::PROFILE,
-    utils::settings::{Base, RefreshSpeed, TemperatureUnit, SETTINGS},
+    utils::settings::{Base, CpuUsageMode, MemoryMetric, RefreshSpeed, TemperatureUnit, SETTINGS},
 };
 
 mod imp {
@@ -25,6 +25,10 @@ mod imp {
         #[template_child]
         pub refresh_speed_combo_row: TemplateChild<adw::ComboRow>,
         #[template_child]
+        pub memory_metric_combo_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub cpu_usage_mode_combo_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
         pub show_search_on_start_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub sidebar_details_row: TemplateChild<adw::SwitchRow>,
@@ -41,6 +45,12 @@ mod imp {
         pub apps_show_drive_write_speed_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub apps_show_drive_write_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_gpu_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_gpu_memory_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_pressure_row: TemplateChild<adw::SwitchRow>,
 
         #[template_child]
         pub processes_show_id_row: TemplateChild<adw::SwitchRow>,
@@ -58,6 +68,24 @@ mod imp {
         pub processes_show_drive_write_speed_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub processes_show_drive_write_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_gpu_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_gpu_memory_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_threads_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_nice_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_scheduling_policy_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_io_priority_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_state_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_start_time_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_running_time_row: TemplateChild<adw::SwitchRow>,
 
         #[template_child]
         pub show_virtual_drives_row: TemplateChild<adw::SwitchRow>,
@@ -132,6 +160,11 @@ impl ResSettingsDialog {
         imp.show_search_on_start_row
             .set_active(SETTINGS.show_search_on_start());
 
+        imp.memory_metric_combo_row
+            .set_selected((SETTINGS.memory_metric() as u8) as u32);
+        imp.cpu_usage_mode_combo_row
+            .set_selected((SETTINGS.cpu_usage_mode() as u8) as u32);
+
         imp.apps_show_memory_row
             .set_active(SETTINGS.apps_show_memory());
         imp.apps_show_cpu_row.set_active(SETTINGS.apps_show_cpu());
@@ -143,6 +176,11 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.apps_show_drive_write_speed());
         imp.apps_show_drive_write_total_row
             .set_active(SETTINGS.apps_show_drive_write_total());
+        imp.apps_show_gpu_row.set_active(SETTINGS.apps_show_gpu());
+        imp.apps_show_gpu_memory_row
+            .set_active(SETTINGS.apps_show_gpu_memory());
+        imp.apps_show_pressure_row
+            .set_active(SETTINGS.apps_show_pressure());
 
         imp.processes_show_id_row
             .set_active(SETTINGS.processes_show_id());
@@ -160,6 +198,24 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.processes_show_drive_write_speed());
         imp.processes_show_drive_write_total_row
             .set_active(SETTINGS.processes_show_drive_write_total());
+        imp.processes_show_gpu_row
+            .set_active(SETTINGS.processes_show_gpu());
+        imp.processes_show_gpu_memory_row
+            .set_active(SETTINGS.processes_show_gpu_memory());
+        imp.processes_show_threads_row
+            .set_active(SETTINGS.processes_show_threads());
+        imp.processes_show_nice_row
+            .set_active(SETTINGS.processes_show_nice());
+        imp.processes_show_scheduling_policy_row
+            .set_active(SETTINGS.processes_show_scheduling_policy());
+        imp.processes_show_io_priority_row
+            .set_active(SETTINGS.processes_show_io_priority());
+        imp.processes_show_state_row
+            .set_active(SETTINGS.processes_show_state());
+        imp.processes_show_start_time_row
+            .set_active(SETTINGS.processes_show_start_time());
+        imp.processes_show_running_time_row
+            .set_active(SETTINGS.processes_show_running_time());
 
         imp.show_virtual_drives_row
             .set_active(SETTINGS.show_virtual_drives());
@@ -200,6 +256,20 @@ impl ResSettingsDialog {
             let _ = SETTINGS.set_sidebar_details(switch_row.is_active());
         });
 
+        imp.memory_metric_combo_row
+            .connect_selected_item_notify(|combo_row| {
+                if let Some(memory_metric) = MemoryMetric::from_repr(combo_row.selected() as u8) {
+                    let _ = SETTINGS.set_memory_metric(memory_metric);
+                }
+            });
+
+        imp.cpu_usage_mode_combo_row
+            .connect_selected_item_notify(|combo_row| {
+                if let Some(cpu_usage_mode) = CpuUsageMode::from_repr(combo_row.selected() as u8) {
+                    let _ = SETTINGS.set_cpu_usage_mode(cpu_usage_mode);
+                }
+            });
+
         imp.show_search_on_start_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_show_search_on_start(switch_row.is_active());
@@ -234,6 +304,20 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_apps_show_drive_write_total(switch_row.is_active());
             });
 
+        imp.apps_show_gpu_row.connect_active_notify(|switch_row| {
+            let _ = SETTINGS.set_apps_show_gpu(switch_row.is_active());
+        });
+
+        imp.apps_show_gpu_memory_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_gpu_memory(switch_row.is_active());
+            });
+
+        imp.apps_show_pressure_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_pressure(switch_row.is_active());
+            });
+
         imp.processes_show_id_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_processes_show_id(switch_row.is_active());
@@ -274,6 +358,51 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_processes_show_drive_write_total(switch_row.is_active());
             });
 
+        imp.processes_show_gpu_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_gpu(switch_row.is_active());
+            });
+
+        imp.processes_show_gpu_memory_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_gpu_memory(switch_row.is_active());
+            });
+
+        imp.processes_show_threads_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_threads(switch_row.is_active());
+            });
+
+        imp.processes_show_nice_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_nice(switch_row.is_active());
+            });
+
+        imp.processes_show_scheduling_policy_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_scheduling_policy(switch_row.is_active());
+            });
+
+        imp.processes_show_io_priority_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_io_priority(switch_row.is_active());
+            });
+
+        imp.processes_show_state_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_state(switch_row.is_active());
+            });
+
+        imp.processes_show_start_time_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_start_time(switch_row.is_active());
+            });
+
+        imp.processes_show_running_time_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_running_time(switch_row.is_active());
+            });
+
         imp.show_virtual_drives_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_show_virtual_drives(switch_row.is_active());
@@ -0,0 +1,98 @@
+use adw::{prelude::*, ResponseAppearance};
+use gtk::glib::clone;
+
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::window::MainWindow;
+use crate::utils::process::ProcessAction;
+
+/// The signals that can be picked directly, realtime signals come after them
+const SIGNALS: [ProcessAction; 9] = [
+    ProcessAction::HUP,
+    ProcessAction::INT,
+    ProcessAction::QUIT,
+    ProcessAction::TERM,
+    ProcessAction::KILL,
+    ProcessAction::STOP,
+    ProcessAction::CONT,
+    ProcessAction::USR1,
+    ProcessAction::USR2,
+];
+
+fn signal_label(action: ProcessAction) -> String {
+    let description = match action {
+        ProcessAction::HUP => i18n("Hangup"),
+        ProcessAction::INT => i18n("Interrupt"),
+        ProcessAction::QUIT => i18n("Quit"),
+        ProcessAction::TERM => i18n("Terminate"),
+        ProcessAction::KILL => i18n("Kill"),
+        ProcessAction::STOP => i18n("Stop"),
+        ProcessAction::CONT => i18n("Continue"),
+        ProcessAction::USR1 => i18n("User-Defined Signal 1"),
+        ProcessAction::USR2 => i18n("User-Defined Signal 2"),
+        ProcessAction::RT(_) => i18n("Realtime Signal"),
+    };
+    format!("{description} (SIG{})", action.signal_name())
+}
+
+/// Lets the user pick a signal that is to be sent to `name`. `on_send` is
+/// called with the chosen signal once the user confirms.
+pub fn show_signal_dialog<F: Fn(ProcessAction) + 'static>(name: &str, on_send: F) {
+    let signal_labels: Vec<String> = SIGNALS
+        .into_iter()
+        .map(signal_label)
+        .chain(std::iter::once(format!(
+            "{} (SIGRTMIN+n)",
+            i18n("Realtime Signal")
+        )))
+        .collect();
+    let signal_labels: Vec<&str> = signal_labels.iter().map(String::as_str).collect();
+
+    let signal_row = adw::ComboRow::builder()
+        .title(i18n("Signal"))
+        .model(&gtk::StringList::new(&signal_labels))
+        .build();
+
+    let realtime_row =
+        adw::SpinRow::with_range(0.0, f64::from(ProcessAction::max_realtime_offset()), 1.0);
+    realtime_row.set_title(&i18n("Realtime Signal Offset"));
+    realtime_row.set_visible(false);
+
+    signal_row.connect_selected_notify(clone!(@weak realtime_row => move |signal_row| {
+        realtime_row.set_visible(signal_row.selected() as usize == SIGNALS.len());
+    }));
+
+    let list_box = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    list_box.add_css_class("boxed-list");
+    list_box.append(&signal_row);
+    list_box.append(&realtime_row);
+
+    let dialog = adw::MessageDialog::builder()
+        .transient_for(&MainWindow::default())
+        .modal(true)
+        .heading(i18n_f("Send a Signal to {}", &[name]))
+        .body(i18n(
+            "Processes that don't handle the chosen signal will usually end. Unsaved work might be lost.",
+        ))
+        .extra_child(&list_box)
+        .build();
+
+    dialog.add_response("no", &i18n("Cancel"));
+    dialog.add_response("yes", &i18n("Send Signal"));
+    dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+    dialog.set_default_response(Some("no"));
+    dialog.set_close_response("no");
+
+    dialog.connect_response(None, move |_, response| {
+        if response == "yes" {
+            let action = SIGNALS
+                .get(signal_row.selected() as usize)
+                .copied()
+                .unwrap_or_else(|| ProcessAction::RT(realtime_row.value() as u8));
+            on_send(action);
+        }
+    });
+
+    dialog.show();
+}
@@ -3,7 +3,10 @@ use gtk::{
     subclass::prelude::ObjectSubclassIsExt,
 };
 
+use crate::i18n::i18n_f;
 use crate::utils::app::AppItem;
+use crate::utils::process::{containerization_description, containerization_name};
+use crate::utils::search::{Field, Searchable};
 
 mod imp {
     use std::cell::{Cell, RefCell};
@@ -32,6 +35,12 @@ mod imp {
         #[property(get = Self::icon, set = Self::set_icon, type = Icon)]
         icon: RefCell<Icon>,
 
+        #[property(get = Self::containerization, set = Self::set_containerization, type = glib::GString)]
+        containerization: Cell<glib::GString>, // will be empty if not containerized
+
+        #[property(get = Self::containerization_description, set = Self::set_containerization_description, type = glib::GString)]
+        containerization_description: Cell<glib::GString>,
+
         #[property(get, set)]
         cpu_usage: Cell<f32>,
 
@@ -50,6 +59,23 @@ mod imp {
         #[property(get, set)]
         write_total: Cell<u64>,
 
+        #[property(get, set)]
+        gpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        gpu_memory: Cell<u64>,
+
+        #[property(get, set)]
+        frozen: Cell<bool>,
+
+        /// The highest `some avg10` pressure of the app in percent, NaN if
+        /// it isn't available
+        #[property(get, set)]
+        pressure: Cell<f64>,
+
+        #[property(get = Self::pressure_description, set = Self::set_pressure_description, type = glib::GString)]
+        pressure_description: Cell<glib::GString>,
+
         pub app_item: RefCell<Option<AppItem>>,
     }
 
@@ -60,12 +86,19 @@ mod imp {
                 id: Cell::new(None),
                 description: Cell::new(None),
                 icon: RefCell::new(ThemedIcon::new("generic-process").into()),
+                containerization: Cell::new(glib::GString::default()),
+                containerization_description: Cell::new(glib::GString::default()),
                 cpu_usage: Cell::new(0.0),
                 memory_usage: Cell::new(0),
                 read_speed: Cell::new(0.0),
                 read_total: Cell::new(0),
                 write_speed: Cell::new(0.0),
                 write_total: Cell::new(0),
+                gpu_usage: Cell::new(0.0),
+                gpu_memory: Cell::new(0),
+                frozen: Cell::new(false),
+                pressure: Cell::new(f64::NAN),
+                pressure_description: Cell::new(glib::GString::default()),
                 app_item: RefCell::new(None),
             }
         }
@@ -117,6 +150,43 @@ mod imp {
         pub fn set_icon(&self, icon: &Icon) {
             self.icon.set(icon.clone());
         }
+
+        pub fn containerization(&self) -> glib::GString {
+            let containerization = self.containerization.take();
+            let result = containerization.clone();
+            self.containerization.set(containerization);
+            result
+        }
+
+        pub fn set_containerization(&self, containerization: &str) {
+            self.containerization
+                .set(glib::GString::from(containerization));
+        }
+
+        pub fn containerization_description(&self) -> glib::GString {
+            let containerization_description = self.containerization_description.take();
+            let result = containerization_description.clone();
+            self.containerization_description
+                .set(containerization_description);
+            result
+        }
+
+        pub fn set_containerization_description(&self, containerization_description: &str) {
+            self.containerization_description
+                .set(glib::GString::from(containerization_description));
+        }
+
+        pub fn pressure_description(&self) -> glib::GString {
+            let pressure_description = self.pressure_description.take();
+            let result = pressure_description.clone();
+            self.pressure_description.set(pressure_description);
+            result
+        }
+
+        pub fn set_pressure_description(&self, pressure_description: &str) {
+            self.pressure_description
+                .set(glib::GString::from(pressure_description));
+        }
     }
 
     #[glib::object_subclass]
@@ -155,23 +225,42 @@ impl ApplicationEntry {
             .property("icon", &app_item.icon)
             .property("id", &app_item.id)
             .build();
-        this.set_cpu_usage(app_item.cpu_time_ratio);
-        this.set_memory_usage(app_item.memory_usage as u64);
-        this.set_read_speed(app_item.read_speed);
-        this.set_read_total(app_item.read_total);
-        this.set_write_speed(app_item.write_speed);
-        this.set_write_total(app_item.write_total);
-        this.imp().app_item.replace(Some(app_item));
+        this.update(app_item);
         this
     }
 
     pub fn update(&self, app_item: AppItem) {
+        // the first process of an app that is containerized might only be
+        // started after the app has been registered
+        self.set_containerization(
+            containerization_name(&app_item.containerization).unwrap_or_default(),
+        );
+        self.set_containerization_description(
+            containerization_description(&app_item.containerization).unwrap_or_default(),
+        );
         self.set_cpu_usage(app_item.cpu_time_ratio);
         self.set_memory_usage(app_item.memory_usage as u64);
         self.set_read_speed(app_item.read_speed);
         self.set_read_total(app_item.read_total);
         self.set_write_speed(app_item.write_speed);
         self.set_write_total(app_item.write_total);
+        self.set_gpu_usage(app_item.gpu_usage);
+        self.set_gpu_memory(app_item.gpu_memory);
+        self.set_frozen(app_item.frozen);
+        if let Some(pressure) = app_item.pressure {
+            self.set_pressure(pressure.highest_avg10());
+            self.set_pressure_description(&i18n_f(
+                "Processor: {} % · Memory: {} % · Drives: {} %",
+                &[
+                    &format!("{:.1}", pressure.cpu.some.avg10),
+                    &format!("{:.1}", pressure.memory.some.avg10),
+                    &format!("{:.1}", pressure.io.some.avg10),
+                ],
+            ));
+        } else {
+            self.set_pressure(f64::NAN);
+            self.set_pressure_description("");
+        }
         self.imp().app_item.replace(Some(app_item));
     }
 
@@ -182,3 +271,39 @@ impl ApplicationEntry {
         item
     }
 }
+
+impl Searchable for ApplicationEntry {
+    fn default_fields(&self) -> &'static [Field] {
+        &[Field::Name, Field::Id, Field::Description]
+    }
+
+    fn text(&self, field: Field) -> Option<String> {
+        match field {
+            Field::Name => Some(self.name().to_string()),
+            Field::Id => self.id().map(|id| id.to_string()),
+            Field::Description => self
+                .description()
+                .map(|description| description.to_string()),
+            Field::Cgroup => self
+                .imp()
+                .app_item
+                .borrow()
+                .as_ref()
+                .map(|app_item| app_item.cgroup_paths.join("\n")),
+            Field::Container => Some(self.containerization_description().to_string()),
+            _ => None,
+        }
+    }
+
+    fn number(&self, field: Field) -> Option<f64> {
+        match field {
+            Field::Cpu => Some(f64::from(self.cpu_usage()) * 100.0),
+            Field::Memory => Some(self.memory_usage() as f64),
+            Field::Read => Some(self.read_speed()),
+            Field::Write => Some(self.write_speed()),
+            Field::Gpu => Some(f64::from(self.gpu_usage()) * 100.0),
+            Field::VideoMemory => Some(self.gpu_memory() as f64),
+            _ => None,
+        }
+    }
+}
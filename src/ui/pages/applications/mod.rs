@@ -1,5 +1,5 @@
 mod application_entry;
-mod application_name_cell;
+pub mod application_name_cell;
 
 use std::collections::HashSet;
 
@@ -14,11 +14,13 @@ use log::error;
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
 use crate::ui::dialogs::app_dialog::ResAppDialog;
+use crate::ui::dialogs::signal_dialog;
+use crate::ui::pages::columns;
 use crate::ui::window::{self, Action, MainWindow};
 use crate::utils::app::{AppItem, AppsContext};
 use crate::utils::process::ProcessAction;
+use crate::utils::search::{parse_search_entry, Query};
 use crate::utils::settings::SETTINGS;
-use crate::utils::units::{convert_speed, convert_storage};
 
 use self::application_entry::ApplicationEntry;
 use self::application_name_cell::ResApplicationNameCell;
@@ -54,13 +56,16 @@ mod imp {
         #[template_child]
         pub search_button: TemplateChild<gtk::ToggleButton>,
         #[template_child]
+        pub select_all_button: TemplateChild<gtk::Button>,
+        #[template_child]
         pub information_button: TemplateChild<gtk::Button>,
         #[template_child]
         pub end_application_button: TemplateChild<adw::SplitButton>,
 
         pub store: RefCell<gio::ListStore>,
-        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub selection_model: RefCell<gtk::MultiSelection>,
         pub filter_model: RefCell<gtk::FilterListModel>,
+        pub search_query: RefCell<Option<Query>>,
         pub sort_model: RefCell<gtk::SortListModel>,
         pub column_view: RefCell<gtk::ColumnView>,
         pub open_dialog: RefCell<Option<(Option<String>, ResAppDialog)>>,
@@ -107,10 +112,12 @@ mod imp {
                 search_revealer: Default::default(),
                 search_entry: Default::default(),
                 search_button: Default::default(),
+                select_all_button: Default::default(),
                 information_button: Default::default(),
                 store: gio::ListStore::new::<ApplicationEntry>().into(),
                 selection_model: Default::default(),
                 filter_model: Default::default(),
+                search_query: Default::default(),
                 sort_model: Default::default(),
                 column_view: Default::default(),
                 open_dialog: Default::default(),
@@ -136,8 +143,9 @@ mod imp {
                 "applications.kill-application",
                 None,
                 move |res_applications, _, _| {
-                    if let Some(app) = res_applications.get_selected_app_item() {
-                        res_applications.execute_process_action_dialog(app, ProcessAction::KILL);
+                    let apps = res_applications.get_selected_app_items();
+                    if !apps.is_empty() {
+                        res_applications.execute_process_action_dialog(apps, ProcessAction::KILL);
                     }
                 },
             );
@@ -146,8 +154,9 @@ mod imp {
                 "applications.halt-application",
                 None,
                 move |res_applications, _, _| {
-                    if let Some(app) = res_applications.get_selected_app_item() {
-                        res_applications.execute_process_action_dialog(app, ProcessAction::STOP);
+                    let apps = res_applications.get_selected_app_items();
+                    if !apps.is_empty() {
+                        res_applications.execute_process_action_dialog(apps, ProcessAction::STOP);
                     }
                 },
             );
@@ -156,8 +165,19 @@ mod imp {
                 "applications.continue-application",
                 None,
                 move |res_applications, _, _| {
-                    if let Some(app) = res_applications.get_selected_app_item() {
-                        res_applications.execute_process_action_dialog(app, ProcessAction::CONT);
+                    let apps = res_applications.get_selected_app_items();
+                    if !apps.is_empty() {
+                        res_applications.execute_process_action_dialog(apps, ProcessAction::CONT);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "applications.send-signal",
+                None,
+                move |res_applications, _, _| {
+                    if let [app] = res_applications.get_selected_app_items().as_slice() {
+                        res_applications.send_signal_dialog(app.clone());
                     }
                 },
             );
@@ -226,7 +246,7 @@ impl ResApplications {
     pub fn setup_widgets(&self) {
         let imp = self.imp();
 
-        let column_view = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = gtk::ColumnView::new(None::<gtk::MultiSelection>);
         let store = gio::ListStore::new::<ApplicationEntry>();
         let filter_model = gtk::FilterListModel::new(
             Some(store.clone()),
@@ -235,10 +255,8 @@ impl ResApplications {
             )),
         );
         let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
-        let selection_model = gtk::SingleSelection::new(Some(sort_model.clone()));
+        let selection_model = gtk::MultiSelection::new(Some(sort_model.clone()));
         column_view.set_model(Some(&selection_model));
-        selection_model.set_can_unselect(true);
-        selection_model.set_autoselect(false);
 
         *imp.store.borrow_mut() = store;
         *imp.selection_model.borrow_mut() = selection_model;
@@ -260,6 +278,15 @@ impl ResApplications {
             item.property_expression("item")
                 .chain_property::<ApplicationEntry>("icon")
                 .bind(&row, "icon", Widget::NONE);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("containerization")
+                .bind(&row, "badge", Widget::NONE);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("containerization_description")
+                .bind(&row, "badge_tooltip", Widget::NONE);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("frozen")
+                .bind(&row, "frozen", Widget::NONE);
         });
         let name_col_sorter = CustomSorter::new(move |a, b| {
             let item_a = a.downcast_ref::<ApplicationEntry>().unwrap();
@@ -272,135 +299,56 @@ impl ResApplications {
         });
         name_col.set_sorter(Some(&name_col_sorter));
 
-        let memory_col_factory = gtk::SignalListItemFactory::new();
-        let memory_col =
-            gtk::ColumnViewColumn::new(Some(&i18n("Memory")), Some(memory_col_factory.clone()));
-        memory_col.set_resizable(true);
-        memory_col_factory.connect_setup(move |_factory, item| {
-            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            let row = gtk::Inscription::new(None);
-            row.set_min_chars(9);
-            item.set_child(Some(&row));
-            item.property_expression("item")
-                .chain_property::<ApplicationEntry>("memory_usage")
-                .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
-                    convert_storage(memory_usage as f64, false)
-                }))
-                .bind(&row, "text", Widget::NONE);
-        });
-        let memory_col_sorter = CustomSorter::new(move |a, b| {
-            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().memory_usage();
-            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().memory_usage();
-            item_a.cmp(&item_b).into()
-        });
-        memory_col.set_sorter(Some(&memory_col_sorter));
+        let [memory_col, cpu_col, read_speed_col, read_total_col, write_speed_col, write_total_col] =
+            columns::usage_columns::<ApplicationEntry>();
         memory_col.set_visible(SETTINGS.apps_show_memory());
-
-        let cpu_col_factory = gtk::SignalListItemFactory::new();
-        let cpu_col =
-            gtk::ColumnViewColumn::new(Some(&i18n("Processor")), Some(cpu_col_factory.clone()));
-        cpu_col.set_resizable(true);
-        cpu_col_factory.connect_setup(move |_factory, item| {
-            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            let row = gtk::Inscription::new(None);
-            item.set_child(Some(&row));
-            item.property_expression("item")
-                .chain_property::<ApplicationEntry>("cpu_usage")
-                .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
-                    format!("{:.1} %", cpu_usage * 100.0)
-                }))
-                .bind(&row, "text", Widget::NONE);
-        });
-        let cpu_col_sorter = CustomSorter::new(move |a, b| {
-            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().cpu_usage();
-            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().cpu_usage();
-            if item_a > item_b {
-                Ordering::Larger
-            } else if item_a < item_b {
-                Ordering::Smaller
-            } else {
-                Ordering::Equal
-            }
-        });
-        cpu_col.set_sorter(Some(&cpu_col_sorter));
         cpu_col.set_visible(SETTINGS.apps_show_cpu());
-
-        let read_speed_col_factory = gtk::SignalListItemFactory::new();
-        let read_speed_col = gtk::ColumnViewColumn::new(
-            Some(&i18n("Drive Read")),
-            Some(read_speed_col_factory.clone()),
-        );
-        read_speed_col.set_resizable(true);
-        read_speed_col_factory.connect_setup(move |_factory, item| {
-            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            let row = gtk::Inscription::new(None);
-            item.set_child(Some(&row));
-            item.property_expression("item")
-                .chain_property::<ApplicationEntry>("read_speed")
-                .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
-                    convert_speed(read_speed, false)
-                }))
-                .bind(&row, "text", Widget::NONE);
-        });
-        let read_speed_col_sorter = CustomSorter::new(move |a, b| {
-            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().read_speed();
-            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().read_speed();
-            if item_a > item_b {
-                Ordering::Larger
-            } else if item_a < item_b {
-                Ordering::Smaller
-            } else {
-                Ordering::Equal
-            }
-        });
-        read_speed_col.set_sorter(Some(&read_speed_col_sorter));
         read_speed_col.set_visible(SETTINGS.apps_show_drive_read_speed());
+        read_total_col.set_visible(SETTINGS.apps_show_drive_read_total());
+        write_speed_col.set_visible(SETTINGS.apps_show_drive_write_speed());
+        write_total_col.set_visible(SETTINGS.apps_show_drive_write_total());
 
-        let read_total_col_factory = gtk::SignalListItemFactory::new();
-        let read_total_col = gtk::ColumnViewColumn::new(
-            Some(&i18n("Drive Read Total")),
-            Some(read_total_col_factory.clone()),
+        let gpu_col = columns::text_column::<ApplicationEntry, f32>(
+            &i18n("GPU"),
+            "gpu_usage",
+            None,
+            columns::format_percentage,
         );
-        read_total_col.set_resizable(true);
-        read_total_col_factory.connect_setup(move |_factory, item| {
-            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            let row = gtk::Inscription::new(None);
-            item.set_child(Some(&row));
-            item.property_expression("item")
-                .chain_property::<ApplicationEntry>("read_total")
-                .chain_closure::<String>(closure!(|_: Option<Object>, read_total: u64| {
-                    convert_storage(read_total as f64, false)
-                }))
-                .bind(&row, "text", Widget::NONE);
-        });
-        let read_total_col_sorter = CustomSorter::new(move |a, b| {
-            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().read_total();
-            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().read_total();
-            item_a.cmp(&item_b).into()
-        });
-        read_total_col.set_sorter(Some(&read_total_col_sorter));
-        read_total_col.set_visible(SETTINGS.apps_show_drive_read_total());
+        gpu_col.set_visible(SETTINGS.apps_show_gpu());
 
-        let write_speed_col_factory = gtk::SignalListItemFactory::new();
-        let write_speed_col = gtk::ColumnViewColumn::new(
-            Some(&i18n("Drive Write")),
-            Some(write_speed_col_factory.clone()),
+        let gpu_memory_col = columns::text_column::<ApplicationEntry, u64>(
+            &i18n("Video Memory"),
+            "gpu_memory",
+            None,
+            columns::format_storage,
         );
-        write_speed_col.set_resizable(true);
-        write_speed_col_factory.connect_setup(move |_factory, item| {
+        gpu_memory_col.set_visible(SETTINGS.apps_show_gpu_memory());
+
+        let pressure_col_factory = gtk::SignalListItemFactory::new();
+        let pressure_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Pressure")), Some(pressure_col_factory.clone()));
+        pressure_col.set_resizable(true);
+        pressure_col_factory.connect_setup(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
             item.property_expression("item")
-                .chain_property::<ApplicationEntry>("write_speed")
-                .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
-                    convert_speed(write_speed, false)
+                .chain_property::<ApplicationEntry>("pressure")
+                .chain_closure::<String>(closure!(|_: Option<Object>, pressure: f64| {
+                    if pressure.is_nan() {
+                        i18n("N/A")
+                    } else {
+                        format!("{pressure:.1} %")
+                    }
                 }))
                 .bind(&row, "text", Widget::NONE);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("pressure_description")
+                .bind(&row, "tooltip-text", Widget::NONE);
         });
-        let write_speed_col_sorter = CustomSorter::new(move |a, b| {
-            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().write_speed();
-            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().write_speed();
+        let pressure_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().pressure();
+            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().pressure();
             if item_a > item_b {
                 Ordering::Larger
             } else if item_a < item_b {
@@ -409,33 +357,8 @@ impl ResApplications {
                 Ordering::Equal
             }
         });
-        write_speed_col.set_sorter(Some(&write_speed_col_sorter));
-        write_speed_col.set_visible(SETTINGS.apps_show_drive_write_speed());
-
-        let write_total_col_factory = gtk::SignalListItemFactory::new();
-        let write_total_col = gtk::ColumnViewColumn::new(
-            Some(&i18n("Drive Write Total")),
-            Some(write_total_col_factory.clone()),
-        );
-        write_total_col.set_resizable(true);
-        write_total_col_factory.connect_setup(move |_factory, item| {
-            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            let row = gtk::Inscription::new(None);
-            item.set_child(Some(&row));
-            item.property_expression("item")
-                .chain_property::<ApplicationEntry>("write_total")
-                .chain_closure::<String>(closure!(|_: Option<Object>, write_total: u64| {
-                    convert_storage(write_total as f64, false)
-                }))
-                .bind(&row, "text", Widget::NONE);
-        });
-        let write_total_col_sorter = CustomSorter::new(move |a, b| {
-            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().write_total();
-            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().write_total();
-            item_a.cmp(&item_b).into()
-        });
-        write_total_col.set_sorter(Some(&write_total_col_sorter));
-        write_total_col.set_visible(SETTINGS.apps_show_drive_write_total());
+        pressure_col.set_sorter(Some(&pressure_col_sorter));
+        pressure_col.set_visible(SETTINGS.apps_show_pressure());
 
         column_view.append_column(&name_col);
         column_view.append_column(&memory_col);
@@ -444,6 +367,9 @@ impl ResApplications {
         column_view.append_column(&read_total_col);
         column_view.append_column(&write_speed_col);
         column_view.append_column(&write_total_col);
+        column_view.append_column(&gpu_col);
+        column_view.append_column(&gpu_memory_col);
+        column_view.append_column(&pressure_col);
 
         column_view.sort_by_column(Some(&name_col), SortType::Ascending);
 
@@ -459,6 +385,9 @@ impl ResApplications {
         SETTINGS.connect_apps_show_drive_write_total(move |visible| {
             write_total_col.set_visible(visible)
         });
+        SETTINGS.connect_apps_show_gpu(move |visible| gpu_col.set_visible(visible));
+        SETTINGS.connect_apps_show_gpu_memory(move |visible| gpu_memory_col.set_visible(visible));
+        SETTINGS.connect_apps_show_pressure(move |visible| pressure_col.set_visible(visible));
 
         column_view.set_enable_rubberband(true);
         imp.applications_scrolled_window
@@ -470,20 +399,21 @@ impl ResApplications {
         let imp = self.imp();
 
         imp.selection_model.borrow().connect_selection_changed(
-        clone!(@strong self as this => move |model, _, _| {
-            let imp = this.imp();
-                let is_system_processes = model.selected_item().map_or(false, |object| {
-                    object
-                    .downcast::<ApplicationEntry>()
-                    .unwrap()
-                    .id()
-                    .is_none()
-                });
-                imp.information_button.set_sensitive(model.selected() != u32::MAX);
-                imp.end_application_button.set_sensitive(model.selected() != u32::MAX && !is_system_processes);
+            clone!(@strong self as this => move |model, _, _| {
+                let imp = this.imp();
+                // System Processes can't be ended as a whole, so they're left out here
+                let apps = this.get_selected_app_items().len();
+                imp.information_button.set_sensitive(model.selection().size() == 1);
+                imp.end_application_button.set_sensitive(apps > 0);
+                this.action_set_enabled("applications.send-signal", apps == 1);
             }),
         );
 
+        imp.select_all_button
+            .connect_clicked(clone!(@strong self as this => move |_| {
+                this.imp().selection_model.borrow().select_all();
+            }));
+
         imp.search_button
             .connect_toggled(clone!(@strong self as this => move |button| {
                 let imp = this.imp();
@@ -498,7 +428,8 @@ impl ResApplications {
 
         imp.search_entry
             .connect_search_changed(clone!(@strong self as this => move |_| {
-            let imp = this.imp();
+                let imp = this.imp();
+                *imp.search_query.borrow_mut() = parse_search_entry(&imp.search_entry);
                 if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
@@ -507,25 +438,36 @@ impl ResApplications {
         imp.information_button
             .connect_clicked(clone!(@strong self as this => move |_| {
                 let imp = this.imp();
-                let selection_option = imp.selection_model.borrow()
-                .selected_item()
-                .map(|object| {
-                    object
-                    .downcast::<ApplicationEntry>()
-                    .unwrap()
-                });
-                if let Some(selection) = selection_option {
+                if let [selection] = this.get_selected_app_entries().as_slice() {
                     let app_dialog = ResAppDialog::new();
                     app_dialog.init(selection.app_item().as_ref().unwrap());
+                    if let Some(id) = selection.id() {
+                        app_dialog.connect_apply_limits(clone!(@weak this => move |previous, limits| {
+                            let imp = this.imp();
+                            send!(
+                                imp.sender.get().unwrap(),
+                                Action::LimitApp(previous, limits, id.to_string(), imp.toast_overlay.get())
+                            );
+                        }));
+                    }
                     app_dialog.show();
+                    // the graphs would be empty until the next refresh otherwise
+                    send!(
+                        imp.sender.get().unwrap(),
+                        Action::UpdateAppHistory(
+                            selection.id().map(|gs| gs.to_string()),
+                            app_dialog.clone()
+                        )
+                    );
                     *imp.open_dialog.borrow_mut() = Some((selection.id().map(|gs| gs.to_string()), app_dialog));
                 }
             }));
 
         imp.end_application_button
             .connect_clicked(clone!(@strong self as this => move |_| {
-                if let Some(app) = this.get_selected_app_item() {
-                    this.execute_process_action_dialog(app, ProcessAction::TERM);
+                let apps = this.get_selected_app_items();
+                if !apps.is_empty() {
+                    this.execute_process_action_dialog(apps, ProcessAction::TERM);
                 }
             }));
     }
@@ -533,26 +475,42 @@ impl ResApplications {
     fn search_filter(&self, obj: &Object) -> bool {
         let imp = self.imp();
         let item = obj.downcast_ref::<ApplicationEntry>().unwrap();
-        let search_string = imp.search_entry.text().to_string().to_lowercase();
         !imp.search_revealer.reveals_child()
-            || item.name().to_lowercase().contains(&search_string)
-            || item
-                .id()
-                .map(|id| id.to_lowercase().contains(&search_string))
-                .unwrap_or_default()
-            || item
-                .description()
-                .unwrap_or_default()
-                .to_lowercase()
-                .contains(&search_string)
+            || imp
+                .search_query
+                .borrow()
+                .as_ref()
+                .map_or(true, |query| query.matches(item))
+    }
+
+    /// Returns all selected entries in the order they're shown in
+    fn get_selected_app_entries(&self) -> Vec<ApplicationEntry> {
+        let selection_model = self.imp().selection_model.borrow();
+        let selection = selection_model.selection();
+        (0..selection.size())
+            .filter_map(|i| selection_model.item(selection.nth(i as u32)))
+            .map(|object| object.downcast::<ApplicationEntry>().unwrap())
+            .collect()
     }
 
-    fn get_selected_app_item(&self) -> Option<AppItem> {
+    /// Returns the selected apps except for System Processes
+    fn get_selected_app_items(&self) -> Vec<AppItem> {
+        self.get_selected_app_entries()
+            .iter()
+            .filter(|entry| entry.id().is_some())
+            .filter_map(ApplicationEntry::app_item)
+            .collect()
+    }
+
+    /// Returns the ID of the app whose dialog is open, `Some(None)` stands
+    /// for the system processes
+    pub fn dialog_app(&self) -> Option<Option<String>> {
         self.imp()
-            .selection_model
+            .open_dialog
             .borrow()
-            .selected_item()
-            .and_then(|object| object.downcast::<ApplicationEntry>().unwrap().app_item())
+            .as_ref()
+            .filter(|(_, dialog)| dialog.is_visible())
+            .map(|(id, _)| id.clone())
     }
 
     pub fn refresh_apps_list(&self, apps: &AppsContext) {
@@ -589,6 +547,9 @@ impl ResApplications {
                     if let Some((dialog_id, dialog)) = dialog_opt {
                         if *dialog_id == app_id {
                             dialog.update(&new_item);
+                            if let Some(history) = apps.app_history(app_id.as_deref()) {
+                                dialog.update_history(history);
+                            }
                         }
                     }
                     object.update(new_item);
@@ -624,23 +585,40 @@ impl ResApplications {
         );
     }
 
-    pub fn execute_process_action_dialog(&self, app: AppItem, action: ProcessAction) {
-        let imp = self.imp();
+    pub fn send_signal_dialog(&self, app: AppItem) {
+        signal_dialog::show_signal_dialog(
+            &app.display_name,
+            clone!(@strong self as this, @strong app => move |action| {
+                let imp = this.imp();
+                send!(
+                    imp.sender.get().unwrap(),
+                    Action::ManipulateApp(action, app.id.clone().unwrap(), imp.toast_overlay.get())
+                );
+            }),
+        );
+    }
 
+    /// Executes `action` on `apps` after asking for confirmation
+    pub fn execute_process_action_dialog(&self, apps: Vec<AppItem>, action: ProcessAction) {
         // Nothing too bad can happen on Continue so dont show the dialog
         if action == ProcessAction::CONT {
-            send!(
-                imp.sender.get().unwrap(),
-                Action::ManipulateApp(action, app.id.unwrap(), self.imp().toast_overlay.get())
-            );
+            self.send_app_action(&apps, action);
             return;
         }
 
+        let display_names = apps
+            .iter()
+            .map(|app| app.display_name.as_str())
+            .collect::<Vec<_>>();
+
         // Confirmation dialog & warning
         let dialog = adw::MessageDialog::builder()
             .transient_for(&MainWindow::default())
             .modal(true)
-            .heading(window::get_action_name(action, &[&app.display_name]))
+            .heading(window::get_action_name(
+                action,
+                &[&window::name_list(&display_names)],
+            ))
             .body(window::get_app_action_warning(action))
             .build();
 
@@ -654,17 +632,29 @@ impl ResApplications {
         // Called when "yes" or "no" were clicked
         dialog.connect_response(
             None,
-            clone!(@strong self as this, @strong app => move |_, response| {
+            clone!(@strong self as this => move |_, response| {
                 if response == "yes" {
-                    let imp = this.imp();
-                    send!(
-                        imp.sender.get().unwrap(),
-                        Action::ManipulateApp(action, app.id.clone().unwrap(), imp.toast_overlay.get())
-                    );
+                    this.send_app_action(&apps, action);
                 }
             }),
         );
 
         dialog.show();
     }
+
+    fn send_app_action(&self, apps: &[AppItem], action: ProcessAction) {
+        let imp = self.imp();
+
+        let action = if let [app] = apps {
+            Action::ManipulateApp(action, app.id.clone().unwrap(), imp.toast_overlay.get())
+        } else {
+            Action::ManipulateApps(
+                action,
+                apps.iter().filter_map(|app| app.id.clone()).collect(),
+                imp.toast_overlay.get(),
+            )
+        };
+
+        send!(imp.sender.get().unwrap(), action);
+    }
 }
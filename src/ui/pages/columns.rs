@@ -0,0 +1,87 @@
+use adw::prelude::*;
+use gtk::glib::{self, value::FromValue, Object};
+use gtk::{CustomSorter, Widget};
+
+use crate::i18n::i18n;
+use crate::utils::units::{convert_speed, convert_storage};
+
+/// Creates a resizable column titled `title` whose cells show the property
+/// `property` of items of type `T` formatted by `format` and which sorts the
+/// items by that property
+pub fn text_column<T, V>(
+    title: &str,
+    property: &'static str,
+    min_chars: Option<u32>,
+    format: fn(V) -> String,
+) -> gtk::ColumnViewColumn
+where
+    T: IsA<Object>,
+    V: for<'a> FromValue<'a> + PartialOrd + 'static,
+{
+    let factory = gtk::SignalListItemFactory::new();
+    let column = gtk::ColumnViewColumn::new(Some(title), Some(factory.clone()));
+    column.set_resizable(true);
+    factory.connect_setup(move |_factory, item| {
+        let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+        let row = gtk::Inscription::new(None);
+        if let Some(min_chars) = min_chars {
+            row.set_min_chars(min_chars);
+        }
+        item.set_child(Some(&row));
+        item.property_expression("item")
+            .chain_property::<T>(property)
+            .chain_closure::<String>(glib::RustClosure::new(move |values| {
+                let value = values[1]
+                    .get::<V>()
+                    .unwrap_or_else(|e| panic!("property {property} has an unexpected type: {e}"));
+                Some(format(value).to_value())
+            }))
+            .bind(&row, "text", Widget::NONE);
+    });
+    let sorter = CustomSorter::new(move |a, b| {
+        let value_a = a.property::<V>(property);
+        let value_b = b.property::<V>(property);
+        value_a
+            .partial_cmp(&value_b)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .into()
+    });
+    column.set_sorter(Some(&sorter));
+    column
+}
+
+/// Creates the memory, processor, drive read, drive read total, drive write
+/// and drive write total columns, in this order, for items of type `T` that
+/// have the respective properties
+pub fn usage_columns<T: IsA<Object>>() -> [gtk::ColumnViewColumn; 6] {
+    [
+        text_column::<T, u64>(&i18n("Memory"), "memory_usage", Some(9), format_storage),
+        text_column::<T, f32>(&i18n("Processor"), "cpu_usage", None, format_percentage),
+        text_column::<T, f64>(&i18n("Drive Read"), "read_speed", None, format_speed),
+        text_column::<T, u64>(
+            &i18n("Drive Read Total"),
+            "read_total",
+            None,
+            format_storage,
+        ),
+        text_column::<T, f64>(&i18n("Drive Write"), "write_speed", None, format_speed),
+        text_column::<T, u64>(
+            &i18n("Drive Write Total"),
+            "write_total",
+            None,
+            format_storage,
+        ),
+    ]
+}
+
+pub fn format_storage(bytes: u64) -> String {
+    convert_storage(bytes as f64, false)
+}
+
+pub fn format_speed(bytes_per_second: f64) -> String {
+    convert_speed(bytes_per_second, false)
+}
+
+pub fn format_percentage(ratio: f32) -> String {
+    format!("{:.1} %", ratio * 100.0)
+}
@@ -0,0 +1,169 @@
+use gtk::{
+    glib::{self},
+    subclass::prelude::ObjectSubclassIsExt,
+};
+
+use crate::utils::app::ContainerItem;
+use crate::utils::container;
+use crate::utils::process::containerization_name;
+
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties)]
+    #[properties(wrapper_type = super::ContainerEntry)]
+    pub struct ContainerEntry {
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::engine, set = Self::set_engine, type = glib::GString)]
+        engine: Cell<glib::GString>,
+
+        #[property(get = Self::identifier, set = Self::set_identifier, type = glib::GString)]
+        identifier: Cell<glib::GString>,
+
+        #[property(get, set)]
+        cpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        memory_usage: Cell<u64>,
+
+        #[property(get, set)]
+        read_speed: Cell<f64>,
+
+        #[property(get, set)]
+        read_total: Cell<u64>,
+
+        #[property(get, set)]
+        write_speed: Cell<f64>,
+
+        #[property(get, set)]
+        write_total: Cell<u64>,
+
+        pub container_item: RefCell<Option<ContainerItem>>,
+    }
+
+    impl Default for ContainerEntry {
+        fn default() -> Self {
+            Self {
+                name: Cell::new(glib::GString::default()),
+                engine: Cell::new(glib::GString::default()),
+                identifier: Cell::new(glib::GString::default()),
+                cpu_usage: Cell::new(0.0),
+                memory_usage: Cell::new(0),
+                read_speed: Cell::new(0.0),
+                read_total: Cell::new(0),
+                write_speed: Cell::new(0.0),
+                write_total: Cell::new(0),
+                container_item: RefCell::new(None),
+            }
+        }
+    }
+
+    impl ContainerEntry {
+        pub fn name(&self) -> glib::GString {
+            let name = self.name.take();
+            let result = name.clone();
+            self.name.set(name);
+            result
+        }
+
+        pub fn set_name(&self, name: &str) {
+            self.name.set(glib::GString::from(name));
+        }
+
+        pub fn engine(&self) -> glib::GString {
+            let engine = self.engine.take();
+            let result = engine.clone();
+            self.engine.set(engine);
+            result
+        }
+
+        pub fn set_engine(&self, engine: &str) {
+            self.engine.set(glib::GString::from(engine));
+        }
+
+        pub fn identifier(&self) -> glib::GString {
+            let identifier = self.identifier.take();
+            let result = identifier.clone();
+            self.identifier.set(identifier);
+            result
+        }
+
+        pub fn set_identifier(&self, identifier: &str) {
+            self.identifier.set(glib::GString::from(identifier));
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ContainerEntry {
+        const NAME: &'static str = "ContainerEntry";
+        type Type = super::ContainerEntry;
+    }
+
+    impl ObjectImpl for ContainerEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct ContainerEntry(ObjectSubclass<imp::ContainerEntry>);
+}
+
+impl ContainerEntry {
+    pub fn new(container_item: ContainerItem) -> Self {
+        let containerization = &container_item.containerization;
+        let this: Self = glib::Object::builder()
+            .property("name", &container_item.display_name)
+            .property(
+                "engine",
+                containerization_name(containerization).unwrap_or_default(),
+            )
+            .property(
+                "identifier",
+                container::short_identifier(containerization).unwrap_or_default(),
+            )
+            .build();
+        this.update(container_item);
+        this
+    }
+
+    pub fn update(&self, container_item: ContainerItem) {
+        self.set_cpu_usage(container_item.cpu_time_ratio);
+        self.set_memory_usage(container_item.memory_usage as u64);
+        self.set_read_speed(container_item.read_speed);
+        self.set_read_total(container_item.read_total);
+        self.set_write_speed(container_item.write_speed);
+        self.set_write_total(container_item.write_total);
+        self.imp().container_item.replace(Some(container_item));
+    }
+
+    pub fn container_item(&self) -> Option<ContainerItem> {
+        let imp = self.imp();
+        let item = imp.container_item.take();
+        imp.container_item.replace(item.clone());
+        item
+    }
+}
@@ -0,0 +1,447 @@
+mod container_entry;
+
+use adw::ResponseAppearance;
+use adw::{prelude::*, subclass::prelude::*};
+use gtk::glib::{self, clone, Object, Sender};
+use gtk::{gio, CustomSorter, FilterChange, SortType, Widget};
+use gtk_macros::send;
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::pages::applications::application_name_cell::ResApplicationNameCell;
+use crate::ui::pages::columns;
+use crate::ui::window::{self, Action, MainWindow};
+use crate::utils::app::{AppsContext, ContainerItem};
+use crate::utils::process::ProcessAction;
+
+use self::container_entry::ContainerEntry;
+
+mod imp {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::OnceLock,
+    };
+
+    use crate::ui::window::Action;
+
+    use super::*;
+
+    use gtk::{
+        gio::{Icon, ThemedIcon},
+        glib::{ParamSpec, Properties, Sender, Value},
+        CompositeTemplate,
+    };
+
+    #[derive(CompositeTemplate, Properties)]
+    #[template(resource = "/net/nokyan/Resources/ui/pages/containers.ui")]
+    #[properties(wrapper_type = super::ResContainers)]
+    pub struct ResContainers {
+        #[template_child]
+        pub toast_overlay: TemplateChild<adw::ToastOverlay>,
+        #[template_child]
+        pub containers_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub search_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
+        pub search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub containers_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub stop_container_button: TemplateChild<adw::SplitButton>,
+
+        pub store: RefCell<gio::ListStore>,
+        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub filter_model: RefCell<gtk::FilterListModel>,
+        pub sort_model: RefCell<gtk::SortListModel>,
+        pub column_view: RefCell<gtk::ColumnView>,
+
+        pub sender: OnceLock<Sender<Action>>,
+
+        #[property(get)]
+        uses_progress_bar: Cell<bool>,
+
+        #[property(get)]
+        icon: RefCell<Icon>,
+
+        #[property(get = Self::tab_name, type = glib::GString)]
+        tab_name: Cell<glib::GString>,
+
+        #[property(get = Self::tab_subtitle, set = Self::set_tab_subtitle, type = glib::GString)]
+        tab_subtitle: Cell<glib::GString>,
+    }
+
+    impl ResContainers {
+        pub fn tab_name(&self) -> glib::GString {
+            let tab_name = self.tab_name.take();
+            let result = tab_name.clone();
+            self.tab_name.set(tab_name);
+            result
+        }
+
+        pub fn tab_subtitle(&self) -> glib::GString {
+            let tab_subtitle = self.tab_subtitle.take();
+            let result = tab_subtitle.clone();
+            self.tab_subtitle.set(tab_subtitle);
+            result
+        }
+
+        pub fn set_tab_subtitle(&self, tab_subtitle: &str) {
+            self.tab_subtitle.set(glib::GString::from(tab_subtitle));
+        }
+    }
+
+    impl Default for ResContainers {
+        fn default() -> Self {
+            Self {
+                toast_overlay: Default::default(),
+                containers_stack: Default::default(),
+                search_revealer: Default::default(),
+                search_entry: Default::default(),
+                search_button: Default::default(),
+                store: gio::ListStore::new::<ContainerEntry>().into(),
+                selection_model: Default::default(),
+                filter_model: Default::default(),
+                sort_model: Default::default(),
+                column_view: Default::default(),
+                sender: Default::default(),
+                containers_scrolled_window: Default::default(),
+                stop_container_button: Default::default(),
+                uses_progress_bar: Cell::new(false),
+                icon: RefCell::new(ThemedIcon::new("package-x-generic-symbolic").into()),
+                tab_name: Cell::from(glib::GString::from(i18n("Containers"))),
+                tab_subtitle: Cell::new(glib::GString::from("")),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResContainers {
+        const NAME: &'static str = "ResContainers";
+        type Type = super::ResContainers;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.install_action(
+                "containers.kill-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container) = res_containers.get_selected_container_item() {
+                        res_containers
+                            .execute_process_action_dialog(container, ProcessAction::KILL);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "containers.pause-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container) = res_containers.get_selected_container_item() {
+                        res_containers
+                            .execute_process_action_dialog(container, ProcessAction::STOP);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "containers.resume-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container) = res_containers.get_selected_container_item() {
+                        res_containers
+                            .execute_process_action_dialog(container, ProcessAction::CONT);
+                    }
+                },
+            );
+
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResContainers {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+
+    impl WidgetImpl for ResContainers {}
+    impl BinImpl for ResContainers {}
+}
+
+glib::wrapper! {
+    pub struct ResContainers(ObjectSubclass<imp::ResContainers>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl ResContainers {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn toggle_search(&self) {
+        let imp = self.imp();
+
+        imp.search_button.set_active(!imp.search_button.is_active());
+    }
+
+    pub fn init(&self, sender: Sender<Action>) {
+        let imp = self.imp();
+        imp.sender.set(sender).unwrap();
+
+        self.setup_widgets();
+        self.setup_signals();
+    }
+
+    pub fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        let column_view = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let store = gio::ListStore::new::<ContainerEntry>();
+        let filter_model = gtk::FilterListModel::new(
+            Some(store.clone()),
+            Some(gtk::CustomFilter::new(
+                clone!(@strong self as this => move |obj| this.search_filter(obj)),
+            )),
+        );
+        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+        let selection_model = gtk::SingleSelection::new(Some(sort_model.clone()));
+        column_view.set_model(Some(&selection_model));
+        selection_model.set_can_unselect(true);
+        selection_model.set_autoselect(false);
+
+        *imp.store.borrow_mut() = store;
+        *imp.selection_model.borrow_mut() = selection_model;
+        *imp.sort_model.borrow_mut() = sort_model;
+        *imp.filter_model.borrow_mut() = filter_model;
+
+        let name_col_factory = gtk::SignalListItemFactory::new();
+        let name_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Container")), Some(name_col_factory.clone()));
+        name_col.set_resizable(true);
+        name_col.set_expand(true);
+        name_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = ResApplicationNameCell::new();
+            item.set_child(Some(&row));
+            item.property_expression("item")
+                .chain_property::<ContainerEntry>("name")
+                .bind(&row, "name", Widget::NONE);
+            item.property_expression("item")
+                .chain_property::<ContainerEntry>("engine")
+                .bind(&row, "badge", Widget::NONE);
+            item.property_expression("item")
+                .chain_property::<ContainerEntry>("identifier")
+                .bind(&row, "badge_tooltip", Widget::NONE);
+        });
+        let name_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ContainerEntry>().unwrap();
+            let item_b = b.downcast_ref::<ContainerEntry>().unwrap();
+            item_a
+                .name()
+                .to_lowercase()
+                .cmp(&item_b.name().to_lowercase())
+                .into()
+        });
+        name_col.set_sorter(Some(&name_col_sorter));
+
+        let [memory_col, cpu_col, read_speed_col, read_total_col, write_speed_col, write_total_col] =
+            columns::usage_columns::<ContainerEntry>();
+
+        column_view.append_column(&name_col);
+        column_view.append_column(&memory_col);
+        column_view.append_column(&cpu_col);
+        column_view.append_column(&read_speed_col);
+        column_view.append_column(&read_total_col);
+        column_view.append_column(&write_speed_col);
+        column_view.append_column(&write_total_col);
+
+        column_view.sort_by_column(Some(&name_col), SortType::Ascending);
+
+        column_view.set_enable_rubberband(true);
+        imp.containers_scrolled_window.set_child(Some(&column_view));
+        *imp.column_view.borrow_mut() = column_view;
+
+        imp.containers_stack.set_visible_child_name("empty");
+    }
+
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.selection_model.borrow().connect_selection_changed(
+            clone!(@strong self as this => move |model, _, _| {
+                let imp = this.imp();
+                imp.stop_container_button.set_sensitive(model.selected() != u32::MAX);
+            }),
+        );
+
+        imp.search_button
+            .connect_toggled(clone!(@strong self as this => move |button| {
+                let imp = this.imp();
+                imp.search_revealer.set_reveal_child(button.is_active());
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+                if button.is_active() {
+                    imp.search_entry.grab_focus();
+                }
+            }));
+
+        imp.search_entry
+            .connect_search_changed(clone!(@strong self as this => move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }));
+
+        imp.stop_container_button
+            .connect_clicked(clone!(@strong self as this => move |_| {
+                if let Some(container) = this.get_selected_container_item() {
+                    this.execute_process_action_dialog(container, ProcessAction::TERM);
+                }
+            }));
+    }
+
+    fn search_filter(&self, obj: &Object) -> bool {
+        let imp = self.imp();
+        let item = obj.downcast_ref::<ContainerEntry>().unwrap();
+        let search_string = imp.search_entry.text().to_string().to_lowercase();
+        !imp.search_revealer.reveals_child()
+            || item.name().to_lowercase().contains(&search_string)
+            || item.engine().to_lowercase().contains(&search_string)
+            || item.identifier().to_lowercase().contains(&search_string)
+    }
+
+    fn get_selected_container_item(&self) -> Option<ContainerItem> {
+        self.imp()
+            .selection_model
+            .borrow()
+            .selected_item()
+            .and_then(|object| {
+                object
+                    .downcast::<ContainerEntry>()
+                    .unwrap()
+                    .container_item()
+            })
+    }
+
+    pub fn refresh_containers_list(&self, apps: &AppsContext) {
+        let imp = self.imp();
+
+        let store = imp.store.borrow_mut();
+
+        let mut new_items = apps.container_items();
+
+        // update the entries of containers that are still running and remove
+        // the ones that have stopped
+        store.retain(|object| {
+            let entry = object.downcast_ref::<ContainerEntry>().unwrap();
+            entry
+                .container_item()
+                .and_then(|item| new_items.remove(&item.containerization))
+                .map(|new_item| entry.update(new_item))
+                .is_some()
+        });
+
+        // add the newly started containers to the store
+        new_items
+            .drain()
+            .for_each(|(_, new_item)| store.append(&ContainerEntry::new(new_item)));
+
+        store.items_changed(0, store.n_items(), store.n_items());
+
+        imp.containers_stack
+            .set_visible_child_name(if store.n_items() == 0 {
+                "empty"
+            } else {
+                "containers"
+            });
+
+        self.set_property(
+            "tab_subtitle",
+            i18n_f("Running Containers: {}", &[&store.n_items().to_string()]),
+        );
+    }
+
+    pub fn execute_process_action_dialog(&self, container: ContainerItem, action: ProcessAction) {
+        let imp = self.imp();
+
+        // Nothing too bad can happen on Continue so dont show the dialog
+        if action == ProcessAction::CONT {
+            send!(
+                imp.sender.get().unwrap(),
+                Action::ManipulateContainer(
+                    action,
+                    container.containerization,
+                    container.display_name,
+                    imp.toast_overlay.get()
+                )
+            );
+            return;
+        }
+
+        // Confirmation dialog & warning
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&MainWindow::default())
+            .modal(true)
+            .heading(window::get_container_action_name(
+                action,
+                &[&container.display_name],
+            ))
+            .body(window::get_container_action_warning(action))
+            .build();
+
+        dialog.add_response("yes", &window::get_container_action_description(action));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        // Called when "yes" or "no" were clicked
+        dialog.connect_response(
+            None,
+            clone!(@strong self as this, @strong container => move |_, response| {
+                if response == "yes" {
+                    let imp = this.imp();
+                    send!(
+                        imp.sender.get().unwrap(),
+                        Action::ManipulateContainer(
+                            action,
+                            container.containerization.clone(),
+                            container.display_name.clone(),
+                            imp.toast_overlay.get()
+                        )
+                    );
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+}
@@ -6,6 +6,7 @@ use gtk::FlowBoxChild;
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
 use crate::ui::widgets::graph_box::ResGraphBox;
+use crate::utils::pressure::PressureResource;
 use crate::utils::settings::SETTINGS;
 use crate::utils::units::{convert_frequency, convert_temperature};
 use crate::utils::{cpu, NaNDefault};
@@ -13,7 +14,7 @@ use crate::utils::{cpu, NaNDefault};
 mod imp {
     use std::cell::{Cell, RefCell};
 
-    use crate::ui::widgets::graph_box::ResGraphBox;
+    use crate::ui::widgets::{graph_box::ResGraphBox, pressure_group::ResPressureGroup};
 
     use super::*;
 
@@ -53,6 +54,8 @@ mod imp {
         pub architecture: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub temperature: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub pressure: TemplateChild<ResPressureGroup>,
         pub thread_graphs: RefCell<Vec<ResGraphBox>>,
         pub old_total_usage: Cell<(u64, u64)>,
         pub old_thread_usages: RefCell<Vec<(u64, u64)>>,
@@ -110,6 +113,7 @@ mod imp {
                 virtualization: Default::default(),
                 architecture: Default::default(),
                 temperature: Default::default(),
+                pressure: Default::default(),
                 thread_graphs: Default::default(),
                 uses_progress_bar: Cell::new(true),
                 icon: RefCell::new(ThemedIcon::new("processor-symbolic").into()),
@@ -207,6 +211,8 @@ impl ResCPU {
             imp.total_cpu.set_data_points_max_amount(60);
             imp.total_cpu.set_graph_color(28, 113, 216);
 
+            imp.pressure.init(PressureResource::Cpu, 28, 113, 216);
+
             // if our CPU happens to only have one thread, showing a single thread box with the exact
             // same fraction as the progress bar for total CPU usage would be silly, so only do
             // thread boxes if we have more than one thread
@@ -319,6 +325,8 @@ impl ResCPU {
             }
         }
 
+        imp.pressure.refresh();
+
         let temperature = cpu::get_temperature().await;
         if let Ok(temp) = temperature {
             imp.temperature
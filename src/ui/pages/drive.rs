@@ -7,6 +7,7 @@ use gtk::glib;
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
 use crate::utils::drive::Drive;
+use crate::utils::pressure::PressureResource;
 use crate::utils::units::{convert_speed, convert_storage};
 
 mod imp {
@@ -15,7 +16,7 @@ mod imp {
         collections::HashMap,
     };
 
-    use crate::ui::widgets::graph_box::ResGraphBox;
+    use crate::ui::widgets::{graph_box::ResGraphBox, pressure_group::ResPressureGroup};
 
     use super::*;
 
@@ -45,6 +46,8 @@ mod imp {
         pub writable: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub removable: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub pressure: TemplateChild<ResPressureGroup>,
         pub old_stats: RefCell<HashMap<String, usize>>,
         pub drive: RefCell<Drive>,
         pub last_timestamp: Cell<SystemTime>,
@@ -111,6 +114,7 @@ mod imp {
                 capacity: Default::default(),
                 writable: Default::default(),
                 removable: Default::default(),
+                pressure: Default::default(),
                 uses_progress_bar: Cell::new(true),
                 icon: RefCell::new(Drive::default_icon()),
                 usage: Default::default(),
@@ -191,6 +195,7 @@ impl ResDrive {
         imp.total_usage.set_title_label(&i18n("Total Usage"));
         imp.total_usage.set_data_points_max_amount(60);
         imp.total_usage.set_graph_color(229, 165, 10);
+        imp.pressure.init(PressureResource::Io, 229, 165, 10);
         imp.drive_type.set_subtitle(
             &(match drive.drive_type {
                 crate::utils::drive::DriveType::CdDvdBluray => i18n("CD/DVD/Blu-ray Drive"),
@@ -269,6 +274,7 @@ impl ResDrive {
         let percentage_string = format!("{} %", (total_usage.unwrap_or(0.0) * 100.0).round());
         imp.total_usage.push_data_point(total_usage.unwrap_or(0.0));
         imp.total_usage.set_subtitle(&percentage_string);
+        imp.pressure.refresh();
         self.set_property("usage", total_usage.unwrap_or(0.0));
 
         let rw_bytes_per_second = if let (
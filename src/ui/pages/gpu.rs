@@ -4,10 +4,14 @@ use gtk::glib::{self};
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
+use crate::utils::app::AppsContext;
 use crate::utils::gpu::GPU;
 use crate::utils::units::{convert_frequency, convert_power, convert_storage, convert_temperature};
 use crate::utils::NaNDefault;
 
+/// The maximum amount of processes listed as using the GPU the most
+const TOP_PROCESSES_AMOUNT: usize = 5;
+
 mod imp {
     use std::{
         cell::{Cell, RefCell},
@@ -50,6 +54,12 @@ mod imp {
         pub current_power_cap: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub max_power_cap: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub processes: TemplateChild<adw::PreferencesGroup>,
+
+        /// The rows listing the processes and their icons, they're reused
+        /// across refreshes
+        pub process_rows: RefCell<Vec<(adw::ActionRow, gtk::Image)>>,
 
         pub gpu: OnceLock<GPU>,
         pub number: OnceLock<usize>,
@@ -108,6 +118,8 @@ mod imp {
                 driver_used: Default::default(),
                 current_power_cap: Default::default(),
                 max_power_cap: Default::default(),
+                processes: Default::default(),
+                process_rows: Default::default(),
                 gpu: Default::default(),
                 number: Default::default(),
                 uses_progress_bar: Cell::new(true),
@@ -195,6 +207,79 @@ impl ResGPU {
         imp.driver_used.set_subtitle(&gpu.driver);
     }
 
+    /// Lists the processes that use this GPU the most, matched by the PCI slot
+    /// of the GPU
+    pub fn refresh_processes(&self, apps_context: &AppsContext) {
+        let imp = self.imp();
+        let Some(gpu) = imp.gpu.get() else {
+            return;
+        };
+
+        let mut gpu_processes: Vec<_> = apps_context
+            .all_processes()
+            .filter_map(|process| {
+                process
+                    .gpu_usages()
+                    .remove(&gpu.pci_slot)
+                    .map(|gpu_usage| (process.data.pid, gpu_usage))
+            })
+            .collect();
+
+        gpu_processes.sort_by(|(_, a), (_, b)| {
+            b.usage
+                .total_cmp(&a.usage)
+                .then_with(|| b.memory.cmp(&a.memory))
+        });
+
+        let mut process_rows = imp.process_rows.borrow_mut();
+        let mut shown = 0;
+
+        for (pid, gpu_usage) in gpu_processes.into_iter().take(TOP_PROCESSES_AMOUNT) {
+            let Some(process_item) = apps_context.process_item(pid) else {
+                continue;
+            };
+
+            let usage = format!("{:.1} %", gpu_usage.usage * 100.0);
+            let memory = convert_storage(gpu_usage.memory as f64, false);
+
+            let subtitle =
+                if let (Some(encoder), Some(decoder)) = (gpu_usage.encoder, gpu_usage.decoder) {
+                    i18n_f(
+                        "{} · Encoder: {} · Decoder: {} · VRAM: {}",
+                        &[
+                            &usage,
+                            &format!("{:.1} %", encoder * 100.0),
+                            &format!("{:.1} %", decoder * 100.0),
+                            &memory,
+                        ],
+                    )
+                } else {
+                    i18n_f("{} · VRAM: {}", &[&usage, &memory])
+                };
+
+            if process_rows.len() <= shown {
+                let row = adw::ActionRow::builder().use_markup(false).build();
+                let image = gtk::Image::new();
+                row.add_prefix(&image);
+                imp.processes.add(&row);
+                process_rows.push((row, image));
+            }
+
+            let (row, image) = &process_rows[shown];
+            row.set_title(&process_item.display_name);
+            row.set_subtitle(&subtitle);
+            image.set_from_gicon(&process_item.icon);
+
+            shown += 1;
+        }
+
+        for (row, _) in process_rows.drain(shown..) {
+            imp.processes.remove(&row);
+        }
+
+        imp.processes.set_visible(!process_rows.is_empty());
+    }
+
     pub async fn refresh_page(&self) -> Result<()> {
         let imp = self.imp();
         let gpu = imp.gpu.get().with_context(|| "GPU not initialized")?;
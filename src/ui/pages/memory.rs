@@ -7,13 +7,14 @@ use crate::i18n::{i18n, i18n_f};
 use crate::utils::memory::{
     self, get_available_memory, get_free_swap, get_total_memory, get_total_swap, MemoryDevice,
 };
+use crate::utils::pressure::PressureResource;
 use crate::utils::units::convert_storage;
 use crate::utils::NaNDefault;
 
 mod imp {
     use std::cell::{Cell, RefCell};
 
-    use crate::ui::widgets::graph_box::ResGraphBox;
+    use crate::ui::widgets::{graph_box::ResGraphBox, pressure_group::ResPressureGroup};
 
     use super::*;
 
@@ -32,6 +33,8 @@ mod imp {
         #[template_child]
         pub swap: TemplateChild<ResGraphBox>,
         #[template_child]
+        pub pressure: TemplateChild<ResPressureGroup>,
+        #[template_child]
         pub authentication_banner: TemplateChild<adw::Banner>,
         #[template_child]
         pub properties: TemplateChild<adw::PreferencesGroup>,
@@ -87,6 +90,7 @@ mod imp {
             Self {
                 memory: Default::default(),
                 swap: Default::default(),
+                pressure: Default::default(),
                 authentication_banner: Default::default(),
                 properties: Default::default(),
                 slots_used: Default::default(),
@@ -170,6 +174,7 @@ impl ResMemory {
         imp.swap.set_title_label(&i18n("Swap"));
         imp.swap.set_graph_color(46, 194, 126);
         imp.swap.set_data_points_max_amount(60);
+        imp.pressure.init(PressureResource::Memory, 129, 61, 156);
 
         if let Ok(memory_devices) = memory::get_memory_devices() {
             self.setup_properties(&memory_devices);
@@ -290,6 +295,8 @@ impl ResMemory {
             );
         }
 
+        imp.pressure.refresh();
+
         self.set_property("usage", memory_fraction);
 
         Ok(())
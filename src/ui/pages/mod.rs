@@ -1,4 +1,6 @@
 pub mod applications;
+pub mod columns;
+pub mod containers;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
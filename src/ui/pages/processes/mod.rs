@@ -1,24 +1,30 @@
 mod process_entry;
 mod process_name_cell;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use adw::ResponseAppearance;
 use adw::{prelude::*, subclass::prelude::*};
 use gtk::glib::{self, clone, closure, Object, Sender};
-use gtk::{gio, CustomSorter, FilterChange, Ordering, SortType, Widget};
+use gtk::{gio, CustomSorter, FilterChange, Ordering, SortType, SorterChange, TreeListRow, Widget};
 use gtk_macros::send;
 
 use log::error;
+use process_data::IoPriority;
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
 use crate::ui::dialogs::process_dialog::ResProcessDialog;
+use crate::ui::dialogs::{affinity_dialog, priority_dialog, signal_dialog};
 use crate::ui::window::{self, Action, MainWindow};
 use crate::utils::app::AppsContext;
-use crate::utils::process::{ProcessAction, ProcessItem};
+use crate::utils::process::{
+    io_priority_name, scheduling_policy_name, state_flag, state_name, ProcessAction,
+    ProcessAdjustment, ProcessItem,
+};
+use crate::utils::search::{parse_search_entry, Query};
 use crate::utils::settings::SETTINGS;
-use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::units::{convert_duration, convert_speed, convert_storage};
 
 use self::process_entry::ProcessEntry;
 use self::process_name_cell::ResProcessNameCell;
@@ -55,13 +61,21 @@ mod imp {
         #[template_child]
         pub search_button: TemplateChild<gtk::ToggleButton>,
         #[template_child]
+        pub tree_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub select_all_button: TemplateChild<gtk::Button>,
+        #[template_child]
         pub information_button: TemplateChild<gtk::Button>,
         #[template_child]
         pub end_process_button: TemplateChild<adw::SplitButton>,
 
         pub store: RefCell<gio::ListStore>,
-        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub tree_store: RefCell<gio::ListStore>,
+        pub entries: RefCell<HashMap<i32, ProcessEntry>>,
+        pub tree_mode: Cell<bool>,
+        pub selection_model: RefCell<gtk::MultiSelection>,
         pub filter_model: RefCell<gtk::FilterListModel>,
+        pub search_query: RefCell<Option<Query>>,
         pub sort_model: RefCell<gtk::SortListModel>,
         pub column_view: RefCell<gtk::ColumnView>,
         pub open_dialog: RefCell<Option<(i32, ResProcessDialog)>>,
@@ -111,11 +125,17 @@ mod imp {
                 search_entry: Default::default(),
                 processes_scrolled_window: Default::default(),
                 search_button: Default::default(),
+                tree_button: Default::default(),
+                select_all_button: Default::default(),
                 information_button: Default::default(),
                 end_process_button: Default::default(),
                 store: gio::ListStore::new::<ProcessEntry>().into(),
+                tree_store: gio::ListStore::new::<ProcessEntry>().into(),
+                entries: Default::default(),
+                tree_mode: Cell::new(false),
                 selection_model: Default::default(),
                 filter_model: Default::default(),
+                search_query: Default::default(),
                 sort_model: Default::default(),
                 column_view: Default::default(),
                 open_dialog: Default::default(),
@@ -140,8 +160,9 @@ mod imp {
                 "processes.kill-process",
                 None,
                 move |res_processes, _, _| {
-                    if let Some(app) = res_processes.get_selected_process_item() {
-                        res_processes.execute_process_action_dialog(app, ProcessAction::KILL);
+                    let processes = res_processes.get_selected_process_items();
+                    if !processes.is_empty() {
+                        res_processes.execute_process_action_dialog(processes, ProcessAction::KILL);
                     }
                 },
             );
@@ -150,8 +171,9 @@ mod imp {
                 "processes.halt-process",
                 None,
                 move |res_processes, _, _| {
-                    if let Some(app) = res_processes.get_selected_process_item() {
-                        res_processes.execute_process_action_dialog(app, ProcessAction::STOP);
+                    let processes = res_processes.get_selected_process_items();
+                    if !processes.is_empty() {
+                        res_processes.execute_process_action_dialog(processes, ProcessAction::STOP);
                     }
                 },
             );
@@ -159,9 +181,36 @@ mod imp {
             klass.install_action(
                 "processes.continue-process",
                 None,
+                move |res_processes, _, _| {
+                    let processes = res_processes.get_selected_process_items();
+                    if !processes.is_empty() {
+                        res_processes.execute_process_action_dialog(processes, ProcessAction::CONT);
+                    }
+                },
+            );
+
+            klass.install_action("processes.send-signal", None, move |res_processes, _, _| {
+                if let Some(app) = res_processes.get_selected_process_item() {
+                    res_processes.send_signal_dialog(app);
+                }
+            });
+
+            klass.install_action(
+                "processes.change-priority",
+                None,
                 move |res_processes, _, _| {
                     if let Some(app) = res_processes.get_selected_process_item() {
-                        res_processes.execute_process_action_dialog(app, ProcessAction::CONT);
+                        res_processes.change_priority_dialog(app, &MainWindow::default());
+                    }
+                },
+            );
+
+            klass.install_action(
+                "processes.change-affinity",
+                None,
+                move |res_processes, _, _| {
+                    if let Some(app) = res_processes.get_selected_process_item() {
+                        res_processes.change_affinity_dialog(app, &MainWindow::default());
                     }
                 },
             );
@@ -230,21 +279,30 @@ impl ResProcesses {
     pub fn setup_widgets(&self) {
         let imp = self.imp();
 
-        let column_view = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = gtk::ColumnView::new(None::<gtk::MultiSelection>);
         let store = gio::ListStore::new::<ProcessEntry>();
+        let tree_store = gio::ListStore::new::<ProcessEntry>();
         let filter_model = gtk::FilterListModel::new(
             Some(store.clone()),
             Some(gtk::CustomFilter::new(
                 clone!(@strong self as this => move |obj| this.search_filter(obj)),
             )),
         );
-        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
-        let selection_model = gtk::SingleSelection::new(Some(sort_model.clone()));
+        let tree_model = gtk::TreeListModel::new(
+            filter_model.clone(),
+            false,
+            false,
+            clone!(@strong self as this => move |obj| this.tree_children(obj)),
+        );
+        let sort_model = gtk::SortListModel::new(
+            Some(tree_model),
+            Some(gtk::TreeListRowSorter::new(column_view.sorter())),
+        );
+        let selection_model = gtk::MultiSelection::new(Some(sort_model.clone()));
         column_view.set_model(Some(&selection_model));
-        selection_model.set_can_unselect(true);
-        selection_model.set_autoselect(false);
 
         *imp.store.borrow_mut() = store;
+        *imp.tree_store.borrow_mut() = tree_store;
         *imp.selection_model.borrow_mut() = selection_model;
         *imp.sort_model.borrow_mut() = sort_model;
         *imp.filter_model.borrow_mut() = filter_model;
@@ -257,16 +315,35 @@ impl ResProcesses {
         name_col_factory.connect_setup(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = ResProcessNameCell::new();
-            item.set_child(Some(&row));
-            item.property_expression("item")
+            let expander = gtk::TreeExpander::new();
+            expander.set_child(Some(&row));
+            item.set_child(Some(&expander));
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("name")
                 .bind(&row, "name", Widget::NONE);
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("icon")
                 .bind(&row, "icon", Widget::NONE);
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("commandline")
                 .bind(&row, "tooltip", Widget::NONE);
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("containerization")
+                .bind(&row, "badge", Widget::NONE);
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("containerization_description")
+                .bind(&row, "badge_tooltip", Widget::NONE);
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("state")
+                .chain_closure::<String>(closure!(|_: Option<Object>, state: u8| {
+                    state_flag(state as char)
+                }))
+                .bind(&row, "flag", Widget::NONE);
+        });
+        name_col_factory.connect_bind(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let expander = item.child().and_downcast::<gtk::TreeExpander>().unwrap();
+            expander.set_list_row(item.item().and_downcast::<TreeListRow>().as_ref());
         });
         let name_col_sorter = CustomSorter::new(move |a, b| {
             let item_a = a.downcast_ref::<ProcessEntry>().unwrap();
@@ -287,7 +364,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("pid")
                 .bind(&row, "text", Widget::NONE);
         });
@@ -307,7 +384,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("user")
                 .bind(&row, "text", Widget::NONE);
         });
@@ -328,7 +405,7 @@ impl ResProcesses {
             let row = gtk::Inscription::new(None);
             row.set_min_chars(9);
             item.set_child(Some(&row));
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("memory_usage")
                 .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
                     convert_storage(memory_usage as f64, false)
@@ -351,7 +428,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("cpu_usage")
                 .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
                     format!("{:.1} %", cpu_usage * 100.0)
@@ -382,7 +459,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("read_speed")
                 .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
                     if read_speed == -1.0 {
@@ -417,7 +494,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("read_total")
                 .chain_closure::<String>(closure!(|_: Option<Object>, read_total: i64| {
                     if read_total == -1 {
@@ -446,7 +523,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("write_speed")
                 .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
                     if write_speed == -1.0 {
@@ -481,7 +558,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
-            item.property_expression("item")
+            entry_expression(item)
                 .chain_property::<ProcessEntry>("write_total")
                 .chain_closure::<String>(closure!(|_: Option<Object>, write_total: i64| {
                     if write_total == -1 {
@@ -500,6 +577,240 @@ impl ResProcesses {
         write_total_col.set_sorter(Some(&write_total_col_sorter));
         write_total_col.set_visible(SETTINGS.processes_show_drive_write_total());
 
+        let gpu_col_factory = gtk::SignalListItemFactory::new();
+        let gpu_col = gtk::ColumnViewColumn::new(Some(&i18n("GPU")), Some(gpu_col_factory.clone()));
+        gpu_col.set_resizable(true);
+        gpu_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("gpu_usage")
+                .chain_closure::<String>(closure!(|_: Option<Object>, gpu_usage: f32| {
+                    format!("{:.1} %", gpu_usage * 100.0)
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let gpu_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ProcessEntry>().unwrap().gpu_usage();
+            let item_b = b.downcast_ref::<ProcessEntry>().unwrap().gpu_usage();
+            if item_a > item_b {
+                Ordering::Larger
+            } else if item_a < item_b {
+                Ordering::Smaller
+            } else {
+                Ordering::Equal
+            }
+        });
+        gpu_col.set_sorter(Some(&gpu_col_sorter));
+        gpu_col.set_visible(SETTINGS.processes_show_gpu());
+
+        let gpu_memory_col_factory = gtk::SignalListItemFactory::new();
+        let gpu_memory_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Video Memory")),
+            Some(gpu_memory_col_factory.clone()),
+        );
+        gpu_memory_col.set_resizable(true);
+        gpu_memory_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("gpu_memory")
+                .chain_closure::<String>(closure!(|_: Option<Object>, gpu_memory: u64| {
+                    convert_storage(gpu_memory as f64, false)
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let gpu_memory_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ProcessEntry>().unwrap().gpu_memory();
+            let item_b = b.downcast_ref::<ProcessEntry>().unwrap().gpu_memory();
+            item_a.cmp(&item_b).into()
+        });
+        gpu_memory_col.set_sorter(Some(&gpu_memory_col_sorter));
+        gpu_memory_col.set_visible(SETTINGS.processes_show_gpu_memory());
+
+        let threads_col_factory = gtk::SignalListItemFactory::new();
+        let threads_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Threads")), Some(threads_col_factory.clone()));
+        threads_col.set_resizable(true);
+        threads_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("thread_count")
+                .bind(&row, "text", Widget::NONE);
+        });
+        let threads_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ProcessEntry>().unwrap().thread_count();
+            let item_b = b.downcast_ref::<ProcessEntry>().unwrap().thread_count();
+            item_a.cmp(&item_b).into()
+        });
+        threads_col.set_sorter(Some(&threads_col_sorter));
+        threads_col.set_visible(SETTINGS.processes_show_threads());
+
+        let nice_col_factory = gtk::SignalListItemFactory::new();
+        let nice_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Nice")), Some(nice_col_factory.clone()));
+        nice_col.set_resizable(true);
+        nice_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("nice")
+                .bind(&row, "text", Widget::NONE);
+        });
+        let nice_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ProcessEntry>().unwrap().nice();
+            let item_b = b.downcast_ref::<ProcessEntry>().unwrap().nice();
+            item_a.cmp(&item_b).into()
+        });
+        nice_col.set_sorter(Some(&nice_col_sorter));
+        nice_col.set_visible(SETTINGS.processes_show_nice());
+
+        let scheduling_policy_col_factory = gtk::SignalListItemFactory::new();
+        let scheduling_policy_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Scheduling Policy")),
+            Some(scheduling_policy_col_factory.clone()),
+        );
+        scheduling_policy_col.set_resizable(true);
+        scheduling_policy_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("scheduling_policy")
+                .chain_closure::<String>(closure!(|_: Option<Object>, policy: u32| {
+                    scheduling_policy_name(policy)
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let scheduling_policy_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a
+                .downcast_ref::<ProcessEntry>()
+                .unwrap()
+                .scheduling_policy();
+            let item_b = b
+                .downcast_ref::<ProcessEntry>()
+                .unwrap()
+                .scheduling_policy();
+            item_a.cmp(&item_b).into()
+        });
+        scheduling_policy_col.set_sorter(Some(&scheduling_policy_col_sorter));
+        scheduling_policy_col.set_visible(SETTINGS.processes_show_scheduling_policy());
+
+        let io_priority_col_factory = gtk::SignalListItemFactory::new();
+        let io_priority_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("I/O Priority")),
+            Some(io_priority_col_factory.clone()),
+        );
+        io_priority_col.set_resizable(true);
+        io_priority_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("io_priority")
+                .chain_closure::<String>(closure!(|_: Option<Object>, io_priority: i32| {
+                    io_priority_name((io_priority != -1).then(|| IoPriority::from_raw(io_priority)))
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let io_priority_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ProcessEntry>().unwrap().io_priority();
+            let item_b = b.downcast_ref::<ProcessEntry>().unwrap().io_priority();
+            item_a.cmp(&item_b).into()
+        });
+        io_priority_col.set_sorter(Some(&io_priority_col_sorter));
+        io_priority_col.set_visible(SETTINGS.processes_show_io_priority());
+
+        let state_col_factory = gtk::SignalListItemFactory::new();
+        let state_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("State")), Some(state_col_factory.clone()));
+        state_col.set_resizable(true);
+        state_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("state")
+                .chain_closure::<String>(closure!(|_: Option<Object>, state: u8| {
+                    state_name(state as char)
+                }))
+                .bind(&row, "text", Widget::NONE);
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("state")
+                .chain_closure::<Vec<String>>(closure!(|_: Option<Object>, state: u8| {
+                    match state {
+                        b'Z' => vec!["error".to_string()],
+                        b'D' => vec!["warning".to_string()],
+                        _ => Vec::new(),
+                    }
+                }))
+                .bind(&row, "css-classes", Widget::NONE);
+        });
+        let state_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = state_name(a.downcast_ref::<ProcessEntry>().unwrap().state() as char);
+            let item_b = state_name(b.downcast_ref::<ProcessEntry>().unwrap().state() as char);
+            item_a.cmp(&item_b).into()
+        });
+        state_col.set_sorter(Some(&state_col_sorter));
+        state_col.set_visible(SETTINGS.processes_show_state());
+
+        let start_time_col_factory = gtk::SignalListItemFactory::new();
+        let start_time_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Started")),
+            Some(start_time_col_factory.clone()),
+        );
+        start_time_col.set_resizable(true);
+        start_time_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("start_time")
+                .chain_closure::<String>(closure!(|_: Option<Object>, start_time: i64| {
+                    glib::DateTime::from_unix_local(start_time)
+                        .and_then(|date_time| date_time.format("%x %X"))
+                        .map_or_else(|_| i18n("N/A"), String::from)
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let start_time_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ProcessEntry>().unwrap().start_time();
+            let item_b = b.downcast_ref::<ProcessEntry>().unwrap().start_time();
+            item_a.cmp(&item_b).into()
+        });
+        start_time_col.set_sorter(Some(&start_time_col_sorter));
+        start_time_col.set_visible(SETTINGS.processes_show_start_time());
+
+        let running_time_col_factory = gtk::SignalListItemFactory::new();
+        let running_time_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Running For")),
+            Some(running_time_col_factory.clone()),
+        );
+        running_time_col.set_resizable(true);
+        running_time_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            entry_expression(item)
+                .chain_property::<ProcessEntry>("running_time")
+                .chain_closure::<String>(closure!(|_: Option<Object>, running_time: i64| {
+                    u64::try_from(running_time).map_or_else(|_| i18n("N/A"), convert_duration)
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let running_time_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ProcessEntry>().unwrap().running_time();
+            let item_b = b.downcast_ref::<ProcessEntry>().unwrap().running_time();
+            item_a.cmp(&item_b).into()
+        });
+        running_time_col.set_sorter(Some(&running_time_col_sorter));
+        running_time_col.set_visible(SETTINGS.processes_show_running_time());
+
         column_view.append_column(&name_col);
         column_view.append_column(&pid_col);
         column_view.append_column(&user_col);
@@ -509,6 +820,15 @@ impl ResProcesses {
         column_view.append_column(&read_total_col);
         column_view.append_column(&write_speed_col);
         column_view.append_column(&write_total_col);
+        column_view.append_column(&gpu_col);
+        column_view.append_column(&gpu_memory_col);
+        column_view.append_column(&threads_col);
+        column_view.append_column(&nice_col);
+        column_view.append_column(&scheduling_policy_col);
+        column_view.append_column(&io_priority_col);
+        column_view.append_column(&state_col);
+        column_view.append_column(&start_time_col);
+        column_view.append_column(&running_time_col);
 
         column_view.sort_by_column(Some(&memory_col), SortType::Descending);
 
@@ -528,6 +848,23 @@ impl ResProcesses {
         SETTINGS.connect_processes_show_drive_write_total(move |visible| {
             write_total_col.set_visible(visible)
         });
+        SETTINGS.connect_processes_show_gpu(move |visible| gpu_col.set_visible(visible));
+        SETTINGS
+            .connect_processes_show_gpu_memory(move |visible| gpu_memory_col.set_visible(visible));
+        SETTINGS.connect_processes_show_threads(move |visible| threads_col.set_visible(visible));
+        SETTINGS.connect_processes_show_nice(move |visible| nice_col.set_visible(visible));
+        SETTINGS.connect_processes_show_scheduling_policy(move |visible| {
+            scheduling_policy_col.set_visible(visible)
+        });
+        SETTINGS.connect_processes_show_io_priority(move |visible| {
+            io_priority_col.set_visible(visible)
+        });
+        SETTINGS.connect_processes_show_state(move |visible| state_col.set_visible(visible));
+        SETTINGS
+            .connect_processes_show_start_time(move |visible| start_time_col.set_visible(visible));
+        SETTINGS.connect_processes_show_running_time(move |visible| {
+            running_time_col.set_visible(visible)
+        });
 
         column_view.set_enable_rubberband(true);
         imp.processes_scrolled_window.set_child(Some(&column_view));
@@ -537,18 +874,39 @@ impl ResProcesses {
     pub fn setup_signals(&self) {
         let imp = self.imp();
 
+        imp.tree_button
+            .connect_toggled(clone!(@strong self as this => move |button| {
+                let _ = SETTINGS.set_processes_tree_view(button.is_active());
+                this.update_tree_mode();
+            }));
+
         imp.selection_model.borrow().connect_selection_changed(
             clone!(@strong self as this => move |model, _, _| {
                 let imp = this.imp();
-                imp.information_button.set_sensitive(model.selected() != u32::MAX);
-                imp.end_process_button.set_sensitive(model.selected() != u32::MAX);
+                let selected = model.selection().size();
+                // only ending, killing, halting and continuing work on several processes at once
+                imp.information_button.set_sensitive(selected == 1);
+                imp.end_process_button.set_sensitive(selected > 0);
+                for action in [
+                    "processes.send-signal",
+                    "processes.change-priority",
+                    "processes.change-affinity",
+                ] {
+                    this.action_set_enabled(action, selected == 1);
+                }
             }),
         );
 
+        imp.select_all_button
+            .connect_clicked(clone!(@strong self as this => move |_| {
+                this.imp().selection_model.borrow().select_all();
+            }));
+
         imp.search_button
             .connect_toggled(clone!(@strong self as this => move |button| {
                 let imp = this.imp();
                 imp.search_revealer.set_reveal_child(button.is_active());
+                this.update_tree_mode();
                 if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
@@ -560,6 +918,8 @@ impl ResProcesses {
         imp.search_entry
             .connect_search_changed(clone!(@strong self as this => move |_| {
                 let imp = this.imp();
+                *imp.search_query.borrow_mut() = parse_search_entry(&imp.search_entry);
+                this.update_tree_mode();
                 if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
@@ -568,44 +928,130 @@ impl ResProcesses {
         imp.information_button
         .connect_clicked(clone!(@strong self as this => move |_| {
             let imp = this.imp();
-                let selection_option = imp.selection_model.borrow()
-                .selected_item()
-                .map(|object| {
-                    object
-                    .downcast::<ProcessEntry>()
-                    .unwrap()
-                });
-                if let Some(selection) = selection_option {
+                if let Some(selection) = this.get_selected_process_entry() {
                     let process_dialog = ResProcessDialog::new();
                     process_dialog.init(selection.process_item().as_ref().unwrap(), selection.user());
+                    process_dialog.connect_change_priority(clone!(@weak this, @weak process_dialog => move |pid| {
+                        let process_item = this
+                            .imp()
+                            .entries
+                            .borrow()
+                            .get(&pid)
+                            .and_then(ProcessEntry::process_item);
+                        if let Some(process_item) = process_item {
+                            this.change_priority_dialog(process_item, &process_dialog);
+                        }
+                    }));
+                    process_dialog.connect_change_affinity(clone!(@weak this, @weak process_dialog => move |pid| {
+                        let process_item = this
+                            .imp()
+                            .entries
+                            .borrow()
+                            .get(&pid)
+                            .and_then(ProcessEntry::process_item);
+                        if let Some(process_item) = process_item {
+                            this.change_affinity_dialog(process_item, &process_dialog);
+                        }
+                    }));
                     process_dialog.show();
+                    // the graphs would be empty until the next refresh otherwise
+                    send!(
+                        imp.sender.get().unwrap(),
+                        Action::UpdateProcessHistory(selection.pid(), process_dialog.clone())
+                    );
                     *imp.open_dialog.borrow_mut() = Some((selection.pid(), process_dialog));
                 }
             }));
 
         imp.end_process_button
             .connect_clicked(clone!(@strong self as this => move |_| {
-                if let Some(app) = this.get_selected_process_item() {
-                    this.execute_process_action_dialog(app, ProcessAction::TERM);
+                let processes = this.get_selected_process_items();
+                if !processes.is_empty() {
+                    this.execute_process_action_dialog(processes, ProcessAction::TERM);
                 }
             }));
+
+        imp.tree_button.set_active(SETTINGS.processes_tree_view());
+    }
+
+    /// Shows the processes as a tree if the user wants that and as a flat list
+    /// otherwise. While searching, the results are always shown as a flat
+    /// list so that matching child processes aren't hidden below collapsed
+    /// parents.
+    fn update_tree_mode(&self) {
+        let imp = self.imp();
+
+        let searching = imp.search_revealer.reveals_child() && !imp.search_entry.text().is_empty();
+        let tree_mode = imp.tree_button.is_active() && !searching;
+        if tree_mode == imp.tree_mode.get() {
+            return;
+        }
+        imp.tree_mode.set(tree_mode);
+
+        if tree_mode {
+            imp.filter_model
+                .borrow()
+                .set_model(Some(&*imp.tree_store.borrow()));
+        } else {
+            imp.filter_model
+                .borrow()
+                .set_model(Some(&*imp.store.borrow()));
+        }
+    }
+
+    fn tree_children(&self, obj: &Object) -> Option<gio::ListModel> {
+        let children = obj.downcast_ref::<ProcessEntry>()?.children();
+        (self.imp().tree_mode.get() && children.n_items() > 0).then(|| children.clone().upcast())
     }
 
     fn search_filter(&self, obj: &Object) -> bool {
         let imp = self.imp();
         let item = obj.downcast_ref::<ProcessEntry>().unwrap();
-        let search_string = imp.search_entry.text().to_string().to_lowercase();
         !imp.search_revealer.reveals_child()
-            || item.name().to_lowercase().contains(&search_string)
-            || item.commandline().to_lowercase().contains(&search_string)
+            || imp
+                .search_query
+                .borrow()
+                .as_ref()
+                .map_or(true, |query| query.matches(item))
+    }
+
+    /// Returns all selected entries in the order they're shown in
+    fn get_selected_process_entries(&self) -> Vec<ProcessEntry> {
+        let selection_model = self.imp().selection_model.borrow();
+        let selection = selection_model.selection();
+        (0..selection.size())
+            .filter_map(|i| selection_model.item(selection.nth(i as u32)))
+            .filter_map(|object| object.downcast::<TreeListRow>().ok())
+            .filter_map(|row| row.item().and_downcast::<ProcessEntry>())
+            .collect()
+    }
+
+    /// Returns the selected entry if exactly one entry is selected
+    fn get_selected_process_entry(&self) -> Option<ProcessEntry> {
+        let mut entries = self.get_selected_process_entries();
+        (entries.len() == 1).then(|| entries.remove(0))
+    }
+
+    fn get_selected_process_items(&self) -> Vec<ProcessItem> {
+        self.get_selected_process_entries()
+            .iter()
+            .filter_map(ProcessEntry::process_item)
+            .collect()
     }
 
     fn get_selected_process_item(&self) -> Option<ProcessItem> {
+        self.get_selected_process_entry()
+            .and_then(|entry| entry.process_item())
+    }
+
+    /// Returns the PID of the process whose dialog is open
+    pub fn dialog_pid(&self) -> Option<i32> {
         self.imp()
-            .selection_model
+            .open_dialog
             .borrow()
-            .selected_item()
-            .and_then(|object| object.downcast::<ProcessEntry>().unwrap().process_item())
+            .as_ref()
+            .filter(|(_, dialog)| dialog.is_visible())
+            .map(|(pid, _)| *pid)
     }
 
     pub fn refresh_processes_list(&self, apps: &AppsContext) {
@@ -616,6 +1062,8 @@ impl ResProcesses {
 
         let mut new_items = apps.process_items();
         let mut pids_to_remove = HashSet::new();
+        let mut dead_entries = Vec::new();
+        let mut new_entries = Vec::with_capacity(new_items.len());
 
         // change process entries of processes that have existed before
         store.iter::<ProcessEntry>().flatten().for_each(|object| {
@@ -629,11 +1077,15 @@ impl ResProcesses {
                     }
                 }
                 pids_to_remove.insert(item_pid);
+                dead_entries.push(object.clone());
             }
             if let Some((_, new_item)) = new_items.remove_entry(&item_pid) {
                 if let Some((dialog_pid, dialog)) = dialog_opt {
                     if *dialog_pid == item_pid {
                         dialog.update(&new_item);
+                        if let Some(history) = apps.process_history(item_pid) {
+                            dialog.update_history(history);
+                        }
                     }
                 }
                 object.update(new_item);
@@ -648,10 +1100,22 @@ impl ResProcesses {
         // add the newly started process to the store
         for (_, new_item) in new_items.drain() {
             let user_name = self.get_user_name_by_uid(new_item.uid);
-            store.append(&ProcessEntry::new(new_item, &user_name));
+            let entry = ProcessEntry::new(new_item, &user_name);
+            store.append(&entry);
+            new_entries.push(entry);
         }
 
-        store.items_changed(0, store.n_items(), store.n_items());
+        self.refresh_process_tree(&new_entries, &dead_entries);
+
+        if imp.tree_mode.get() {
+            // re-announcing every item would collapse the whole tree, so only
+            // ask the sorter to sort again
+            if let Some(sorter) = imp.sort_model.borrow().sorter() {
+                sorter.changed(SorterChange::Different);
+            }
+        } else {
+            store.items_changed(0, store.n_items(), store.n_items());
+        }
 
         self.set_property(
             "tab_subtitle",
@@ -659,28 +1123,108 @@ impl ResProcesses {
         );
     }
 
-    pub fn execute_process_action_dialog(&self, process: ProcessItem, action: ProcessAction) {
+    /// Keeps the process tree in sync with the flat list of processes. Instead
+    /// of rebuilding the tree, entries of dead processes are removed from
+    /// their parents, new entries are appended to their parents and entries
+    /// whose parent has changed (e. g. because they have been reparented to
+    /// init) are moved, so that expanded rows stay expanded.
+    fn refresh_process_tree(&self, new_entries: &[ProcessEntry], dead_entries: &[ProcessEntry]) {
         let imp = self.imp();
 
+        let tree_store = imp.tree_store.borrow();
+        let mut entries = imp.entries.borrow_mut();
+
+        // whether a parent had children before we started moving entries around
+        let mut had_children = HashMap::new();
+
+        for dead_entry in dead_entries {
+            detach_from_tree(dead_entry, &tree_store, &entries, &mut had_children);
+            // living children will be placed somewhere else below
+            dead_entry.children().remove_all();
+        }
+
+        for dead_entry in dead_entries {
+            entries.remove(&dead_entry.pid());
+        }
+
+        let new_pids: HashSet<i32> = new_entries.iter().map(ProcessEntry::pid).collect();
+        for new_entry in new_entries {
+            entries.insert(new_entry.pid(), new_entry.clone());
+        }
+
+        let misplaced_entries: Vec<ProcessEntry> = entries
+            .values()
+            .filter(|entry| {
+                new_pids.contains(&entry.pid())
+                    || entry
+                        .tree_parent()
+                        .map_or(false, |tree_parent| !entries.contains_key(&tree_parent))
+                    || entry.tree_parent() != desired_tree_parent(entry, &entries)
+            })
+            .cloned()
+            .collect();
+
+        for entry in misplaced_entries {
+            if !new_pids.contains(&entry.pid()) {
+                detach_from_tree(&entry, &tree_store, &entries, &mut had_children);
+            }
+
+            let tree_parent = desired_tree_parent(&entry, &entries);
+            if let Some(parent) = tree_parent.and_then(|parent_pid| entries.get(&parent_pid)) {
+                had_children
+                    .entry(parent.pid())
+                    .or_insert_with(|| parent.children().n_items() > 0);
+                parent.children().append(&entry);
+            } else {
+                tree_store.append(&entry);
+            }
+            entry.set_tree_parent(tree_parent);
+        }
+
+        // the tree model remembers whether a row is expandable, so rows that
+        // gained their first child or lost their last one need to be re-added
+        for (parent_pid, had_children) in had_children {
+            let Some(parent) = entries.get(&parent_pid) else {
+                continue;
+            };
+
+            if new_pids.contains(&parent_pid) || (parent.children().n_items() > 0) == had_children {
+                continue;
+            }
+
+            if let Some(container) = tree_container(parent, &tree_store, &entries) {
+                if let Some(position) = position_in_store(&container, parent) {
+                    container.items_changed(position, 1, 1);
+                }
+            }
+        }
+    }
+
+    /// Executes `action` on `processes` after asking for confirmation
+    pub fn execute_process_action_dialog(
+        &self,
+        processes: Vec<ProcessItem>,
+        action: ProcessAction,
+    ) {
         // Nothing too bad can happen on Continue so dont show the dialog
         if action == ProcessAction::CONT {
-            send!(
-                imp.sender.get().unwrap(),
-                Action::ManipulateProcess(
-                    action,
-                    process.pid,
-                    process.display_name,
-                    imp.toast_overlay.get()
-                )
-            );
+            self.send_process_action(&processes, action);
             return;
         }
 
+        let display_names = processes
+            .iter()
+            .map(|process| process.display_name.as_str())
+            .collect::<Vec<_>>();
+
         // Confirmation dialog & warning
         let dialog = adw::MessageDialog::builder()
             .transient_for(&MainWindow::default())
             .modal(true)
-            .heading(window::get_action_name(action, &[&process.display_name]))
+            .heading(window::get_action_name(
+                action,
+                &[&window::name_list(&display_names)],
+            ))
             .body(window::get_app_action_warning(action))
             .build();
 
@@ -694,18 +1238,9 @@ impl ResProcesses {
         // Called when "yes" or "no" were clicked
         dialog.connect_response(
             None,
-            clone!(@strong self as this, @strong process => move |_, response| {
+            clone!(@strong self as this => move |_, response| {
                 if response == "yes" {
-                    let imp = this.imp();
-                    send!(
-                        imp.sender.get().unwrap(),
-                        Action::ManipulateProcess(
-                            action,
-                            process.pid,
-                            process.clone().display_name,
-                            imp.toast_overlay.get()
-                        )
-                    );
+                    this.send_process_action(&processes, action);
                 }
             }),
         );
@@ -713,6 +1248,91 @@ impl ResProcesses {
         dialog.show();
     }
 
+    fn send_process_action(&self, processes: &[ProcessItem], action: ProcessAction) {
+        let imp = self.imp();
+
+        let action = if let [process] = processes {
+            Action::ManipulateProcess(
+                action,
+                process.pid,
+                process.display_name.clone(),
+                imp.toast_overlay.get(),
+            )
+        } else {
+            Action::ManipulateProcesses(
+                action,
+                processes
+                    .iter()
+                    .map(|process| (process.pid, process.display_name.clone()))
+                    .collect(),
+                imp.toast_overlay.get(),
+            )
+        };
+
+        send!(imp.sender.get().unwrap(), action);
+    }
+
+    pub fn send_signal_dialog(&self, process: ProcessItem) {
+        signal_dialog::show_signal_dialog(
+            &process.display_name,
+            clone!(@strong self as this, @strong process => move |action| {
+                let imp = this.imp();
+                send!(
+                    imp.sender.get().unwrap(),
+                    Action::ManipulateProcess(
+                        action,
+                        process.pid,
+                        process.display_name.clone(),
+                        imp.toast_overlay.get()
+                    )
+                );
+            }),
+        );
+    }
+
+    pub fn change_priority_dialog<W: IsA<gtk::Window>>(&self, process: ProcessItem, parent: &W) {
+        priority_dialog::show_priority_dialog(
+            parent,
+            &process.display_name,
+            process.nice,
+            process.scheduling_policy,
+            process.io_priority,
+            clone!(@strong self as this, @strong process => move |adjustments| {
+                let imp = this.imp();
+                send!(
+                    imp.sender.get().unwrap(),
+                    Action::AdjustProcess(
+                        adjustments,
+                        process.pid,
+                        process.display_name.clone(),
+                        imp.toast_overlay.get()
+                    )
+                );
+            }),
+        );
+    }
+
+    pub fn change_affinity_dialog<W: IsA<gtk::Window>>(&self, process: ProcessItem, parent: &W) {
+        affinity_dialog::show_affinity_dialog(
+            parent,
+            &process.display_name,
+            process.last_cpu,
+            process.affinity.clone(),
+            clone!(@strong self as this, @strong process => move |cpus| {
+                let imp = this.imp();
+                send!(
+                    imp.sender.get().unwrap(),
+                    Action::AdjustProcess(
+                        vec![ProcessAdjustment::Affinity(cpus)],
+                        process.pid,
+                        process.display_name.clone(),
+                        imp.toast_overlay.get()
+                    )
+                );
+            }),
+        );
+    }
+
     fn get_user_name_by_uid(&self, uid: u32) -> String {
         let imp = self.imp();
         // cache all the user names so we don't have
@@ -730,3 +1350,56 @@ impl ResProcesses {
         .to_string()
     }
 }
+
+/// Returns an expression for the `ProcessEntry` of a `gtk::ListItem`, which is
+/// wrapped inside of a `gtk::TreeListRow`
+fn entry_expression(item: &gtk::ListItem) -> gtk::PropertyExpression {
+    item.property_expression("item")
+        .chain_property::<TreeListRow>("item")
+}
+
+fn desired_tree_parent(entry: &ProcessEntry, entries: &HashMap<i32, ProcessEntry>) -> Option<i32> {
+    let parent_pid = entry.parent_pid();
+    (parent_pid != entry.pid() && entries.contains_key(&parent_pid)).then_some(parent_pid)
+}
+
+/// Returns the store `entry` is currently placed in, `None` if its parent
+/// has died in the meantime
+fn tree_container(
+    entry: &ProcessEntry,
+    tree_store: &gio::ListStore,
+    entries: &HashMap<i32, ProcessEntry>,
+) -> Option<gio::ListStore> {
+    match entry.tree_parent() {
+        Some(parent_pid) => entries
+            .get(&parent_pid)
+            .map(|parent| parent.children().clone()),
+        None => Some(tree_store.clone()),
+    }
+}
+
+fn detach_from_tree(
+    entry: &ProcessEntry,
+    tree_store: &gio::ListStore,
+    entries: &HashMap<i32, ProcessEntry>,
+    had_children: &mut HashMap<i32, bool>,
+) {
+    if let Some(container) = tree_container(entry, tree_store, entries) {
+        if let Some(parent_pid) = entry.tree_parent() {
+            had_children
+                .entry(parent_pid)
+                .or_insert_with(|| container.n_items() > 0);
+        }
+        if let Some(position) = position_in_store(&container, entry) {
+            container.remove(position);
+        }
+    }
+}
+
+fn position_in_store(store: &gio::ListStore, entry: &ProcessEntry) -> Option<u32> {
+    (0..store.n_items()).find(|position| {
+        store
+            .item(*position)
+            .map_or(false, |item| &item == entry.upcast_ref::<Object>())
+    })
+}
@@ -1,9 +1,15 @@
+use std::time::SystemTime;
+
 use gtk::{
+    gio,
     glib::{self},
     subclass::prelude::ObjectSubclassIsExt,
 };
 
-use crate::utils::process::ProcessItem;
+use crate::utils::{
+    process::{containerization_description, containerization_name, ProcessItem},
+    search::{Field, Searchable},
+};
 
 mod imp {
     use std::cell::{Cell, RefCell};
@@ -32,9 +38,18 @@ mod imp {
         #[property(get = Self::icon, set = Self::set_icon, type = Icon)]
         icon: RefCell<Icon>,
 
+        #[property(get = Self::containerization, set = Self::set_containerization, type = glib::GString)]
+        containerization: Cell<glib::GString>, // will be empty if not containerized
+
+        #[property(get = Self::containerization_description, set = Self::set_containerization_description, type = glib::GString)]
+        containerization_description: Cell<glib::GString>,
+
         #[property(get, set)]
         pid: Cell<i32>,
 
+        #[property(get, set)]
+        parent_pid: Cell<i32>,
+
         #[property(get, set)]
         cpu_usage: Cell<f32>,
 
@@ -53,7 +68,42 @@ mod imp {
         #[property(get, set)]
         write_total: Cell<i64>, // will be -1 if write data is not available
 
+        #[property(get, set)]
+        gpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        gpu_memory: Cell<u64>,
+
+        #[property(get, set)]
+        thread_count: Cell<u32>,
+
+        #[property(get, set)]
+        nice: Cell<i32>,
+
+        #[property(get, set)]
+        scheduling_policy: Cell<u32>,
+
+        #[property(get, set)]
+        io_priority: Cell<i32>, // will be -1 if the I/O priority is not available
+
+        #[property(get, set)]
+        state: Cell<u8>, // the state letter as found in /proc/<pid>/stat
+
+        #[property(get, set)]
+        start_time: Cell<i64>, // will be -1 if the start time is not available
+
+        #[property(get, set)]
+        running_time: Cell<i64>, // will be -1 if the start time is not available
+
         pub process_item: RefCell<Option<ProcessItem>>,
+
+        /// The entries of the processes whose parent is this process, used
+        /// as the child model when showing the processes as a tree
+        pub children: gio::ListStore,
+
+        /// The PID of the entry whose `children` this entry is currently
+        /// placed in, `None` if it's a root of the process tree
+        pub tree_parent: Cell<Option<i32>>,
     }
 
     impl Default for ProcessEntry {
@@ -63,7 +113,10 @@ mod imp {
                 commandline: Cell::new(glib::GString::default()),
                 user: Cell::new(glib::GString::default()),
                 icon: RefCell::new(ThemedIcon::new("generic-process").into()),
+                containerization: Cell::new(glib::GString::default()),
+                containerization_description: Cell::new(glib::GString::default()),
                 pid: Cell::new(0),
+                parent_pid: Cell::new(0),
                 cpu_usage: Cell::new(0.0),
                 memory_usage: Cell::new(0),
                 process_item: RefCell::new(None),
@@ -71,6 +124,17 @@ mod imp {
                 read_total: Cell::new(0),
                 write_speed: Cell::new(0.0),
                 write_total: Cell::new(0),
+                gpu_usage: Cell::new(0.0),
+                gpu_memory: Cell::new(0),
+                thread_count: Cell::new(0),
+                nice: Cell::new(0),
+                scheduling_policy: Cell::new(0),
+                io_priority: Cell::new(-1),
+                state: Cell::new(b'S'),
+                start_time: Cell::new(-1),
+                running_time: Cell::new(-1),
+                children: gio::ListStore::new::<super::ProcessEntry>(),
+                tree_parent: Cell::new(None),
             }
         }
     }
@@ -121,6 +185,31 @@ mod imp {
         pub fn set_icon(&self, icon: &Icon) {
             self.icon.set(icon.clone());
         }
+
+        pub fn containerization(&self) -> glib::GString {
+            let containerization = self.containerization.take();
+            let result = containerization.clone();
+            self.containerization.set(containerization);
+            result
+        }
+
+        pub fn set_containerization(&self, containerization: &str) {
+            self.containerization
+                .set(glib::GString::from(containerization));
+        }
+
+        pub fn containerization_description(&self) -> glib::GString {
+            let containerization_description = self.containerization_description.take();
+            let result = containerization_description.clone();
+            self.containerization_description
+                .set(containerization_description);
+            result
+        }
+
+        pub fn set_containerization_description(&self, containerization_description: &str) {
+            self.containerization_description
+                .set(glib::GString::from(containerization_description));
+        }
     }
 
     #[glib::object_subclass]
@@ -159,6 +248,14 @@ impl ProcessEntry {
             .property("commandline", &process_item.commandline)
             .property("user", user)
             .property("icon", &process_item.icon)
+            .property(
+                "containerization",
+                containerization_name(&process_item.containerization).unwrap_or_default(),
+            )
+            .property(
+                "containerization_description",
+                containerization_description(&process_item.containerization).unwrap_or_default(),
+            )
             .property("pid", process_item.pid)
             .build();
         this.update(process_item);
@@ -166,6 +263,7 @@ impl ProcessEntry {
     }
 
     pub fn update(&self, process_item: ProcessItem) {
+        self.set_parent_pid(process_item.parent_pid);
         self.set_cpu_usage(process_item.cpu_time_ratio);
         self.set_memory_usage(process_item.memory_usage as u64);
         self.set_read_speed(process_item.read_speed.unwrap_or(-1.0));
@@ -182,9 +280,49 @@ impl ProcessEntry {
                 .map(|write_total| write_total as i64)
                 .unwrap_or(-1),
         );
+        self.set_gpu_usage(process_item.gpu_usage);
+        self.set_gpu_memory(process_item.gpu_memory);
+        self.set_thread_count(process_item.thread_count);
+        self.set_nice(i32::from(process_item.nice));
+        self.set_scheduling_policy(process_item.scheduling_policy);
+        self.set_io_priority(
+            process_item
+                .io_priority
+                .map(|io_priority| io_priority.to_raw())
+                .unwrap_or(-1),
+        );
+        self.set_state(u8::try_from(process_item.state).unwrap_or(b'?'));
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        self.set_start_time(
+            process_item
+                .start_time
+                .map(|start_time| start_time as i64)
+                .unwrap_or(-1),
+        );
+        self.set_running_time(
+            process_item
+                .start_time
+                .map(|start_time| now.saturating_sub(start_time) as i64)
+                .unwrap_or(-1),
+        );
         self.imp().process_item.replace(Some(process_item));
     }
 
+    pub fn children(&self) -> &gio::ListStore {
+        &self.imp().children
+    }
+
+    pub fn tree_parent(&self) -> Option<i32> {
+        self.imp().tree_parent.get()
+    }
+
+    pub fn set_tree_parent(&self, tree_parent: Option<i32>) {
+        self.imp().tree_parent.set(tree_parent);
+    }
+
     pub fn process_item(&self) -> Option<ProcessItem> {
         let imp = self.imp();
         let item = imp.process_item.take();
@@ -192,3 +330,38 @@ impl ProcessEntry {
         item
     }
 }
+
+impl Searchable for ProcessEntry {
+    fn default_fields(&self) -> &'static [Field] {
+        &[Field::Name, Field::Commandline]
+    }
+
+    fn text(&self, field: Field) -> Option<String> {
+        match field {
+            Field::Name => Some(self.name().to_string()),
+            Field::Commandline => Some(self.commandline().to_string()),
+            Field::User => Some(self.user().to_string()),
+            Field::Cgroup => self
+                .imp()
+                .process_item
+                .borrow()
+                .as_ref()
+                .and_then(|process_item| process_item.cgroup.clone()),
+            Field::Container => Some(self.containerization_description().to_string()),
+            _ => None,
+        }
+    }
+
+    fn number(&self, field: Field) -> Option<f64> {
+        match field {
+            Field::Pid => Some(f64::from(self.pid())),
+            Field::Cpu => Some(f64::from(self.cpu_usage()) * 100.0),
+            Field::Memory => Some(self.memory_usage() as f64),
+            Field::Read => Some(self.read_speed()).filter(|speed| *speed >= 0.0),
+            Field::Write => Some(self.write_speed()).filter(|speed| *speed >= 0.0),
+            Field::Gpu => Some(f64::from(self.gpu_usage()) * 100.0),
+            Field::VideoMemory => Some(self.gpu_memory() as f64),
+            _ => None,
+        }
+    }
+}
@@ -20,6 +20,10 @@ mod imp {
         pub image: TemplateChild<gtk::Image>,
         #[template_child]
         pub inscription: TemplateChild<gtk::Inscription>,
+        #[template_child]
+        pub badge_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub flag_image: TemplateChild<gtk::Image>,
 
         #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
         name: Cell<glib::GString>,
@@ -27,6 +31,16 @@ mod imp {
         tooltip: Cell<glib::GString>,
         #[property(get = Self::icon, set = Self::set_icon, type = Icon)]
         icon: RefCell<Icon>,
+        /// The way the process is containerized, the badge is hidden if this
+        /// is empty
+        #[property(get = Self::badge, set = Self::set_badge, type = glib::GString)]
+        badge: Cell<glib::GString>,
+        #[property(get = Self::badge_tooltip, set = Self::set_badge_tooltip, type = glib::GString)]
+        badge_tooltip: Cell<glib::GString>,
+        /// Explains why the process has been flagged, e. g. because it's a
+        /// zombie, the flag is hidden if this is empty
+        #[property(get = Self::flag, set = Self::set_flag, type = glib::GString)]
+        flag: Cell<glib::GString>,
     }
 
     impl Default for ResProcessNameCell {
@@ -34,9 +48,14 @@ mod imp {
             Self {
                 image: Default::default(),
                 inscription: Default::default(),
+                badge_label: Default::default(),
+                flag_image: Default::default(),
                 name: Default::default(),
                 tooltip: Default::default(),
                 icon: RefCell::new(ThemedIcon::new("generic-process").into()),
+                badge: Default::default(),
+                badge_tooltip: Default::default(),
+                flag: Default::default(),
             }
         }
     }
@@ -101,6 +120,64 @@ mod imp {
         }
     }
 
+    impl ResProcessNameCell {
+        pub fn flag(&self) -> glib::GString {
+            let flag = self.flag.take();
+            let result = flag.clone();
+            self.flag.set(flag);
+
+            result
+        }
+
+        pub fn set_flag(&self, flag: &str) {
+            let current_flag = self.flag.take();
+            if current_flag.as_str() == flag {
+                self.flag.set(current_flag);
+                return;
+            }
+            self.flag.set(glib::GString::from(flag));
+            self.flag_image.set_visible(!flag.is_empty());
+            self.flag_image.set_tooltip_text(Some(flag));
+        }
+
+        pub fn badge(&self) -> glib::GString {
+            let badge = self.badge.take();
+            let result = badge.clone();
+            self.badge.set(badge);
+
+            result
+        }
+
+        pub fn set_badge(&self, badge: &str) {
+            let current_badge = self.badge.take();
+            if current_badge.as_str() == badge {
+                self.badge.set(current_badge);
+                return;
+            }
+            self.badge.set(glib::GString::from(badge));
+            self.badge_label.set_label(badge);
+            self.badge_label.set_visible(!badge.is_empty());
+        }
+
+        pub fn badge_tooltip(&self) -> glib::GString {
+            let badge_tooltip = self.badge_tooltip.take();
+            let result = badge_tooltip.clone();
+            self.badge_tooltip.set(badge_tooltip);
+
+            result
+        }
+
+        pub fn set_badge_tooltip(&self, badge_tooltip: &str) {
+            let current_badge_tooltip = self.badge_tooltip.take();
+            if current_badge_tooltip.as_str() == badge_tooltip {
+                self.badge_tooltip.set(current_badge_tooltip);
+                return;
+            }
+            self.badge_tooltip.set(glib::GString::from(badge_tooltip));
+            self.badge_label.set_tooltip_text(Some(badge_tooltip));
+        }
+    }
+
     #[glib::object_subclass]
     impl ObjectSubclass for ResProcessNameCell {
         const NAME: &'static str = "ResProcessNameCell";
@@ -151,6 +151,20 @@ impl ResGraph {
             .unwrap_or(&0.0)
     }
 
+    /// Replaces all data points, keeping only the newest ones if there are
+    /// more than fit into the graph
+    pub fn set_data_points<I: IntoIterator<Item = f64>>(&self, data: I) {
+        let imp = self.imp();
+        let mut data_points = imp.data_points.borrow_mut();
+        data_points.clear();
+        data_points.extend(data);
+        let excess = data_points
+            .len()
+            .saturating_sub(*imp.data_points_max_amount.borrow());
+        data_points.drain(..excess);
+        imp.obj().queue_draw();
+    }
+
     pub fn push_data_point(&self, data: f64) {
         let imp = self.imp();
         let mut data_points = imp.data_points.borrow_mut();
@@ -1,7 +1,10 @@
+use std::collections::VecDeque;
+
 use adw::{prelude::*, subclass::prelude::*};
 use gtk::glib;
 
 use crate::config::PROFILE;
+use crate::i18n::i18n_f;
 
 mod imp {
     use crate::ui::widgets::graph::ResGraph;
@@ -86,6 +89,24 @@ impl ResGraphBox {
         imp.graph.push_data_point(data);
     }
 
+    pub fn set_data_points<I: IntoIterator<Item = f64>>(&self, data: I) {
+        let imp = self.imp();
+        imp.graph.set_data_points(data);
+    }
+
+    /// Shows `samples` and describes the newest and the highest of them in
+    /// the subtitle, formatted using `format`
+    pub fn set_history<F: Fn(f64) -> String>(&self, samples: &VecDeque<f64>, format: F) {
+        self.set_data_points(samples.iter().copied());
+        self.set_subtitle(&i18n_f(
+            "{} · Highest: {}",
+            &[
+                &format(samples.back().copied().unwrap_or_default()),
+                &format(self.get_highest_value()),
+            ],
+        ));
+    }
+
     pub fn set_title_label(&self, str: &str) {
         let imp = self.imp();
         imp.title_label.set_label(str);
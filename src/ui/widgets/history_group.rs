@@ -0,0 +1,103 @@
+use adw::{prelude::*, subclass::prelude::*};
+use gtk::glib;
+
+use crate::config::PROFILE;
+use crate::i18n::i18n;
+use crate::utils::app::{UsageHistory, HISTORY_LENGTH};
+use crate::utils::units::{convert_speed, convert_storage};
+
+mod imp {
+    use crate::ui::widgets::graph_box::ResGraphBox;
+
+    use super::*;
+
+    use gtk::CompositeTemplate;
+
+    #[derive(Debug, CompositeTemplate, Default)]
+    #[template(resource = "/net/nokyan/Resources/ui/widgets/history_group.ui")]
+    pub struct ResHistoryGroup {
+        #[template_child]
+        pub cpu: TemplateChild<ResGraphBox>,
+        #[template_child]
+        pub memory: TemplateChild<ResGraphBox>,
+        #[template_child]
+        pub drive_read: TemplateChild<ResGraphBox>,
+        #[template_child]
+        pub drive_write: TemplateChild<ResGraphBox>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResHistoryGroup {
+        const NAME: &'static str = "ResHistoryGroup";
+        type Type = super::ResHistoryGroup;
+        type ParentType = adw::PreferencesGroup;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResHistoryGroup {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+
+            obj.setup_graphs();
+        }
+    }
+
+    impl WidgetImpl for ResHistoryGroup {}
+
+    impl PreferencesGroupImpl for ResHistoryGroup {}
+}
+
+glib::wrapper! {
+    pub struct ResHistoryGroup(ObjectSubclass<imp::ResHistoryGroup>)
+        @extends gtk::Widget, adw::PreferencesGroup;
+}
+
+impl ResHistoryGroup {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    fn setup_graphs(&self) {
+        let imp = self.imp();
+
+        for (graph_box, title, (r, g, b)) in [
+            (&imp.cpu, i18n("Processor"), (28, 113, 216)),
+            (&imp.memory, i18n("Memory"), (129, 61, 156)),
+            (&imp.drive_read, i18n("Drive Read"), (229, 165, 10)),
+            (&imp.drive_write, i18n("Drive Write"), (222, 77, 119)),
+        ] {
+            graph_box.set_title_label(&title);
+            graph_box.set_graph_color(r, g, b);
+            graph_box.set_data_points_max_amount(HISTORY_LENGTH);
+            graph_box.set_locked_max_y(None);
+        }
+    }
+
+    /// Plots the usage during the last refreshes
+    pub fn update(&self, history: &UsageHistory) {
+        let imp = self.imp();
+
+        imp.cpu
+            .set_history(&history.cpu, |ratio| format!("{:.1} %", ratio * 100.0));
+        imp.memory
+            .set_history(&history.memory, |bytes| convert_storage(bytes, false));
+        imp.drive_read
+            .set_history(&history.read_speed, |speed| convert_speed(speed, false));
+        imp.drive_write
+            .set_history(&history.write_speed, |speed| convert_speed(speed, false));
+    }
+}
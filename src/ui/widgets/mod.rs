@@ -1,4 +1,6 @@
 pub mod graph;
 pub mod graph_box;
+pub mod history_group;
+pub mod pressure_group;
 pub mod stack_sidebar;
 pub mod stack_sidebar_item;
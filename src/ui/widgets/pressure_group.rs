@@ -0,0 +1,137 @@
+use adw::{prelude::*, subclass::prelude::*};
+use gtk::glib;
+
+use crate::config::PROFILE;
+use crate::i18n::i18n;
+use crate::utils::pressure::{Pressure, PressureAverages, PressureResource};
+
+mod imp {
+    use std::cell::Cell;
+
+    use crate::ui::widgets::graph_box::ResGraphBox;
+
+    use super::*;
+
+    use gtk::CompositeTemplate;
+
+    #[derive(Debug, CompositeTemplate)]
+    #[template(resource = "/net/nokyan/Resources/ui/widgets/pressure_group.ui")]
+    pub struct ResPressureGroup {
+        #[template_child]
+        pub some: TemplateChild<ResGraphBox>,
+        #[template_child]
+        pub full: TemplateChild<ResGraphBox>,
+
+        pub resource: Cell<PressureResource>,
+    }
+
+    impl Default for ResPressureGroup {
+        fn default() -> Self {
+            Self {
+                some: Default::default(),
+                full: Default::default(),
+                resource: Cell::new(PressureResource::Cpu),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResPressureGroup {
+        const NAME: &'static str = "ResPressureGroup";
+        type Type = super::ResPressureGroup;
+        type ParentType = adw::PreferencesGroup;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResPressureGroup {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+    }
+
+    impl WidgetImpl for ResPressureGroup {}
+
+    impl PreferencesGroupImpl for ResPressureGroup {}
+}
+
+glib::wrapper! {
+    pub struct ResPressureGroup(ObjectSubclass<imp::ResPressureGroup>)
+        @extends gtk::Widget, adw::PreferencesGroup;
+}
+
+/// Formats the averages over 10, 60 and 300 seconds in percent
+fn format_averages(averages: &PressureAverages) -> String {
+    format!(
+        "{:.1} % · {:.1} % · {:.1} %",
+        averages.avg10, averages.avg60, averages.avg300
+    )
+}
+
+impl ResPressureGroup {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    /// Sets up the graphs for the Pressure Stall Information of `resource`
+    /// on the whole system.
+    pub fn init(&self, resource: PressureResource, r: u8, g: u8, b: u8) {
+        let imp = self.imp();
+        imp.resource.set(resource);
+
+        self.set_description(Some(&match resource {
+            PressureResource::Cpu => i18n("Share of time processes had to wait for the processor"),
+            PressureResource::Memory => i18n("Share of time processes had to wait for memory"),
+            PressureResource::Io => i18n("Share of time processes had to wait for drives"),
+        }));
+
+        for graph_box in [&imp.some, &imp.full] {
+            graph_box.set_data_points_max_amount(60);
+            graph_box.set_graph_color(r, g, b);
+            graph_box.set_tooltip(Some(&i18n("Averaged over 10, 60 and 300 seconds")));
+        }
+        imp.some.set_title_label(&i18n("Some Processes Waiting"));
+        imp.full.set_title_label(&i18n("All Processes Waiting"));
+
+        // the kernel only reports full CPU pressure for cgroups, for the
+        // whole system it is always zero
+        imp.full.set_visible(resource != PressureResource::Cpu);
+
+        self.refresh();
+    }
+
+    /// Reads the current pressure, the group is hidden if the kernel doesn't
+    /// support PSI.
+    pub fn refresh(&self) {
+        let imp = self.imp();
+
+        let Ok(pressure) = Pressure::system(imp.resource.get()) else {
+            self.set_visible(false);
+            return;
+        };
+        self.set_visible(true);
+
+        imp.some.push_data_point(pressure.some.avg10 / 100.0);
+        imp.some.set_subtitle(&format_averages(&pressure.some));
+
+        if let Some(full) = pressure.full {
+            imp.full.push_data_point(full.avg10 / 100.0);
+            imp.full.set_subtitle(&format_averages(&full));
+        } else {
+            imp.full.set_visible(false);
+        }
+    }
+}
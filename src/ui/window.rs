@@ -1,4 +1,4 @@
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -7,19 +7,24 @@ use adw::{Toast, ToastOverlay};
 use anyhow::{Context, Result};
 use gtk::glib::{clone, timeout_future, MainContext};
 use gtk::{gio, glib, Widget};
+use process_data::Containerization;
 
 use crate::application::Application;
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f, ni18n_f};
+use crate::ui::dialogs::app_dialog::ResAppDialog;
+use crate::ui::dialogs::process_dialog::ResProcessDialog;
 use crate::ui::pages::applications::ResApplications;
+use crate::ui::pages::containers::ResContainers;
 use crate::ui::pages::drive::ResDrive;
 use crate::ui::pages::processes::ResProcesses;
-use crate::utils::app::AppsContext;
+use crate::utils::app::{App, AppsContext};
+use crate::utils::cgroup::CgroupLimits;
 use crate::utils::cpu;
 use crate::utils::drive::Drive;
 use crate::utils::gpu::GPU;
 use crate::utils::network::{InterfaceType, NetworkInterface};
-use crate::utils::process::ProcessAction;
+use crate::utils::process::{Process, ProcessAction, ProcessAdjustment, ProcessIdentityChanged};
 use crate::utils::settings::SETTINGS;
 
 use super::pages::gpu::ResGPU;
@@ -28,7 +33,15 @@ use super::pages::network::ResNetwork;
 #[derive(Debug, Clone)]
 pub enum Action {
     ManipulateProcess(ProcessAction, i32, String, ToastOverlay),
+    ManipulateProcesses(ProcessAction, Vec<(i32, String)>, ToastOverlay),
+    AdjustProcess(Vec<ProcessAdjustment>, i32, String, ToastOverlay),
     ManipulateApp(ProcessAction, String, ToastOverlay),
+    ManipulateApps(ProcessAction, Vec<String>, ToastOverlay),
+    /// The previous and the new limits of the app
+    LimitApp(CgroupLimits, CgroupLimits, String, ToastOverlay),
+    ManipulateContainer(ProcessAction, Containerization, String, ToastOverlay),
+    UpdateProcessHistory(i32, ResProcessDialog),
+    UpdateAppHistory(Option<String>, ResAppDialog),
 }
 
 mod imp {
@@ -37,8 +50,8 @@ mod imp {
     use crate::{
         ui::{
             pages::{
-                applications::ResApplications, cpu::ResCPU, memory::ResMemory,
-                processes::ResProcesses,
+                applications::ResApplications, containers::ResContainers, cpu::ResCPU,
+                memory::ResMemory, processes::ResProcesses,
             },
             widgets::stack_sidebar::ResStackSidebar,
         },
@@ -77,6 +90,10 @@ mod imp {
         #[template_child]
         pub processes_page: TemplateChild<gtk::StackPage>,
         #[template_child]
+        pub containers: TemplateChild<ResContainers>,
+        #[template_child]
+        pub containers_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
         pub memory: TemplateChild<ResMemory>,
         #[template_child]
         pub memory_page: TemplateChild<gtk::StackPage>,
@@ -108,6 +125,8 @@ mod imp {
                 applications_page: TemplateChild::default(),
                 processes: TemplateChild::default(),
                 processes_page: TemplateChild::default(),
+                containers: TemplateChild::default(),
+                containers_page: TemplateChild::default(),
                 cpu: TemplateChild::default(),
                 cpu_page: TemplateChild::default(),
                 memory: TemplateChild::default(),
@@ -208,6 +227,8 @@ impl MainWindow {
             imp.applications.toggle_search();
         } else if selected_page.is::<ResProcesses>() {
             imp.processes.toggle_search();
+        } else if selected_page.is::<ResContainers>() {
+            imp.containers.toggle_search();
         }
     }
 
@@ -218,6 +239,7 @@ impl MainWindow {
 
         imp.applications.init(imp.sender.clone());
         imp.processes.init(imp.sender.clone());
+        imp.containers.init(imp.sender.clone());
         imp.cpu.init();
         imp.memory.init();
 
@@ -229,6 +251,10 @@ impl MainWindow {
             imp.applications.toggle_search()
         }
 
+        if SETTINGS.show_search_on_start() {
+            imp.containers.toggle_search()
+        }
+
         let main_context = MainContext::default();
         main_context.spawn_local(clone!(@strong self as this => async move {
             let imp = this.imp();
@@ -305,9 +331,17 @@ impl MainWindow {
                 loop {
                     {
                         let mut apps_context = imp.apps_context.lock().await;
+                        apps_context.set_detailed_process(imp.processes.dialog_pid());
+                        apps_context.set_detailed_app(imp.applications.dialog_app());
                         apps_context.refresh().await;
                         imp.applications.refresh_apps_list(&apps_context);
                         imp.processes.refresh_processes_list(&apps_context);
+                        imp.containers.refresh_containers_list(&apps_context);
+                        if let Ok(gpu_pages) = imp.gpu_pages.try_borrow() {
+                            for gpu_page_toolbar in gpu_pages.iter() {
+                                gpu_page_toolbar.content().and_downcast::<ResGPU>().unwrap().refresh_processes(&apps_context);
+                            }
+                        }
                     }
 
                     timeout_future(Duration::from_secs_f32(SETTINGS.refresh_speed().process_refresh_interval())).await;
@@ -444,8 +478,12 @@ impl MainWindow {
                     if let Some(process) = apps_context.get_process(pid) {
                         let toast_message = match process.execute_process_action(action) {
                             Ok(()) => get_action_success(action, &[&display_name]),
+                            Err(e) if e.is::<ProcessIdentityChanged>() => {
+                                log_action_failure(action, pid, &e);
+                                get_process_identity_changed(&[&display_name])
+                            }
                             Err(e) => {
-                                log::error!("Unable to kill process {}: {}", pid, e);
+                                log_action_failure(action, pid, &e);
                                 get_process_action_failure(action, &[&display_name])
                             }
                         };
@@ -453,28 +491,149 @@ impl MainWindow {
                     }
                 }
 
+                Action::ManipulateProcesses(action, processes, toast_overlay) => {
+                    // a single batch so that the user is asked for authorization
+                    // at most once
+                    let (processes, display_names): (Vec<_>, Vec<_>) = processes
+                        .into_iter()
+                        .filter_map(|(pid, display_name)| {
+                            apps_context.get_process(pid).map(|process| (process, display_name))
+                        })
+                        .unzip();
+                    let results = Process::execute_process_action_batch(processes, action);
+
+                    let mut succeeded = Vec::new();
+                    let mut failed = Vec::new();
+                    for ((pid, result), display_name) in results.into_iter().zip(display_names) {
+                        match result {
+                            Ok(()) => succeeded.push(display_name),
+                            Err(e) => {
+                                log_action_failure(action, pid, &e);
+                                failed.push(display_name);
+                            }
+                        }
+                    }
+                    let toast_message = get_bulk_action_result(action, &succeeded, &failed);
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                }
+
+                Action::AdjustProcess(adjustments, pid, display_name, toast_overlay) => {
+                    if let Some(process) = apps_context.get_process(pid) {
+                        let affinity = adjustments
+                            .iter()
+                            .any(|adjustment| matches!(adjustment, ProcessAdjustment::Affinity(_)));
+                        let result = adjustments
+                            .into_iter()
+                            .try_for_each(|adjustment| process.adjust(adjustment));
+                        let toast_message = match result {
+                            Ok(()) => get_adjustment_success(affinity, &[&display_name]),
+                            Err(e) if e.is::<ProcessIdentityChanged>() => {
+                                log::warn!("Not adjusting process {}: {}", pid, e);
+                                get_process_identity_changed(&[&display_name])
+                            }
+                            Err(e) => {
+                                log::error!("Unable to adjust process {}: {}", pid, e);
+                                get_adjustment_failure(affinity, &[&display_name])
+                            }
+                        };
+                        toast_overlay.add_toast(Toast::new(&toast_message));
+                    }
+                }
+
                 Action::ManipulateApp(action, id, toast_overlay) => {
                     let app = apps_context.get_app(&id).unwrap();
-                    let res = app.execute_process_action(&apps_context, action);
 
-                    for r in &res {
-                        if let Err(e) = r {
-                            log::error!("Unable to kill a process: {}", e);
+                    let toast_message = match set_app_frozen(app, action).await {
+                        Some(Ok(())) => get_action_success(action, &[&app.display_name]),
+                        Some(Err(_)) => get_process_action_failure(action, &[&app.display_name]),
+                        None => {
+                            let res = app.execute_process_action(&apps_context, action);
+                            get_batch_action_result(action, &app.display_name, &res)
+                        }
+                    };
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                }
+
+                Action::ManipulateApps(action, ids, toast_overlay) => {
+                    let apps = ids
+                        .iter()
+                        .filter_map(|id| apps_context.get_app(id))
+                        .collect::<Vec<_>>();
+
+                    let mut succeeded = Vec::new();
+                    let mut failed = Vec::new();
+                    let mut signalled_apps = Vec::new();
+                    for app in apps {
+                        match set_app_frozen(app, action).await {
+                            Some(Ok(())) => succeeded.push(app.display_name.clone()),
+                            Some(Err(_)) => failed.push(app.display_name.clone()),
+                            None => signalled_apps.push(app),
                         }
                     }
 
-                    let processes_tried = res.len();
-                    let processes_successful = res.iter().flatten().count();
-                    let processes_unsuccessful = processes_tried - processes_successful;
+                    // the processes of all other apps are signalled in a single
+                    // batch so that the user is asked for authorization at most
+                    // once
+                    let results = Process::execute_process_action_batch(
+                        signalled_apps
+                            .iter()
+                            .flat_map(|app| app.processes_iter(&apps_context)),
+                        action,
+                    );
+                    let mut failed_pids = HashSet::new();
+                    for (pid, result) in results {
+                        if let Err(e) = result {
+                            log_action_failure(action, pid, &e);
+                            failed_pids.insert(pid);
+                        }
+                    }
 
-                    let toast_message = if processes_unsuccessful > 0 {
-                        get_app_action_failure(action, processes_unsuccessful as u32)
-                    } else {
-                        get_action_success(action, &[&app.display_name])
-                    };
+                    for app in signalled_apps {
+                        if app
+                            .processes_iter(&apps_context)
+                            .any(|process| failed_pids.contains(&process.data.pid))
+                        {
+                            failed.push(app.display_name.clone());
+                        } else {
+                            succeeded.push(app.display_name.clone());
+                        }
+                    }
 
+                    let toast_message = get_bulk_action_result(action, &succeeded, &failed);
                     toast_overlay.add_toast(Toast::new(&toast_message));
                 }
+
+                Action::LimitApp(previous, limits, id, toast_overlay) => {
+                    if let Some(app) = apps_context.get_app(&id) {
+                        let toast_message = match app.set_limits(&previous, &limits).await {
+                            Ok(()) => get_limits_success(&[&app.display_name]),
+                            Err(e) => {
+                                log::error!("Unable to limit {}: {}", id, e);
+                                get_limits_failure(&[&app.display_name])
+                            }
+                        };
+                        toast_overlay.add_toast(Toast::new(&toast_message));
+                    }
+                }
+
+                Action::ManipulateContainer(action, containerization, display_name, toast_overlay) => {
+                    let res = apps_context.execute_container_action(&containerization, action);
+
+                    let toast_message = get_batch_action_result(action, &display_name, &res);
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                }
+
+                Action::UpdateProcessHistory(pid, dialog) => {
+                    if let Some(history) = apps_context.process_history(pid) {
+                        dialog.update_history(history);
+                    }
+                }
+
+                Action::UpdateAppHistory(id, dialog) => {
+                    if let Some(history) = apps_context.app_history(id.as_deref()) {
+                        dialog.update_history(history);
+                    }
+                }
             };
         }));
 
@@ -558,6 +717,10 @@ pub fn get_action_name(action: ProcessAction, args: &[&str]) -> String {
         ProcessAction::STOP => i18n_f("Halt {}?", args),
         ProcessAction::KILL => i18n_f("Kill {}?", args),
         ProcessAction::CONT => i18n_f("Continue {}?", args),
+        _ => i18n_f(
+            "Send SIG{} to {}?",
+            &[&[action.signal_name().as_str()], args].concat(),
+        ),
     }
 }
 
@@ -567,6 +730,7 @@ pub fn get_app_action_warning(action: ProcessAction) -> String {
             ProcessAction::STOP => i18n("Halting an application can come with serious risks such as losing data and security implications. Use with caution."),
             ProcessAction::KILL => i18n("Killing an application can come with serious risks such as losing data and security implications. Use with caution."),
             ProcessAction::CONT => String::new(),
+            _ => i18n("Processes that don't handle this signal will usually end. Unsaved work might be lost."),
         }
 }
 
@@ -576,6 +740,36 @@ pub fn get_app_action_description(action: ProcessAction) -> String {
         ProcessAction::STOP => i18n("Halt application"),
         ProcessAction::KILL => i18n("Kill application"),
         ProcessAction::CONT => i18n("Continue application"),
+        _ => i18n("Send signal"),
+    }
+}
+
+pub fn get_container_action_name(action: ProcessAction, args: &[&str]) -> String {
+    match action {
+        ProcessAction::TERM => i18n_f("Stop {}?", args),
+        ProcessAction::STOP => i18n_f("Pause {}?", args),
+        ProcessAction::CONT => i18n_f("Resume {}?", args),
+        _ => get_action_name(action, args),
+    }
+}
+
+pub fn get_container_action_warning(action: ProcessAction) -> String {
+    match action {
+            ProcessAction::TERM => i18n("All processes inside of the container will be asked to end, unsaved data might be lost."),
+            ProcessAction::STOP => i18n("Pausing a container halts all processes inside of it, network connections to it might time out."),
+            ProcessAction::KILL => i18n("Killing a container can come with serious risks such as losing data. Use with caution."),
+            ProcessAction::CONT => String::new(),
+            _ => i18n("Processes that don't handle this signal will usually end. Unsaved work might be lost."),
+        }
+}
+
+pub fn get_container_action_description(action: ProcessAction) -> String {
+    match action {
+        ProcessAction::TERM => i18n("Stop container"),
+        ProcessAction::STOP => i18n("Pause container"),
+        ProcessAction::KILL => i18n("Kill container"),
+        ProcessAction::CONT => i18n("Resume container"),
+        _ => i18n("Send signal"),
     }
 }
 
@@ -585,34 +779,166 @@ pub fn get_action_success(action: ProcessAction, args: &[&str]) -> String {
         ProcessAction::STOP => i18n_f("Successfully halted {}", args),
         ProcessAction::KILL => i18n_f("Successfully killed {}", args),
         ProcessAction::CONT => i18n_f("Successfully continued {}", args),
+        _ => i18n_f(
+            "Successfully sent SIG{} to {}",
+            &[&[action.signal_name().as_str()], args].concat(),
+        ),
+    }
+}
+
+/// Summarizes the results of executing `action` on all processes of an app or a
+/// container called `display_name` for use in a toast
+fn get_batch_action_result(
+    action: ProcessAction,
+    display_name: &str,
+    results: &[(i32, Result<()>)],
+) -> String {
+    let mut failed_pids = Vec::new();
+    let mut identity_changed_pids = Vec::new();
+    for (pid, r) in results {
+        match r {
+            Err(e) if e.is::<ProcessIdentityChanged>() => {
+                log_action_failure(action, *pid, e);
+                identity_changed_pids.push(*pid);
+            }
+            Err(e) => {
+                log_action_failure(action, *pid, e);
+                failed_pids.push(*pid);
+            }
+            Ok(()) => (),
+        }
+    }
+
+    if !failed_pids.is_empty() {
+        get_app_action_failure(action, &failed_pids)
+    } else if !identity_changed_pids.is_empty() {
+        get_app_identity_changed(&identity_changed_pids)
+    } else {
+        get_action_success(action, &[display_name])
+    }
+}
+
+/// Freezes or thaws `app` as a whole for `action` if it lives in cgroups of its
+/// own, returns `None` if its processes have to be signalled one by one instead
+async fn set_app_frozen(app: &App, action: ProcessAction) -> Option<Result<()>> {
+    if !app.is_freezable() || !matches!(action, ProcessAction::STOP | ProcessAction::CONT) {
+        return None;
+    }
+
+    let result = app.set_frozen(action == ProcessAction::STOP).await;
+    if let Err(e) = &result {
+        log::error!("Unable to freeze or thaw {}: {}", app.id, e);
+    }
+    Some(result)
+}
+
+/// Logs why sending the signal of `action` to the process `pid` failed
+fn log_action_failure(action: ProcessAction, pid: i32, error: &anyhow::Error) {
+    if error.is::<ProcessIdentityChanged>() {
+        log::warn!(
+            "Not sending SIG{} to process {}: {}",
+            action.signal_name(),
+            pid,
+            error
+        );
+    } else {
+        log::error!(
+            "Unable to send SIG{} to process {}: {}",
+            action.signal_name(),
+            pid,
+            error
+        );
+    }
+}
+
+/// Summarizes the results of executing `action` on several processes or apps
+/// for use in a toast, naming the ones that succeeded and the ones that failed
+fn get_bulk_action_result(
+    action: ProcessAction,
+    succeeded: &[String],
+    failed: &[String],
+) -> String {
+    if failed.is_empty() {
+        get_action_success(action, &[&name_list(succeeded)])
+    } else if succeeded.is_empty() {
+        get_process_action_failure(action, &[&name_list(failed)])
+    } else {
+        i18n_f(
+            "{}. {}",
+            &[
+                &get_action_success(action, &[&name_list(succeeded)]),
+                &get_process_action_failure(action, &[&name_list(failed)]),
+            ],
+        )
+    }
+}
+
+/// Formats `names` as a comma-separated list for use in dialogs and toasts,
+/// only naming the first few of them
+pub fn name_list<S: AsRef<str>>(names: &[S]) -> String {
+    const SHOWN_NAMES: usize = 3;
+
+    let list = names
+        .iter()
+        .take(SHOWN_NAMES)
+        .map(AsRef::as_ref)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if names.len() > SHOWN_NAMES {
+        let others = names.len() - SHOWN_NAMES;
+        ni18n_f(
+            "{} and {} other",
+            "{} and {} others",
+            others as u32,
+            &[&list, &others.to_string()],
+        )
+    } else {
+        list
     }
 }
 
-pub fn get_app_action_failure(action: ProcessAction, args: u32) -> String {
+/// Formats `pids` as a comma-separated list for use in toasts
+fn pid_list(pids: &[i32]) -> String {
+    pids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn get_app_action_failure(action: ProcessAction, failed_pids: &[i32]) -> String {
+    let count = failed_pids.len() as u32;
+    let pids = pid_list(failed_pids);
     match action {
         ProcessAction::TERM => ni18n_f(
-            "There was a problem ending a process",
-            "There were problems ending {} processes",
-            args,
-            &[&args.to_string()],
+            "There was a problem ending process {}",
+            "There were problems ending processes {}",
+            count,
+            &[&pids],
         ),
         ProcessAction::STOP => ni18n_f(
-            "There was a problem halting a process",
-            "There were problems halting {} processes",
-            args,
-            &[&args.to_string()],
+            "There was a problem halting process {}",
+            "There were problems halting processes {}",
+            count,
+            &[&pids],
         ),
         ProcessAction::KILL => ni18n_f(
-            "There was a problem killing a process",
-            "There were problems killing {} processes",
-            args,
-            &[&args.to_string()],
+            "There was a problem killing process {}",
+            "There were problems killing processes {}",
+            count,
+            &[&pids],
         ),
         ProcessAction::CONT => ni18n_f(
-            "There was a problem continuing a process",
-            "There were problems continuing {} processes",
-            args,
-            &[&args.to_string()],
+            "There was a problem continuing process {}",
+            "There were problems continuing processes {}",
+            count,
+            &[&pids],
+        ),
+        _ => ni18n_f(
+            "There was a problem sending SIG{} to process {}",
+            "There were problems sending SIG{} to processes {}",
+            count,
+            &[&action.signal_name(), &pids],
         ),
     }
 }
@@ -623,5 +949,52 @@ pub fn get_process_action_failure(action: ProcessAction, args: &[&str]) -> Strin
         ProcessAction::STOP => i18n_f("There was a problem halting {}", args),
         ProcessAction::KILL => i18n_f("There was a problem killing {}", args),
         ProcessAction::CONT => i18n_f("There was a problem continuing {}", args),
+        _ => i18n_f(
+            "There was a problem sending SIG{} to {}",
+            &[&[action.signal_name().as_str()], args].concat(),
+        ),
     }
 }
+
+pub fn get_adjustment_success(affinity: bool, args: &[&str]) -> String {
+    if affinity {
+        i18n_f("Successfully changed the CPU affinity of {}", args)
+    } else {
+        i18n_f("Successfully changed the priority of {}", args)
+    }
+}
+
+pub fn get_adjustment_failure(affinity: bool, args: &[&str]) -> String {
+    if affinity {
+        i18n_f("There was a problem changing the CPU affinity of {}", args)
+    } else {
+        i18n_f("There was a problem changing the priority of {}", args)
+    }
+}
+
+pub fn get_limits_success(args: &[&str]) -> String {
+    i18n_f("Successfully changed the resource limits of {}", args)
+}
+
+pub fn get_limits_failure(args: &[&str]) -> String {
+    i18n_f(
+        "There was a problem changing the resource limits of {}",
+        args,
+    )
+}
+
+pub fn get_process_identity_changed(args: &[&str]) -> String {
+    i18n_f(
+        "{} has ended in the meantime and its process ID now belongs to another process, nothing has been done",
+        args,
+    )
+}
+
+pub fn get_app_identity_changed(pids: &[i32]) -> String {
+    ni18n_f(
+        "Process {} has ended in the meantime and its process ID now belongs to another process, it has been skipped",
+        "Processes {} have ended in the meantime and their process IDs now belong to other processes, they have been skipped",
+        pids.len() as u32,
+        &[&pid_list(pids)],
+    )
+}
@@ -1,14 +1,21 @@
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use futures_util::future::join_all;
 use gtk::gio::{Icon, ThemedIcon};
 use hashbrown::{HashMap, HashSet};
 use once_cell::sync::Lazy;
-use process_data::Containerization;
+use process_data::{Containerization, MemoryDetails, ScanOptions};
 
 use crate::i18n::i18n;
 
-use super::process::{Process, ProcessAction, ProcessItem};
+use super::cgroup::{self, CgroupLimits, CgroupStats};
+use super::container;
+use super::gpu;
+use super::pressure::CgroupPressure;
+use super::process::{Process, ProcessAction, ProcessItem, LOGICAL_CPUS};
+use super::settings::{CpuUsageMode, MemoryMetric, SETTINGS};
 
 // Adapted from Mission Center: https://gitlab.com/mission-center-devs/mission-center/
 static DATA_DIRS: Lazy<Vec<PathBuf>> = Lazy::new(|| {
@@ -39,8 +46,25 @@ pub struct AppsContext {
     apps: HashMap<String, App>,
     processes: HashMap<i32, Process>,
     processes_assigned_to_apps: HashSet<i32>,
+    container_names: HashMap<Containerization, String>,
     read_bytes_from_dead_processes: u64,
     write_bytes_from_dead_processes: u64,
+    /// The timestamps of the newest NVML utilization samples per GPU
+    nvidia_last_seen_timestamps: HashMap<String, u64>,
+    /// Keyed by the PID and the start time of the process so that a recycled
+    /// PID doesn't inherit the history of the previous process
+    process_histories: HashMap<(i32, u64), UsageHistory>,
+    /// `None` is the history of the system processes
+    app_histories: HashMap<Option<String>, UsageHistory>,
+    /// The process whose details are shown, see
+    /// [`AppsContext::set_detailed_process`]
+    detailed_process: Option<i32>,
+    /// The app whose details are shown, see [`AppsContext::set_detailed_app`]
+    detailed_app: Option<Option<String>>,
+    /// The memory metric chosen by the user, read once per refresh
+    memory_metric: MemoryMetric,
+    /// The CPU usage mode chosen by the user, read once per refresh
+    cpu_usage_mode: CpuUsageMode,
 }
 
 /// Convenience struct for displaying running applications and
@@ -52,6 +76,7 @@ pub struct AppItem {
     pub icon: Icon,
     pub description: Option<String>,
     pub memory_usage: usize,
+    pub memory_details: Option<MemoryDetails>,
     pub cpu_time_ratio: f32,
     pub processes_amount: usize,
     pub containerization: Containerization,
@@ -59,6 +84,67 @@ pub struct AppItem {
     pub read_total: u64,
     pub write_speed: f64,
     pub write_total: u64,
+    /// Whether the app's cgroups are frozen
+    pub frozen: bool,
+    /// The cgroups of the app, empty if its processes don't live in cgroups
+    /// of their own
+    pub cgroup_paths: Vec<String>,
+    /// `None` if the app doesn't live in cgroups of its own or the kernel
+    /// doesn't support PSI
+    pub pressure: Option<CgroupPressure>,
+    /// The usage of the busiest GPU from 0.0 to 1.0
+    pub gpu_usage: f32,
+    /// The video memory used on all GPUs in bytes
+    pub gpu_memory: u64,
+}
+
+/// The amount of samples kept in every `UsageHistory`
+pub const HISTORY_LENGTH: usize = 60;
+
+/// The usage of an app or a process during the last `HISTORY_LENGTH`
+/// refreshes, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct UsageHistory {
+    /// In the same unit as `Process::cpu_time_ratio`
+    pub cpu: VecDeque<f64>,
+    /// In bytes
+    pub memory: VecDeque<f64>,
+    /// In bytes per second
+    pub read_speed: VecDeque<f64>,
+    /// In bytes per second
+    pub write_speed: VecDeque<f64>,
+}
+
+impl UsageHistory {
+    fn push(&mut self, cpu: f32, memory: usize, read_speed: f64, write_speed: f64) {
+        for (samples, sample) in [
+            (&mut self.cpu, f64::from(cpu)),
+            (&mut self.memory, memory as f64),
+            (&mut self.read_speed, read_speed),
+            (&mut self.write_speed, write_speed),
+        ] {
+            if samples.len() >= HISTORY_LENGTH {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+        }
+    }
+}
+
+/// Convenience struct for displaying running containers, their usage is the
+/// sum of the usage of all processes inside of them.
+#[derive(Debug, Clone)]
+pub struct ContainerItem {
+    pub containerization: Containerization,
+    pub display_name: String,
+    pub memory_usage: usize,
+    pub memory_details: Option<MemoryDetails>,
+    pub cpu_time_ratio: f32,
+    pub processes_amount: usize,
+    pub read_speed: f64,
+    pub read_total: u64,
+    pub write_speed: f64,
+    pub write_total: u64,
 }
 
 /// Represents an application installed on the system. It doesn't
@@ -73,6 +159,12 @@ pub struct App {
     pub id: String,
     pub read_bytes_from_dead_processes: u64,
     pub write_bytes_from_dead_processes: u64,
+    /// The cgroups of this app's processes, empty unless every one of them
+    /// lives in a cgroup named after this app
+    cgroup_paths: Vec<String>,
+    cgroup_stats: Option<CgroupStats>,
+    cgroup_stats_last: Option<CgroupStats>,
+    pressure: Option<CgroupPressure>,
 }
 
 impl App {
@@ -126,6 +218,10 @@ impl App {
             id,
             read_bytes_from_dead_processes: 0,
             write_bytes_from_dead_processes: 0,
+            cgroup_paths: Vec::new(),
+            cgroup_stats: None,
+            cgroup_stats_last: None,
+            pressure: None,
         })
     }
 
@@ -159,30 +255,135 @@ impl App {
             .filter(move |process| self.processes.contains(&process.data.pid))
     }
 
+    /// Reads the accounting and pressure of this app's cgroups if all of its
+    /// processes live in cgroups named after it. The statistics of the
+    /// previous call are kept in order to calculate CPU usage and I/O speeds.
+    pub async fn refresh_cgroup_stats(&mut self, processes: &HashMap<i32, Process>) {
+        let cgroup_paths = self
+            .processes
+            .iter()
+            .map(|pid| {
+                processes
+                    .get(pid)
+                    .filter(|process| process.data.cgroup.as_deref() == Some(self.id.as_str()))
+                    .and_then(|process| process.data.cgroup_path.clone())
+            })
+            .collect::<Option<HashSet<_>>>()
+            .unwrap_or_default();
+
+        // nested cgroups are already accounted for in their parents
+        let mut cgroup_paths = cgroup_paths
+            .iter()
+            .filter(|path| {
+                !cgroup_paths
+                    .iter()
+                    .any(|other| path.starts_with(&format!("{other}/")))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        cgroup_paths.sort_unstable();
+
+        let cgroup_stats = join_all(
+            cgroup_paths
+                .iter()
+                .map(|path| CgroupStats::from_dir(cgroup::cgroup_dir(path))),
+        )
+        .await
+        .into_iter()
+        .map(Result::ok)
+        .collect::<Option<Vec<_>>>()
+        .and_then(|stats| stats.into_iter().reduce(|a, b| a + b));
+
+        // deltas between different sets of cgroups would be meaningless
+        self.cgroup_stats_last = if cgroup_paths == self.cgroup_paths {
+            self.cgroup_stats.take()
+        } else {
+            None
+        };
+        self.cgroup_stats = cgroup_stats;
+
+        self.pressure = join_all(cgroup_paths.iter().map(CgroupPressure::read))
+            .await
+            .into_iter()
+            .map(Result::ok)
+            .collect::<Option<Vec<_>>>()
+            .and_then(|pressures| pressures.into_iter().reduce(CgroupPressure::max));
+
+        self.cgroup_paths = cgroup_paths;
+    }
+
+    /// Returns the current and the previous cgroup statistics of this app, if
+    /// both are available
+    fn cgroup_stats_pair(&self) -> Option<(&CgroupStats, &CgroupStats)> {
+        self.cgroup_stats
+            .as_ref()
+            .zip(self.cgroup_stats_last.as_ref())
+    }
+
+    /// Returns the memory charged to this app's cgroups, which unlike the
+    /// memory metric of its processes includes e. g. the page cache, or the
+    /// sum of its processes' memory usage if it has no cgroups of its own.
     #[must_use]
     pub fn memory_usage(&self, apps: &AppsContext) -> usize {
+        self.cgroup_stats.map_or_else(
+            || {
+                self.processes_iter(apps)
+                    .map(|process| process.memory_usage(apps.memory_metric))
+                    .sum()
+            },
+            |stats| stats.memory_current as usize,
+        )
+    }
+
+    /// Returns the sum of the detailed memory usage of this app's processes,
+    /// `None` if it's not available for any of them. The swap usage is taken
+    /// from the app's cgroup if possible.
+    #[must_use]
+    pub fn memory_details(&self, apps: &AppsContext) -> Option<MemoryDetails> {
         self.processes_iter(apps)
-            .map(|process| process.data.memory_usage)
-            .sum()
+            .filter_map(|process| process.data.memory_details)
+            .reduce(|a, b| a + b)
+            .map(|mut memory_details| {
+                if let Some(swap) = self
+                    .cgroup_stats
+                    .and_then(|stats| stats.memory_swap_current)
+                {
+                    memory_details.swap = swap as usize;
+                }
+                memory_details
+            })
     }
 
     #[must_use]
     pub fn cpu_time_ratio(&self, apps: &AppsContext) -> f32 {
+        if let Some((stats, stats_last)) = self.cgroup_stats_pair() {
+            return stats.cpu_time_ratio(stats_last, apps.cpu_usage_mode, *LOGICAL_CPUS);
+        }
+
         self.processes_iter(apps)
-            .map(Process::cpu_time_ratio)
+            .map(|process| process.cpu_time_ratio(apps.cpu_usage_mode))
             .sum::<f32>()
-            .clamp(0.0, 1.0)
+            .clamp(0.0, apps.cpu_usage_mode.max_ratio(*LOGICAL_CPUS))
     }
 
     #[must_use]
     pub fn read_speed(&self, apps: &AppsContext) -> f64 {
-        self.processes_iter(apps)
-            .filter_map(Process::read_speed)
-            .sum()
+        self.cgroup_stats_pair()
+            .and_then(|(stats, stats_last)| stats.read_speed(stats_last))
+            .unwrap_or_else(|| {
+                self.processes_iter(apps)
+                    .filter_map(Process::read_speed)
+                    .sum()
+            })
     }
 
     #[must_use]
     pub fn read_total(&self, apps: &AppsContext) -> u64 {
+        // io.stat already includes processes that have exited
+        if let Some(read_bytes) = self.cgroup_stats.and_then(|stats| stats.read_bytes) {
+            return read_bytes;
+        }
+
         self.read_bytes_from_dead_processes.saturating_add(
             self.processes_iter(apps)
                 .filter_map(|process| process.data.read_bytes)
@@ -192,13 +393,21 @@ impl App {
 
     #[must_use]
     pub fn write_speed(&self, apps: &AppsContext) -> f64 {
-        self.processes_iter(apps)
-            .filter_map(Process::write_speed)
-            .sum()
+        self.cgroup_stats_pair()
+            .and_then(|(stats, stats_last)| stats.write_speed(stats_last))
+            .unwrap_or_else(|| {
+                self.processes_iter(apps)
+                    .filter_map(Process::write_speed)
+                    .sum()
+            })
     }
 
     #[must_use]
     pub fn write_total(&self, apps: &AppsContext) -> u64 {
+        if let Some(write_bytes) = self.cgroup_stats.and_then(|stats| stats.write_bytes) {
+            return write_bytes;
+        }
+
         self.write_bytes_from_dead_processes.saturating_add(
             self.processes_iter(apps)
                 .filter_map(|process| process.data.write_bytes)
@@ -210,10 +419,42 @@ impl App {
         &self,
         apps: &AppsContext,
         action: ProcessAction,
-    ) -> Vec<Result<()>> {
-        self.processes_iter(apps)
-            .map(|process| process.execute_process_action(action))
-            .collect()
+    ) -> Vec<(i32, Result<()>)> {
+        Process::execute_process_action_batch(self.processes_iter(apps), action)
+    }
+
+    /// Whether this app can be halted and continued through the cgroup
+    /// freezer, i. e. all of its processes live in cgroups of their own
+    #[must_use]
+    pub fn is_freezable(&self) -> bool {
+        !self.cgroup_paths.is_empty()
+    }
+
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        self.cgroup_stats.is_some_and(|stats| stats.frozen)
+    }
+
+    /// Freezes or thaws all cgroups of this app. Unlike sending `SIGSTOP`,
+    /// this also catches children spawned in the meantime and isn't
+    /// noticeable for the app.
+    pub async fn set_frozen(&self, frozen: bool) -> Result<()> {
+        for cgroup_path in &self.cgroup_paths {
+            cgroup::set_frozen(cgroup_path, frozen).await?;
+        }
+        Ok(())
+    }
+
+    /// Sets the limits that differ between `previous` and `limits` on all
+    /// cgroups of this app, they are lifted again once the app is closed
+    pub async fn set_limits(&self, previous: &CgroupLimits, limits: &CgroupLimits) -> Result<()> {
+        if self.cgroup_paths.is_empty() {
+            bail!("{} doesn't run in cgroups of its own", self.id);
+        }
+        for cgroup_path in &self.cgroup_paths {
+            cgroup::set_limits(cgroup_path, previous, limits).await?;
+        }
+        Ok(())
     }
 }
 
@@ -231,8 +472,16 @@ impl AppsContext {
             apps,
             processes: HashMap::new(),
             processes_assigned_to_apps: HashSet::new(),
+            container_names: HashMap::new(),
             read_bytes_from_dead_processes: 0,
             write_bytes_from_dead_processes: 0,
+            nvidia_last_seen_timestamps: HashMap::new(),
+            process_histories: HashMap::new(),
+            app_histories: HashMap::new(),
+            detailed_process: None,
+            detailed_app: None,
+            memory_metric: SETTINGS.memory_metric(),
+            cpu_usage_mode: SETTINGS.cpu_usage_mode(),
         }
     }
 
@@ -285,6 +534,56 @@ impl AppsContext {
         self.processes.get(&pid)
     }
 
+    /// Returns the recent usage of the process with the PID `pid`
+    pub fn process_history(&self, pid: i32) -> Option<&UsageHistory> {
+        self.get_process(pid)
+            .and_then(|process| self.process_histories.get(&(pid, process.data.starttime)))
+    }
+
+    /// Returns the recent usage of the app with the ID `id`, `None` stands
+    /// for the system processes
+    pub fn app_history(&self, id: Option<&str>) -> Option<&UsageHistory> {
+        self.app_histories.get(&id.map(str::to_string))
+    }
+
+    /// Sets the process whose details are shown, e. g. in an open dialog.
+    /// Details like its threads are only gathered for this process during
+    /// the following refreshes.
+    pub fn set_detailed_process(&mut self, pid: Option<i32>) {
+        self.detailed_process = pid;
+    }
+
+    /// Sets the app whose details are shown like
+    /// [`AppsContext::set_detailed_process`], `Some(None)` stands for the
+    /// system processes.
+    pub fn set_detailed_app(&mut self, id: Option<Option<String>>) {
+        self.detailed_app = id;
+    }
+
+    /// Decides what is read about the processes during the next refresh
+    fn scan_options(&self) -> ScanOptions {
+        let mut options = ScanOptions {
+            thread_pids: self.detailed_process.into_iter().collect(),
+            // the other metrics need the memory details of every process
+            memory_details: !matches!(self.memory_metric, MemoryMetric::ResidentWithoutShared),
+            memory_details_pids: self.detailed_process.into_iter().collect(),
+        };
+
+        match &self.detailed_app {
+            Some(Some(id)) => {
+                if let Some(app) = self.apps.get(id) {
+                    options.memory_details_pids.extend(app.processes.iter());
+                }
+            }
+            Some(None) => options
+                .memory_details_pids
+                .extend(self.system_processes_iter().map(|process| process.data.pid)),
+            None => (),
+        }
+
+        options
+    }
+
     pub fn get_app(&self, id: &str) -> Option<&App> {
         self.apps.get(id)
     }
@@ -315,12 +614,15 @@ impl AppsContext {
             } else {
                 process.data.comm.clone()
             };
+            let gpu_usages = process.gpu_usages();
             ProcessItem {
                 pid: process.data.pid,
+                parent_pid: process.data.parent_pid,
                 display_name: full_comm.clone(),
                 icon: process.icon.clone(),
-                memory_usage: process.data.memory_usage,
-                cpu_time_ratio: process.cpu_time_ratio(),
+                memory_usage: process.memory_usage(self.memory_metric),
+                memory_details: process.data.memory_details,
+                cpu_time_ratio: process.cpu_time_ratio(self.cpu_usage_mode),
                 commandline: Process::sanitize_cmdline(process.data.commandline.clone())
                     .unwrap_or(full_comm),
                 containerization: process.data.containerization.clone(),
@@ -330,6 +632,21 @@ impl AppsContext {
                 read_total: process.data.read_bytes,
                 write_speed: process.write_speed(),
                 write_total: process.data.write_bytes,
+                thread_count: process.data.thread_count,
+                threads: process.thread_items(self.cpu_usage_mode),
+                nice: process.data.nice,
+                scheduling_policy: process.data.scheduling_policy,
+                io_priority: process.data.io_priority,
+                last_cpu: process.data.last_cpu,
+                affinity: process.data.affinity.clone(),
+                state: process.data.state,
+                start_time: process.start_time(),
+                gpu_usage: gpu_usages
+                    .values()
+                    .map(|gpu_usage| gpu_usage.usage)
+                    .fold(0.0, f32::max),
+                gpu_memory: gpu_usages.values().map(|gpu_usage| gpu_usage.memory).sum(),
+                gpu_usages,
             }
         })
     }
@@ -349,18 +666,18 @@ impl AppsContext {
                     app_pids.insert(process.data.pid);
                 });
 
-                let containerization = if app
+                let (gpu_usage, gpu_memory) = Process::combined_gpu_usage(app.processes_iter(self));
+
+                let containerization = app
                     .processes_iter(self)
                     .filter(|process| {
                         !process.data.commandline.starts_with("bwrap")
                             && !process.data.commandline.is_empty()
                     })
-                    .any(|process| process.data.containerization == Containerization::Flatpak)
-                {
-                    Containerization::Flatpak
-                } else {
-                    Containerization::None
-                };
+                    .map(|process| &process.data.containerization)
+                    .find(|containerization| **containerization != Containerization::None)
+                    .cloned()
+                    .unwrap_or_default();
 
                 (
                     Some(app.id.clone()),
@@ -370,6 +687,7 @@ impl AppsContext {
                         icon: app.icon.clone(),
                         description: app.description.clone(),
                         memory_usage: app.memory_usage(self),
+                        memory_details: app.memory_details(self),
                         cpu_time_ratio: app.cpu_time_ratio(self),
                         processes_amount: app.processes_iter(self).count(),
                         containerization,
@@ -377,21 +695,39 @@ impl AppsContext {
                         read_total: app.read_total(self),
                         write_speed: app.write_speed(self),
                         write_total: app.write_total(self),
+                        frozen: app.is_frozen(),
+                        cgroup_paths: app.cgroup_paths.clone(),
+                        pressure: app.pressure,
+                        gpu_usage,
+                        gpu_memory,
                     },
                 )
             })
             .collect::<HashMap<Option<String>, AppItem>>();
 
+        return_map.insert(None, self.system_processes_item());
+        return_map
+    }
+
+    /// Returns the `AppItem` of the processes that neither belong to an app nor
+    /// run inside of a container, see [`AppsContext::system_processes_iter`]
+    fn system_processes_item(&self) -> AppItem {
         let system_cpu_ratio = self
             .system_processes_iter()
-            .map(Process::cpu_time_ratio)
-            .sum();
+            .map(|process| process.cpu_time_ratio(self.cpu_usage_mode))
+            .sum::<f32>()
+            .clamp(0.0, self.cpu_usage_mode.max_ratio(*LOGICAL_CPUS));
 
         let system_memory_usage: usize = self
             .system_processes_iter()
-            .map(|process| process.data.memory_usage)
+            .map(|process| process.memory_usage(self.memory_metric))
             .sum();
 
+        let system_memory_details = self
+            .system_processes_iter()
+            .filter_map(|process| process.data.memory_details)
+            .reduce(|a, b| a + b);
+
         let system_read_speed = self
             .system_processes_iter()
             .filter_map(|process| process.read_speed())
@@ -414,29 +750,98 @@ impl AppsContext {
                 .filter_map(|process| process.data.write_bytes)
                 .sum::<u64>();
 
-        return_map.insert(
-            None,
-            AppItem {
-                id: None,
-                display_name: i18n("System Processes"),
-                icon: ThemedIcon::new("system-processes").into(),
-                description: None,
-                memory_usage: system_memory_usage,
-                cpu_time_ratio: system_cpu_ratio,
-                processes_amount: self.processes.len(),
-                containerization: Containerization::None,
-                read_speed: system_read_speed,
-                read_total: system_read_total,
-                write_speed: system_write_speed,
-                write_total: system_write_total,
-            },
-        );
-        return_map
+        let (system_gpu_usage, system_gpu_memory) =
+            Process::combined_gpu_usage(self.system_processes_iter());
+
+        AppItem {
+            id: None,
+            display_name: i18n("System Processes"),
+            icon: ThemedIcon::new("system-processes").into(),
+            description: None,
+            memory_usage: system_memory_usage,
+            memory_details: system_memory_details,
+            cpu_time_ratio: system_cpu_ratio,
+            processes_amount: self.processes.len(),
+            containerization: Containerization::None,
+            read_speed: system_read_speed,
+            read_total: system_read_total,
+            write_speed: system_write_speed,
+            write_total: system_write_total,
+            frozen: false,
+            cgroup_paths: Vec::new(),
+            pressure: None,
+            gpu_usage: system_gpu_usage,
+            gpu_memory: system_gpu_memory,
+        }
+    }
+
+    /// Returns a `HashMap` of running containers. For more info, refer to
+    /// `ContainerItem`.
+    #[must_use]
+    pub fn container_items(&self) -> HashMap<Containerization, ContainerItem> {
+        self.container_names
+            .iter()
+            .map(|(containerization, display_name)| {
+                let processes = || self.container_processes_iter(containerization);
+
+                (
+                    containerization.clone(),
+                    ContainerItem {
+                        containerization: containerization.clone(),
+                        display_name: display_name.clone(),
+                        memory_usage: processes()
+                            .map(|process| process.memory_usage(self.memory_metric))
+                            .sum(),
+                        memory_details: processes()
+                            .filter_map(|process| process.data.memory_details)
+                            .reduce(|a, b| a + b),
+                        cpu_time_ratio: processes()
+                            .map(|process| process.cpu_time_ratio(self.cpu_usage_mode))
+                            .sum::<f32>()
+                            .clamp(0.0, self.cpu_usage_mode.max_ratio(*LOGICAL_CPUS)),
+                        processes_amount: processes().count(),
+                        read_speed: processes().filter_map(Process::read_speed).sum(),
+                        read_total: processes()
+                            .filter_map(|process| process.data.read_bytes)
+                            .sum(),
+                        write_speed: processes().filter_map(Process::write_speed).sum(),
+                        write_total: processes()
+                            .filter_map(|process| process.data.write_bytes)
+                            .sum(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the processes running inside of the container `containerization`.
+    pub fn container_processes_iter<'a>(
+        &'a self,
+        containerization: &'a Containerization,
+    ) -> impl Iterator<Item = &Process> {
+        self.all_processes()
+            .filter(move |process| process.data.containerization == *containerization)
+    }
+
+    /// Executes `action` on all processes inside of the container
+    /// `containerization`.
+    pub fn execute_container_action(
+        &self,
+        containerization: &Containerization,
+        action: ProcessAction,
+    ) -> Vec<(i32, Result<()>)> {
+        Process::execute_process_action_batch(
+            self.container_processes_iter(containerization),
+            action,
+        )
     }
 
     /// Refreshes the statistics about the running applications and processes.
     pub async fn refresh(&mut self) {
-        let newly_gathered_processes = Process::all().await.unwrap_or_default();
+        self.memory_metric = SETTINGS.memory_metric();
+        self.cpu_usage_mode = SETTINGS.cpu_usage_mode();
+
+        let newly_gathered_processes = Process::all(&self.scan_options()).await.unwrap_or_default();
         let mut updated_processes = HashSet::new();
 
         for mut new_process in newly_gathered_processes {
@@ -449,6 +854,11 @@ impl AppsContext {
                 old_process.read_bytes_last_timestamp = old_process.data.read_bytes_timestamp;
                 old_process.write_bytes_last = old_process.data.write_bytes;
                 old_process.write_bytes_last_timestamp = old_process.data.write_bytes_timestamp;
+                old_process.threads_last = std::mem::take(&mut old_process.data.threads);
+                old_process.gpu_usage_stats_last =
+                    std::mem::take(&mut old_process.data.gpu_usage_stats);
+                old_process.gpu_usage_stats_last_timestamp =
+                    old_process.data.gpu_usage_stats_timestamp;
                 old_process.data = new_process.data.clone();
             } else {
                 // this is a new process, see if it belongs to a graphical app
@@ -529,10 +939,136 @@ impl AppsContext {
         // remove the dead process from out list of app processes
         self.processes_assigned_to_apps
             .retain(|pid| updated_processes.contains(pid));
+
+        // NVIDIA's proprietary driver doesn't expose DRM fdinfo, ask NVML instead
+        let mut nvidia_usages =
+            gpu::nvidia_process_usages(&mut self.nvidia_last_seen_timestamps).unwrap_or_default();
+        for process in self.processes.values_mut() {
+            process.nvidia_usages = nvidia_usages.remove(&process.data.pid).unwrap_or_default();
+        }
+
+        // prefer the kernel's accounting of app cgroups over summing up their
+        // processes, it is more precise and includes short-lived processes
+        for app in self.apps.values_mut() {
+            app.refresh_cgroup_stats(&self.processes).await;
+        }
+
+        // look up the names of newly started containers only once since that
+        // means reading their engines' metadata
+        let running_containers = self
+            .processes
+            .values()
+            .map(|process| &process.data.containerization)
+            .filter(|containerization| containerization.is_container())
+            .cloned()
+            .collect::<HashSet<_>>();
+        self.container_names
+            .retain(|containerization, _| running_containers.contains(containerization));
+        for containerization in running_containers {
+            if !self.container_names.contains_key(&containerization) {
+                let name = container::container_name(&containerization)
+                    .or_else(|| container::short_identifier(&containerization).map(str::to_string))
+                    .unwrap_or_default();
+                self.container_names.insert(containerization, name);
+            }
+        }
+
+        self.refresh_histories();
+    }
+
+    /// Appends the current usage of every process and app to their histories
+    /// and forgets the histories of those that aren't running anymore. The
+    /// usage of every process is only computed once and summed up for the
+    /// system processes.
+    fn refresh_histories(&mut self) {
+        let process_samples = self
+            .processes
+            .values()
+            .map(|process| {
+                (
+                    (process.data.pid, process.data.starttime),
+                    (
+                        process.cpu_time_ratio(self.cpu_usage_mode),
+                        process.memory_usage(self.memory_metric),
+                        process.read_speed().unwrap_or_default(),
+                        process.write_speed().unwrap_or_default(),
+                    ),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut app_samples = self
+            .apps
+            .values()
+            .filter(|app| app.is_running())
+            .map(|app| {
+                (
+                    Some(app.id.clone()),
+                    (
+                        app.cpu_time_ratio(self),
+                        app.memory_usage(self),
+                        app.read_speed(self),
+                        app.write_speed(self),
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let system_sample = self
+            .system_processes_iter()
+            .filter_map(|process| process_samples.get(&(process.data.pid, process.data.starttime)))
+            .fold(
+                (0.0, 0, 0.0, 0.0),
+                |(cpu, memory, read_speed, write_speed), sample| {
+                    (
+                        cpu + sample.0,
+                        memory + sample.1,
+                        read_speed + sample.2,
+                        write_speed + sample.3,
+                    )
+                },
+            );
+        app_samples.push((
+            None,
+            (
+                system_sample
+                    .0
+                    .clamp(0.0, self.cpu_usage_mode.max_ratio(*LOGICAL_CPUS)),
+                system_sample.1,
+                system_sample.2,
+                system_sample.3,
+            ),
+        ));
+
+        self.process_histories
+            .retain(|key, _| process_samples.contains_key(key));
+        for (key, (cpu, memory, read_speed, write_speed)) in process_samples {
+            self.process_histories.entry(key).or_default().push(
+                cpu,
+                memory,
+                read_speed,
+                write_speed,
+            );
+        }
+
+        self.app_histories.retain(|id, _| {
+            id.as_ref()
+                .map_or(true, |id| self.apps.get(id).is_some_and(App::is_running))
+        });
+        for (id, (cpu, memory, read_speed, write_speed)) in app_samples {
+            self.app_histories
+                .entry(id)
+                .or_default()
+                .push(cpu, memory, read_speed, write_speed);
+        }
     }
 
+    /// Returns the processes that neither belong to an app nor run inside of
+    /// a container.
     pub fn system_processes_iter(&self) -> impl Iterator<Item = &Process> {
-        self.all_processes()
-            .filter(|process| !self.processes_assigned_to_apps.contains(&process.data.pid))
+        self.all_processes().filter(|process| {
+            !self.processes_assigned_to_apps.contains(&process.data.pid)
+                && !process.data.containerization.is_container()
+        })
     }
 }
@@ -0,0 +1,400 @@
+use std::ops::Add;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use gtk::prelude::ToVariant;
+use gtk::{gio, glib};
+use process_data::{monotonic_millis, rooted};
+
+use super::settings::CpuUsageMode;
+
+/// The weight systemd and the kernel assign to cgroups by default
+pub const DEFAULT_WEIGHT: u64 = 100;
+
+/// Returns the directory of the cgroup at `cgroup_path` (relative to the
+/// cgroup v2 root, as found in `/proc/<pid>/cgroup`) in the cgroup filesystem
+pub fn cgroup_dir<S: AsRef<str>>(cgroup_path: S) -> PathBuf {
+    rooted("/sys/fs/cgroup").join(cgroup_path.as_ref().trim_start_matches('/'))
+}
+
+/// Freezes or thaws the cgroup at `cgroup_path`, which halts or continues
+/// all of its processes without them noticing. This is done through systemd
+/// if the cgroup is a unit and by writing `cgroup.freeze` otherwise.
+pub async fn set_frozen<S: AsRef<str>>(cgroup_path: S, frozen: bool) -> Result<()> {
+    let cgroup_path = cgroup_path.as_ref();
+
+    if let Err(err) = set_unit_frozen(cgroup_path, frozen).await {
+        log::debug!("Unable to freeze or thaw {cgroup_path} through systemd: {err}");
+        async_std::fs::write(
+            cgroup_dir(cgroup_path).join("cgroup.freeze"),
+            if frozen { "1" } else { "0" },
+        )
+        .await
+        .with_context(|| format!("unable to write cgroup.freeze of {cgroup_path}"))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the name of the systemd unit whose cgroup is at `cgroup_path`
+fn unit_name(cgroup_path: &str) -> Option<&str> {
+    cgroup_path
+        .rsplit('/')
+        .next()
+        .filter(|unit| unit.ends_with(".scope") || unit.ends_with(".service"))
+}
+
+/// Calls `method` of the systemd manager that is responsible for the unit at
+/// `cgroup_path`, units below `user@<uid>.service` belong to the user's
+/// instance
+async fn call_systemd_manager(
+    cgroup_path: &str,
+    method: &str,
+    parameters: glib::Variant,
+) -> Result<()> {
+    let bus_type = if cgroup_path
+        .split('/')
+        .any(|segment| segment.starts_with("user@"))
+    {
+        gio::BusType::Session
+    } else {
+        gio::BusType::System
+    };
+
+    gio::bus_get_future(bus_type)
+        .await?
+        .call_future(
+            Some("org.freedesktop.systemd1"),
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+            method,
+            Some(&parameters),
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Calls `FreezeUnit` or `ThawUnit` for the unit at `cgroup_path`
+async fn set_unit_frozen(cgroup_path: &str, frozen: bool) -> Result<()> {
+    let unit = unit_name(cgroup_path).context("cgroup is not a systemd unit")?;
+    call_systemd_manager(
+        cgroup_path,
+        if frozen { "FreezeUnit" } else { "ThawUnit" },
+        (unit,).to_variant(),
+    )
+    .await
+}
+
+/// Sets the limits of the unit at `cgroup_path` that differ between
+/// `previous` and `limits` until it's stopped, the others are left alone
+pub async fn set_limits<S: AsRef<str>>(
+    cgroup_path: S,
+    previous: &CgroupLimits,
+    limits: &CgroupLimits,
+) -> Result<()> {
+    let cgroup_path = cgroup_path.as_ref();
+    let unit = unit_name(cgroup_path).context("cgroup is not a systemd unit")?;
+
+    // systemd uses the maximum value of an u64 for "infinity"
+    let mut properties = Vec::new();
+    if limits.cpu_weight != previous.cpu_weight {
+        properties.push(("CPUWeight", limits.cpu_weight.to_variant()));
+    }
+    if limits.memory_high != previous.memory_high {
+        properties.push((
+            "MemoryHigh",
+            limits.memory_high.unwrap_or(u64::MAX).to_variant(),
+        ));
+    }
+    if limits.memory_max != previous.memory_max {
+        properties.push((
+            "MemoryMax",
+            limits.memory_max.unwrap_or(u64::MAX).to_variant(),
+        ));
+    }
+    if limits.io_weight != previous.io_weight {
+        properties.push(("IOWeight", limits.io_weight.to_variant()));
+    }
+
+    if properties.is_empty() {
+        return Ok(());
+    }
+
+    call_systemd_manager(
+        cgroup_path,
+        "SetUnitProperties",
+        (unit, true, properties).to_variant(),
+    )
+    .await
+    .with_context(|| format!("unable to set limits of {unit}"))
+}
+
+/// The resource limits of a cgroup v2 that can be changed through systemd
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CgroupLimits {
+    /// `cpu.weight` from 1 to 10000
+    pub cpu_weight: u64,
+    /// `memory.high` in bytes, `None` if there is no limit
+    pub memory_high: Option<u64>,
+    /// `memory.max` in bytes, `None` if there is no limit
+    pub memory_max: Option<u64>,
+    /// The default weight of `io.weight` from 1 to 10000
+    pub io_weight: u64,
+}
+
+impl Default for CgroupLimits {
+    fn default() -> Self {
+        Self {
+            cpu_weight: DEFAULT_WEIGHT,
+            memory_high: None,
+            memory_max: None,
+            io_weight: DEFAULT_WEIGHT,
+        }
+    }
+}
+
+impl CgroupLimits {
+    /// Reads the limits currently in effect for the cgroup whose directory is
+    /// `dir`, limits whose controllers aren't enabled are left at their
+    /// defaults
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Self {
+        let dir = dir.as_ref();
+        let read = |file: &str| std::fs::read_to_string(dir.join(file)).ok();
+        let default = Self::default();
+
+        Self {
+            cpu_weight: read("cpu.weight")
+                .and_then(|weight| CgroupStats::parse_single_value(weight).ok())
+                .unwrap_or(default.cpu_weight),
+            memory_high: read("memory.high").and_then(Self::parse_memory_limit),
+            memory_max: read("memory.max").and_then(Self::parse_memory_limit),
+            io_weight: read("io.weight")
+                .and_then(Self::parse_io_weight)
+                .unwrap_or(default.io_weight),
+        }
+    }
+
+    /// Reads the limits of all cgroups at `cgroup_paths` and combines them
+    /// into the limits of the whole, i. e. the lowest weights and the
+    /// strictest memory limits. `None` if `cgroup_paths` is empty.
+    pub fn of_all<S: AsRef<str>>(cgroup_paths: &[S]) -> Option<Self> {
+        cgroup_paths
+            .iter()
+            .map(|cgroup_path| Self::from_dir(cgroup_dir(cgroup_path)))
+            .reduce(|a, b| Self {
+                cpu_weight: a.cpu_weight.min(b.cpu_weight),
+                memory_high: Self::strictest(a.memory_high, b.memory_high),
+                memory_max: Self::strictest(a.memory_max, b.memory_max),
+                io_weight: a.io_weight.min(b.io_weight),
+            })
+    }
+
+    fn strictest(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Parses `memory.high` or `memory.max`, returns `None` if there is no
+    /// limit
+    #[must_use]
+    pub fn parse_memory_limit<S: AsRef<str>>(limit: S) -> Option<u64> {
+        limit.as_ref().trim().parse().ok()
+    }
+
+    /// Returns the default weight from the contents of an `io.weight` file,
+    /// weights for specific devices are ignored
+    #[must_use]
+    pub fn parse_io_weight<S: AsRef<str>>(io_weight: S) -> Option<u64> {
+        io_weight
+            .as_ref()
+            .lines()
+            .find_map(|line| line.strip_prefix("default "))?
+            .trim()
+            .parse()
+            .ok()
+    }
+}
+
+/// The usage statistics the kernel accounts for a cgroup v2, these include
+/// processes that have already exited
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CgroupStats {
+    /// `memory.current` in bytes
+    pub memory_current: u64,
+    /// `memory.swap.current` in bytes, `None` if swap isn't accounted
+    pub memory_swap_current: Option<u64>,
+    /// `usage_usec` of `cpu.stat` in microseconds
+    pub cpu_usage_usec: u64,
+    /// `rbytes` of `io.stat` summed over all devices, `None` if the I/O
+    /// controller isn't enabled for the cgroup
+    pub read_bytes: Option<u64>,
+    /// `wbytes` of `io.stat` summed over all devices, `None` if the I/O
+    /// controller isn't enabled for the cgroup
+    pub write_bytes: Option<u64>,
+    /// Whether the cgroup is frozen according to `cgroup.events`
+    pub frozen: bool,
+    /// The time of the monotonic clock in milliseconds at which the statistics
+    /// were read, see `process_data::monotonic_millis`
+    pub timestamp: u64,
+}
+
+impl Add for CgroupStats {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let add_options = |a: Option<u64>, b: Option<u64>| a.zip(b).map(|(a, b)| a + b);
+
+        Self {
+            memory_current: self.memory_current + rhs.memory_current,
+            memory_swap_current: add_options(self.memory_swap_current, rhs.memory_swap_current),
+            cpu_usage_usec: self.cpu_usage_usec + rhs.cpu_usage_usec,
+            read_bytes: add_options(self.read_bytes, rhs.read_bytes),
+            write_bytes: add_options(self.write_bytes, rhs.write_bytes),
+            frozen: self.frozen && rhs.frozen,
+            timestamp: self.timestamp.max(rhs.timestamp),
+        }
+    }
+}
+
+impl CgroupStats {
+    /// Reads the statistics of the cgroup whose directory is `dir`. Fails if
+    /// the memory controller isn't enabled for the cgroup.
+    pub async fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+
+        let memory_current = Self::parse_single_value(
+            async_std::fs::read_to_string(dir.join("memory.current"))
+                .await
+                .context("unable to read memory.current")?,
+        )?;
+
+        let memory_swap_current = async_std::fs::read_to_string(dir.join("memory.swap.current"))
+            .await
+            .ok()
+            .and_then(|swap| Self::parse_single_value(swap).ok());
+
+        let cpu_usage_usec = Self::parse_cpu_stat(
+            async_std::fs::read_to_string(dir.join("cpu.stat"))
+                .await
+                .context("unable to read cpu.stat")?,
+        )?;
+
+        let (read_bytes, write_bytes) = async_std::fs::read_to_string(dir.join("io.stat"))
+            .await
+            .map(Self::parse_io_stat)
+            .map_or((None, None), |(read, write)| (Some(read), Some(write)));
+
+        let frozen = async_std::fs::read_to_string(dir.join("cgroup.events"))
+            .await
+            .map(Self::parse_frozen)
+            .unwrap_or_default();
+
+        let timestamp = monotonic_millis();
+
+        Ok(Self {
+            memory_current,
+            memory_swap_current,
+            cpu_usage_usec,
+            read_bytes,
+            write_bytes,
+            frozen,
+            timestamp,
+        })
+    }
+
+    /// Parses files like `memory.current` that only contain a single number
+    pub fn parse_single_value<S: AsRef<str>>(value: S) -> Result<u64> {
+        value
+            .as_ref()
+            .trim()
+            .parse()
+            .context("unable to parse cgroup value")
+    }
+
+    /// Returns `usage_usec` from the contents of a `cpu.stat` file
+    pub fn parse_cpu_stat<S: AsRef<str>>(cpu_stat: S) -> Result<u64> {
+        cpu_stat
+            .as_ref()
+            .lines()
+            .find_map(|line| line.strip_prefix("usage_usec "))
+            .context("no usage_usec in cpu.stat")?
+            .trim()
+            .parse()
+            .context("unable to parse usage_usec")
+    }
+
+    /// Returns the read and written bytes from the contents of an `io.stat`
+    /// file, summed over all devices
+    pub fn parse_io_stat<S: AsRef<str>>(io_stat: S) -> (u64, u64) {
+        io_stat
+            .as_ref()
+            .lines()
+            .flat_map(|line| line.split_whitespace().skip(1))
+            .filter_map(|key_value| key_value.split_once('='))
+            .fold((0, 0), |(read, write), (key, value)| {
+                let value = value.parse::<u64>().unwrap_or_default();
+                match key {
+                    "rbytes" => (read + value, write),
+                    "wbytes" => (read, write + value),
+                    _ => (read, write),
+                }
+            })
+    }
+
+    /// Returns whether the contents of a `cgroup.events` file report the
+    /// cgroup as frozen
+    #[must_use]
+    pub fn parse_frozen<S: AsRef<str>>(cgroup_events: S) -> bool {
+        cgroup_events
+            .as_ref()
+            .lines()
+            .find_map(|line| line.strip_prefix("frozen "))
+            .is_some_and(|frozen| frozen.trim() == "1")
+    }
+
+    /// The ratio of CPU time used between `last` and `self`, in the same unit
+    /// as `Process::cpu_time_ratio`
+    #[must_use]
+    pub fn cpu_time_ratio(&self, last: &Self, mode: CpuUsageMode, logical_cpus: usize) -> f32 {
+        let busy_secs =
+            self.cpu_usage_usec.saturating_sub(last.cpu_usage_usec) as f64 / 1_000_000.0;
+        let elapsed_secs = self.timestamp.saturating_sub(last.timestamp) as f64 / 1000.0;
+        mode.ratio(busy_secs, elapsed_secs, logical_cpus)
+    }
+
+    /// The read speed in bytes per second between `last` and `self`
+    #[must_use]
+    pub fn read_speed(&self, last: &Self) -> Option<f64> {
+        Self::speed(
+            self.read_bytes?,
+            last.read_bytes?,
+            self.timestamp,
+            last.timestamp,
+        )
+    }
+
+    /// The write speed in bytes per second between `last` and `self`
+    #[must_use]
+    pub fn write_speed(&self, last: &Self) -> Option<f64> {
+        Self::speed(
+            self.write_bytes?,
+            last.write_bytes?,
+            self.timestamp,
+            last.timestamp,
+        )
+    }
+
+    fn speed(bytes: u64, bytes_last: u64, timestamp: u64, timestamp_last: u64) -> Option<f64> {
+        let delta_ms = timestamp.saturating_sub(timestamp_last);
+        if delta_ms == 0 {
+            return Some(0.0);
+        }
+        Some(bytes.saturating_sub(bytes_last) as f64 / delta_ms as f64 * 1000.0)
+    }
+}
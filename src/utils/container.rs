@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use process_data::{rooted, Containerization};
+
+/// Container IDs are shortened to this many characters for display, the same
+/// way the engines' CLIs do it
+const SHORT_ID_LENGTH: usize = 12;
+
+/// The directories Docker keeps its `containers` directory in, for both
+/// rootful and rootless installations
+fn docker_data_dirs() -> Vec<PathBuf> {
+    let mut data_dirs = vec![rooted("/var/lib/docker")];
+    if let Some(data_home) = data_home() {
+        data_dirs.push(data_home.join("docker"));
+    }
+    data_dirs
+}
+
+/// The directories Podman keeps its `storage` directory in, for both rootful
+/// and rootless installations
+fn podman_data_dirs() -> Vec<PathBuf> {
+    let mut data_dirs = vec![rooted("/var/lib/containers")];
+    if let Some(data_home) = data_home() {
+        data_dirs.push(data_home.join("containers"));
+    }
+    data_dirs
+}
+
+fn data_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .map(rooted)
+}
+
+/// Parses the name of a Docker container from its `config.v2.json`.
+#[must_use]
+pub fn parse_docker_name<S: AsRef<str>>(config: S) -> Option<String> {
+    let config: serde_json::Value = serde_json::from_str(config.as_ref()).ok()?;
+    config
+        .get("Name")?
+        .as_str()
+        .map(|name| name.trim_start_matches('/').to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Parses the name of the Podman container with the ID `id` from Podman's
+/// `containers.json`.
+#[must_use]
+pub fn parse_podman_name<S: AsRef<str>>(containers: S, id: &str) -> Option<String> {
+    let containers: serde_json::Value = serde_json::from_str(containers.as_ref()).ok()?;
+    containers
+        .as_array()?
+        .iter()
+        .find(|container| container.get("id").and_then(|value| value.as_str()) == Some(id))?
+        .get("names")?
+        .as_array()?
+        .first()?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Looks up the name of a container in the metadata its engine keeps on disk.
+/// Returns `None` if there is no such metadata or if it's not accessible, e. g.
+/// because only root is allowed to read it.
+#[must_use]
+pub fn container_name(containerization: &Containerization) -> Option<String> {
+    match containerization {
+        Containerization::Docker(id) => docker_data_dirs().into_iter().find_map(|data_dir| {
+            std::fs::read_to_string(data_dir.join("containers").join(id).join("config.v2.json"))
+                .ok()
+                .and_then(parse_docker_name)
+        }),
+        Containerization::Podman(id) => podman_data_dirs().into_iter().find_map(|data_dir| {
+            std::fs::read_to_string(data_dir.join("storage/overlay-containers/containers.json"))
+                .ok()
+                .and_then(|containers| parse_podman_name(containers, id))
+        }),
+        // LXC and systemd-nspawn containers are already named in their cgroups
+        Containerization::Lxc(name) | Containerization::Nspawn(name) => Some(name.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the identifier of an app or container, with container IDs being
+/// shortened for display.
+#[must_use]
+pub fn short_identifier(containerization: &Containerization) -> Option<&str> {
+    match containerization {
+        Containerization::Docker(id)
+        | Containerization::Podman(id)
+        | Containerization::Containerd(id) => Some(id.get(..SHORT_ID_LENGTH).unwrap_or(id)),
+        _ => containerization.identifier(),
+    }
+}
@@ -4,6 +4,7 @@ use async_std::path::PathBuf;
 use glob::glob;
 use nparse::KVStrToJson;
 use once_cell::sync::Lazy;
+use process_data::rooted;
 use regex::bytes::Regex;
 use serde_json::Value;
 use std::sync::OnceLock;
@@ -98,9 +99,9 @@ pub async fn cpu_info() -> Result<CPUInfo> {
 /// Will return `Err` if the are problems during reading or parsing
 /// of the corresponding file in sysfs
 pub fn get_cpu_freq(core: usize) -> Result<u64> {
-    std::fs::read_to_string(format!(
+    std::fs::read_to_string(rooted(format!(
         "/sys/devices/system/cpu/cpu{core}/cpufreq/scaling_cur_freq"
-    ))
+    )))
     .with_context(|| format!("unable to read scaling_cur_freq for core {core}"))?
     .replace('\n', "")
     .parse::<u64>()
@@ -108,7 +109,12 @@ pub fn get_cpu_freq(core: usize) -> Result<u64> {
     .map(|x| x * 1000)
 }
 
-fn parse_proc_stat_line(line: &[u8]) -> Result<(u64, u64)> {
+/// Parses a `cpu` line of /proc/stat into a tuple of `(idle_time, total_time)`
+///
+/// # Errors
+///
+/// Will return `Err` if the line is not a valid `cpu` line
+pub fn parse_proc_stat_line(line: &[u8]) -> Result<(u64, u64)> {
     let captures = PROC_STAT_REGEX
         .captures(line)
         .ok_or_else(|| anyhow!("using regex to parse /proc/stat failed"))?;
@@ -131,13 +137,17 @@ fn parse_proc_stat_line(line: &[u8]) -> Result<(u64, u64)> {
     Ok((idle_time, sum))
 }
 
-async fn get_proc_stat(core: Option<usize>) -> Result<String> {
+/// Returns the `cpu` line of the contents of /proc/stat that belongs to either
+/// all cores combined (if `core` is `None`) or to the given `core`
+///
+/// # Errors
+///
+/// Will return `Err` if `core` is greater than the amount of cores found
+/// in `proc_stat_raw`
+pub fn select_proc_stat_line(proc_stat_raw: &str, core: Option<usize>) -> Result<String> {
     // the combined stats are in line 0, the other cores are in the following lines,
     // since our `core` argument starts with 0, we must add 1 to it if it's not `None`.
     let selected_line_number = core.map_or(0, |x| x + 1);
-    let proc_stat_raw = async_std::fs::read_to_string("/proc/stat")
-        .await
-        .with_context(|| "unable to read /proc/stat")?;
     let mut proc_stat = proc_stat_raw.split('\n').collect::<Vec<&str>>();
     proc_stat.retain(|x| x.starts_with("cpu"));
     // return an `Error` if `core` is greater than the number of cores
@@ -147,6 +157,13 @@ async fn get_proc_stat(core: Option<usize>) -> Result<String> {
     Ok(proc_stat[selected_line_number].to_string())
 }
 
+async fn get_proc_stat(core: Option<usize>) -> Result<String> {
+    let proc_stat_raw = async_std::fs::read_to_string(rooted("/proc/stat"))
+        .await
+        .with_context(|| "unable to read /proc/stat")?;
+    select_proc_stat_line(&proc_stat_raw, core)
+}
+
 /// Returns the CPU usage of either all cores combined (if supplied argument is `None`),
 /// or of a specific thread (taken from the supplied argument starting at 0)
 /// Please keep in mind that this is the total CPU time since boot, you have to do delta
@@ -172,7 +189,7 @@ pub async fn get_temperature() -> Result<f32> {
         && X86_PKG_TEMP.get().is_none()
     {
         // collect all the known hwmons
-        for path in (glob("/sys/class/hwmon/hwmon*")?).flatten() {
+        for path in (glob(&rooted("/sys/class/hwmon/hwmon*").to_string_lossy())?).flatten() {
             match async_std::fs::read_to_string(path.join("name"))
                 .await
                 .as_deref()
@@ -187,7 +204,8 @@ pub async fn get_temperature() -> Result<f32> {
         }
 
         // collect all the known thermal zones
-        for path in (glob("/sys/class/thermal/thermal_zone*")?).flatten() {
+        for path in (glob(&rooted("/sys/class/thermal/thermal_zone*").to_string_lossy())?).flatten()
+        {
             match async_std::fs::read_to_string(path.join("type"))
                 .await
                 .as_deref()
@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use async_std::stream::StreamExt;
 use gtk::gio::{Icon, ThemedIcon};
 use once_cell::sync::Lazy;
+use process_data::rooted;
 use regex::Regex;
 use std::{
     collections::HashMap,
@@ -84,7 +85,7 @@ impl Drive {
     /// reading or parsing
     pub async fn get_sysfs_paths() -> Result<Vec<PathBuf>> {
         let mut list = Vec::new();
-        let mut entries = async_std::fs::read_dir("/sys/block").await?;
+        let mut entries = async_std::fs::read_dir(rooted("/sys/block")).await?;
         while let Some(entry) = entries.next().await {
             let entry = entry?;
             let block_device = entry.file_name().to_string_lossy().to_string();
@@ -1,19 +1,23 @@
 use anyhow::{anyhow, bail, Context, Result};
 use nvml_wrapper::{
     enum_wrappers::device::{Clock, TemperatureSensor},
+    enums::device::UsedGpuMemory,
     error::NvmlError,
+    struct_wrappers::device::ProcessUtilizationSample,
     Nvml,
 };
 use once_cell::sync::Lazy;
 
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     convert::TryInto,
     path::{Path, PathBuf},
 };
 
 use glob::glob;
+use hashbrown::HashMap;
 use pci_ids::Device;
+use process_data::rooted;
 
 use crate::i18n::i18n;
 
@@ -23,6 +27,19 @@ const VID_NVIDIA: u16 = 4318;
 
 static NVML: Lazy<Result<Nvml, NvmlError>> = Lazy::new(Nvml::init);
 
+/// The usage of an NVIDIA GPU by a single process as reported by NVML
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct NvidiaProcessUsage {
+    /// The utilization of the streaming multiprocessors in percent
+    pub sm: u32,
+    /// The utilization of the video encoder in percent
+    pub encoder: u32,
+    /// The utilization of the video decoder in percent
+    pub decoder: u32,
+    /// The video memory used by the process in bytes
+    pub memory: u64,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct GPU {
     pub device: Option<&'static Device>,
@@ -41,7 +58,7 @@ impl GPU {
     /// the GPUs in the system
     pub async fn get_gpus() -> Result<Vec<GPU>> {
         let mut gpu_vec: Vec<GPU> = Vec::new();
-        for entry in glob("/sys/class/drm/card?")?.flatten() {
+        for entry in glob(&rooted("/sys/class/drm/card?").to_string_lossy())?.flatten() {
             let sysfs_device_path = entry.join("device");
             let mut uevent_contents: HashMap<String, String> = HashMap::new();
             let uevent_raw =
@@ -547,3 +564,99 @@ impl GPU {
         bail!("no device")
     }
 }
+
+/// Returns the usage of all NVIDIA GPUs by the processes running on them,
+/// keyed by PID and then by the PCI slot of the GPU. Only utilization samples
+/// newer than the ones in `last_seen_timestamps` are taken into account, the
+/// timestamps are updated accordingly.
+///
+/// # Errors
+///
+/// Will return `Err` if there's no NVML connection
+pub fn nvidia_process_usages(
+    last_seen_timestamps: &mut HashMap<String, u64>,
+) -> Result<HashMap<i32, BTreeMap<String, NvidiaProcessUsage>>> {
+    let nvml = NVML
+        .as_ref()
+        .map_err(|error| anyhow!("no NVML connection: {error}"))?;
+
+    let mut usages: HashMap<i32, BTreeMap<String, NvidiaProcessUsage>> = HashMap::new();
+
+    for index in 0..nvml.device_count()? {
+        let Ok(device) = nvml.device_by_index(index) else {
+            continue;
+        };
+
+        let Ok(pci_info) = device.pci_info() else {
+            continue;
+        };
+
+        let pci_slot = normalize_pci_bus_id(&pci_info.bus_id);
+
+        let processes = device
+            .running_compute_processes()
+            .unwrap_or_default()
+            .into_iter()
+            .chain(device.running_graphics_processes().unwrap_or_default());
+
+        for process in processes {
+            let memory = match process.used_gpu_memory {
+                UsedGpuMemory::Used(bytes) => bytes,
+                UsedGpuMemory::Unavailable => 0,
+            };
+
+            let usage = usages
+                .entry(process.pid as i32)
+                .or_default()
+                .entry(pci_slot.clone())
+                .or_default();
+            // processes using both compute and graphics are listed twice
+            usage.memory = usage.memory.max(memory);
+        }
+
+        // NVML returns an error if there are no samples newer than the last
+        // seen one, i. e. if the GPU has been idle
+        let samples = device
+            .process_utilization_stats(last_seen_timestamps.get(&pci_slot).copied())
+            .unwrap_or_default();
+
+        if let Some(newest_timestamp) = samples.iter().map(|sample| sample.timestamp).max() {
+            last_seen_timestamps.insert(pci_slot.clone(), newest_timestamp);
+        }
+
+        // there may be multiple samples per process, only use the newest one
+        let mut newest_samples: HashMap<u32, &ProcessUtilizationSample> = HashMap::new();
+        for sample in &samples {
+            let newest = newest_samples.entry(sample.pid).or_insert(sample);
+            if sample.timestamp > newest.timestamp {
+                *newest = sample;
+            }
+        }
+
+        for sample in newest_samples.into_values() {
+            let usage = usages
+                .entry(sample.pid as i32)
+                .or_default()
+                .entry(pci_slot.clone())
+                .or_default();
+            usage.sm = sample.sm_util;
+            usage.encoder = sample.enc_util;
+            usage.decoder = sample.dec_util;
+        }
+    }
+
+    Ok(usages)
+}
+
+/// Converts a PCI bus ID as reported by NVML (e. g. `00000000:01:00.0`) into
+/// the form used by sysfs and DRM (e. g. `0000:01:00.0`)
+#[must_use]
+pub fn normalize_pci_bus_id<S: AsRef<str>>(bus_id: S) -> String {
+    let bus_id = bus_id.as_ref().trim().to_lowercase();
+    match bus_id.split_once(':') {
+        Some((domain, rest)) if domain.len() > 4 => {
+            format!("{}:{rest}", &domain[domain.len() - 4..])
+        }
+        _ => bus_id,
+    }
+}
@@ -3,6 +3,7 @@ use std::process::Command;
 use anyhow::{bail, Context, Result};
 use nparse::KVStrToJson;
 use once_cell::sync::Lazy;
+use process_data::rooted;
 use regex::Regex;
 use serde_json::Value;
 
@@ -13,13 +14,25 @@ static RE_FORMFACTOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"Form Factor: (.+)"
 static RE_TYPE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Type: (.+)").unwrap());
 static RE_TYPE_DETAIL: Lazy<Regex> = Lazy::new(|| Regex::new(r"Type Detail: (.+)").unwrap());
 
-fn proc_meminfo() -> Result<Value, anyhow::Error> {
-    std::fs::read_to_string("/proc/meminfo")
-        .with_context(|| "unable to read /proc/meminfo")?
+/// Parses the contents of /proc/meminfo into a JSON `Value`
+///
+/// # Errors
+///
+/// Will return `Err` if `meminfo` is not in the expected format
+pub fn parse_meminfo<S: AsRef<str>>(meminfo: S) -> Result<Value, anyhow::Error> {
+    meminfo
+        .as_ref()
         .kv_str_to_json()
         .map_err(anyhow::Error::msg)
 }
 
+fn proc_meminfo() -> Result<Value, anyhow::Error> {
+    parse_meminfo(
+        std::fs::read_to_string(rooted("/proc/meminfo"))
+            .with_context(|| "unable to read /proc/meminfo")?,
+    )
+}
+
 pub fn get_total_memory() -> Option<usize> {
     proc_meminfo().ok()?["MemTotal"]
         .as_str()
@@ -64,7 +77,8 @@ pub struct MemoryDevice {
     pub installed: bool,
 }
 
-fn parse_dmidecode(dmi: &str) -> Vec<MemoryDevice> {
+/// Parses the output of `dmidecode -t 17 -q` into a `Vec` of `MemoryDevice`s
+pub fn parse_dmidecode(dmi: &str) -> Vec<MemoryDevice> {
     let mut devices = Vec::new();
 
     let device_strings = dmi.split("\n\n");
@@ -3,12 +3,16 @@ use ini::Ini;
 use once_cell::sync::Lazy;
 
 pub mod app;
+pub mod cgroup;
+pub mod container;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
 pub mod memory;
 pub mod network;
+pub mod pressure;
 pub mod process;
+pub mod search;
 pub mod settings;
 pub mod units;
 
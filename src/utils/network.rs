@@ -8,6 +8,7 @@ use anyhow::{Context, Result};
 use futures_util::StreamExt;
 use gtk::gio::{Icon, ThemedIcon};
 use pci_ids::FromId;
+use process_data::rooted;
 
 #[derive(Debug, Clone, Copy, Default)]
 pub enum InterfaceType {
@@ -53,7 +54,7 @@ impl PartialEq for NetworkInterface {
 impl NetworkInterface {
     pub async fn get_sysfs_paths() -> Result<Vec<PathBuf>> {
         let mut list = Vec::new();
-        let mut entries = async_std::fs::read_dir("/sys/class/net").await?;
+        let mut entries = async_std::fs::read_dir(rooted("/sys/class/net")).await?;
         while let Some(entry) = entries.next().await {
             let entry = entry?;
             let block_device = entry.file_name().to_string_lossy().to_string();
@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use process_data::rooted;
+
+use super::cgroup;
+
+/// The resources the kernel reports Pressure Stall Information for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureResource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl PressureResource {
+    fn name(self) -> &'static str {
+        match self {
+            PressureResource::Cpu => "cpu",
+            PressureResource::Memory => "memory",
+            PressureResource::Io => "io",
+        }
+    }
+}
+
+/// The share of time in percent that tasks were stalled on a resource,
+/// averaged over the last 10, 60 and 300 seconds
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PressureAverages {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    /// Total stall time in microseconds
+    pub total: u64,
+}
+
+impl PressureAverages {
+    fn parse(values: &str) -> Result<Self> {
+        let mut averages = Self::default();
+        for (key, value) in values
+            .split_whitespace()
+            .filter_map(|key_value| key_value.split_once('='))
+        {
+            match key {
+                "avg10" => averages.avg10 = value.parse()?,
+                "avg60" => averages.avg60 = value.parse()?,
+                "avg300" => averages.avg300 = value.parse()?,
+                "total" => averages.total = value.parse()?,
+                _ => (),
+            }
+        }
+        Ok(averages)
+    }
+
+    /// Returns the element-wise maximum of `self` and `other`
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            avg10: self.avg10.max(other.avg10),
+            avg60: self.avg60.max(other.avg60),
+            avg300: self.avg300.max(other.avg300),
+            total: self.total.max(other.total),
+        }
+    }
+}
+
+/// Pressure Stall Information of a resource. `some` is the share of time in
+/// which at least one task was stalled, `full` the share in which all
+/// non-idle tasks were stalled at the same time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Pressure {
+    pub some: PressureAverages,
+    /// `None` on kernels that don't report it for this resource
+    pub full: Option<PressureAverages>,
+}
+
+impl Pressure {
+    /// Parses the contents of a `/proc/pressure/<resource>` or a
+    /// `<resource>.pressure` file of a cgroup
+    pub fn parse<S: AsRef<str>>(pressure: S) -> Result<Self> {
+        let pressure = pressure.as_ref();
+
+        let some = pressure
+            .lines()
+            .find_map(|line| line.strip_prefix("some "))
+            .context("no some line in pressure file")?;
+
+        let full = pressure
+            .lines()
+            .find_map(|line| line.strip_prefix("full "))
+            .map(PressureAverages::parse)
+            .transpose()?;
+
+        Ok(Self {
+            some: PressureAverages::parse(some)?,
+            full,
+        })
+    }
+
+    /// Reads the pressure of `resource` on the whole system
+    pub fn system(resource: PressureResource) -> Result<Self> {
+        Self::parse(
+            std::fs::read_to_string(rooted("/proc/pressure").join(resource.name()))
+                .with_context(|| format!("unable to read {} pressure", resource.name()))?,
+        )
+    }
+
+    /// Reads the pressure of `resource` inside of the cgroup at `cgroup_path`
+    pub async fn of_cgroup<S: AsRef<str>>(
+        cgroup_path: S,
+        resource: PressureResource,
+    ) -> Result<Self> {
+        Self::parse(
+            async_std::fs::read_to_string(
+                cgroup::cgroup_dir(cgroup_path).join(format!("{}.pressure", resource.name())),
+            )
+            .await
+            .with_context(|| format!("unable to read {} pressure of cgroup", resource.name()))?,
+        )
+    }
+
+    /// Returns the element-wise maximum of `self` and `other`
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            some: self.some.max(other.some),
+            full: match (self.full, other.full) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
+/// The pressure of all resources inside of a cgroup
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CgroupPressure {
+    pub cpu: Pressure,
+    pub memory: Pressure,
+    pub io: Pressure,
+}
+
+impl CgroupPressure {
+    pub async fn read<S: AsRef<str>>(cgroup_path: S) -> Result<Self> {
+        let cgroup_path = cgroup_path.as_ref();
+        Ok(Self {
+            cpu: Pressure::of_cgroup(cgroup_path, PressureResource::Cpu).await?,
+            memory: Pressure::of_cgroup(cgroup_path, PressureResource::Memory).await?,
+            io: Pressure::of_cgroup(cgroup_path, PressureResource::Io).await?,
+        })
+    }
+
+    /// Returns the element-wise maximum of `self` and `other`, pressure can't
+    /// be summed up across cgroups
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            cpu: self.cpu.max(other.cpu),
+            memory: self.memory.max(other.memory),
+            io: self.io.max(other.io),
+        }
+    }
+
+    /// The highest `some avg10` value of all resources in percent
+    #[must_use]
+    pub fn highest_avg10(&self) -> f64 {
+        self.cpu
+            .some
+            .avg10
+            .max(self.memory.some.avg10)
+            .max(self.io.some.avg10)
+    }
+}
@@ -1,18 +1,168 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use config::LIBEXECDIR;
-use glob::glob;
-use process_data::{Containerization, ProcessData};
-use std::process::Command;
+use nix::libc;
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+use once_cell::sync::Lazy;
+use process_data::{
+    format_cpu_list,
+    gpu_usage::GpuUsageStats,
+    ipc::{self, Request},
+    open_files::OpenFile,
+    rooted, Containerization, IoPriority, IoPriorityClass, MemoryDetails, ProcessData,
+    ProcessScanner, ScanOptions, ThreadData, IDENTITY_CHANGED_STATUS_CODE,
+};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    process::{Command, Stdio},
+    time::Duration,
+};
 
-use async_std::sync::Arc;
-use async_std::sync::Mutex;
-use futures_util::future::join_all;
 use gtk::gio::{Icon, ThemedIcon};
 
 use crate::config;
+use crate::i18n::{i18n, i18n_f};
 
+use super::container;
+use super::gpu::NvidiaProcessUsage;
+use super::settings::{CpuUsageMode, MemoryMetric};
 use super::{FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK};
 
+/// Clock ticks per second, the unit of times in `/proc/<pid>/stat`
+pub static CLK_TCK: Lazy<u64> =
+    Lazy::new(|| u64::try_from(unsafe { libc::sysconf(libc::_SC_CLK_TCK) }).unwrap_or(100));
+
+/// The number of CPUs that are currently online
+pub static LOGICAL_CPUS: Lazy<usize> = Lazy::new(|| {
+    usize::try_from(unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) }).unwrap_or(1)
+});
+
+/// The time the system has been booted at in seconds since the Unix epoch
+static BOOT_TIME: Lazy<Option<u64>> = Lazy::new(|| {
+    std::fs::read_to_string(rooted("/proc/stat"))
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("btime"))?
+        .trim()
+        .parse()
+        .ok()
+});
+
+/// The error returned by [`Process::execute_process_action`] if the process
+/// has ended in the meantime and its PID has been reused by another process.
+/// In this case, nothing has been done to the other process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessIdentityChanged(pub i32);
+
+impl Display for ProcessIdentityChanged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "process {} has ended and its PID has been reused by another process",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ProcessIdentityChanged {}
+
+/// The `resources-processes` helper running persistently on the host, used
+/// to gather processes when we're running inside of Flatpak
+struct ProcessesHelper {
+    child: async_process::Child,
+    stdin: async_process::ChildStdin,
+    stdout: async_process::ChildStdout,
+}
+
+impl Drop for ProcessesHelper {
+    /// Killing `flatpak-spawn` would leave the helper running on the host,
+    /// so it's sent `SIGTERM` instead, which `flatpak-spawn` forwards to the
+    /// helper before exiting along with it
+    fn drop(&mut self) {
+        if let Ok(pid) = i32::try_from(self.child.id()) {
+            let _ = kill(Pid::from_raw(pid), Signal::SIGTERM);
+        }
+    }
+}
+
+/// How long the helper may take to answer a request before it's considered
+/// hung and restarted
+const PROCESSES_HELPER_TIMEOUT: Duration = Duration::from_secs(10);
+
+static PROCESSES_HELPER: Lazy<async_std::sync::Mutex<Option<ProcessesHelper>>> =
+    Lazy::new(|| async_std::sync::Mutex::new(None));
+
+/// Used instead of the helper when not running as a Flatpak
+static PROCESS_SCANNER: Lazy<async_std::sync::Mutex<ProcessScanner>> =
+    Lazy::new(|| async_std::sync::Mutex::new(ProcessScanner::new()));
+
+impl ProcessesHelper {
+    fn spawn() -> Result<Self> {
+        let proxy_path = format!(
+            "{}/libexec/resources/resources-processes",
+            FLATPAK_APP_PATH.as_str()
+        );
+
+        // --watch-bus makes sure the helper doesn't outlive us, replaced
+        // helpers are terminated when they're dropped
+        let mut child = async_process::Command::new(FLATPAK_SPAWN)
+            .args(["--host", "--watch-bus", proxy_path.as_str(), "--persistent"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .context("unable to spawn resources-processes")?;
+
+        Ok(Self {
+            stdin: child
+                .stdin
+                .take()
+                .context("no stdin of resources-processes")?,
+            stdout: child
+                .stdout
+                .take()
+                .context("no stdout of resources-processes")?,
+            child,
+        })
+    }
+
+    async fn request<T: DeserializeOwned>(&mut self, request: &Request) -> Result<T> {
+        async_std::future::timeout(PROCESSES_HELPER_TIMEOUT, async {
+            ipc::write_message_async(&mut self.stdin, request).await?;
+            ipc::read_message_async(&mut self.stdout).await
+        })
+        .await
+        .context("resources-processes didn't answer in time")?
+    }
+
+    /// Sends `request` to the helper and returns its answer. The helper is
+    /// started if it isn't running yet and restarted if it has died, hung or
+    /// misbehaved.
+    async fn send<T: DeserializeOwned>(request: Request) -> Result<T> {
+        let mut helper = PROCESSES_HELPER.lock().await;
+
+        if let Some(running_helper) = helper.as_mut() {
+            match running_helper.request(&request).await {
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    log::warn!("resources-processes failed, restarting it: {error:#}");
+                }
+            }
+        }
+
+        // the stream might be out of sync after an error, so always start
+        // over with a new helper
+        *helper = None;
+        let mut new_helper = Self::spawn()?;
+        let response = new_helper.request(&request).await;
+        if response.is_ok() {
+            *helper = Some(new_helper);
+        }
+        response
+    }
+}
+
 /// Represents a process that can be found within procfs.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Process {
@@ -26,6 +176,26 @@ pub struct Process {
     pub read_bytes_last_timestamp: Option<u64>,
     pub write_bytes_last: Option<u64>,
     pub write_bytes_last_timestamp: Option<u64>,
+    pub threads_last: Vec<ThreadData>,
+    pub gpu_usage_stats_last: BTreeMap<String, GpuUsageStats>,
+    pub gpu_usage_stats_last_timestamp: u64,
+    /// The usage of NVIDIA GPUs as reported by NVML, keyed by the GPU's PCI
+    /// slot
+    pub nvidia_usages: BTreeMap<String, NvidiaProcessUsage>,
+}
+
+/// A change to the scheduling priorities of a process, see
+/// [`Process::adjust`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessAdjustment {
+    /// The nice value from -20 to 19
+    Nice(i8),
+    /// The scheduling policy as found in `/proc/<pid>/stat`, see
+    /// [`SCHEDULING_POLICIES`]
+    SchedulingPolicy(u32),
+    IoPriority(IoPriority),
+    /// The logical CPUs the process is allowed to run on
+    Affinity(Vec<usize>),
 }
 
 // TODO: Better name?
@@ -35,15 +205,50 @@ pub enum ProcessAction {
     STOP,
     KILL,
     CONT,
+    HUP,
+    INT,
+    QUIT,
+    USR1,
+    USR2,
+    /// The realtime signal `SIGRTMIN` plus the given offset
+    RT(u8),
+}
+
+impl ProcessAction {
+    /// Returns the name of the signal without its `SIG` prefix as understood
+    /// by `resources-kill`, e. g. `TERM` or `RTMIN+2`
+    #[must_use]
+    pub fn signal_name(&self) -> String {
+        match self {
+            ProcessAction::TERM => "TERM".to_string(),
+            ProcessAction::STOP => "STOP".to_string(),
+            ProcessAction::KILL => "KILL".to_string(),
+            ProcessAction::CONT => "CONT".to_string(),
+            ProcessAction::HUP => "HUP".to_string(),
+            ProcessAction::INT => "INT".to_string(),
+            ProcessAction::QUIT => "QUIT".to_string(),
+            ProcessAction::USR1 => "USR1".to_string(),
+            ProcessAction::USR2 => "USR2".to_string(),
+            ProcessAction::RT(offset) => format!("RTMIN+{offset}"),
+        }
+    }
+
+    /// Returns the highest offset that can be used for `ProcessAction::RT`
+    #[must_use]
+    pub fn max_realtime_offset() -> u8 {
+        (nix::libc::SIGRTMAX() - nix::libc::SIGRTMIN()) as u8
+    }
 }
 /// Convenience struct for displaying running processes
 #[derive(Debug, Clone)]
 pub struct ProcessItem {
     pub pid: i32,
+    pub parent_pid: i32,
     pub uid: u32,
     pub display_name: String,
     pub icon: Icon,
     pub memory_usage: usize,
+    pub memory_details: Option<MemoryDetails>,
     pub cpu_time_ratio: f32,
     pub commandline: String,
     pub containerization: Containerization,
@@ -52,50 +257,94 @@ pub struct ProcessItem {
     pub read_total: Option<u64>,
     pub write_speed: Option<f64>,
     pub write_total: Option<u64>,
+    pub thread_count: u32,
+    /// Empty unless the threads have been asked for, see
+    /// [`ScanOptions::thread_pids`]
+    pub threads: Vec<ThreadItem>,
+    pub nice: i8,
+    pub scheduling_policy: u32,
+    pub io_priority: Option<IoPriority>,
+    pub last_cpu: u32,
+    pub affinity: Option<Vec<usize>>,
+    pub state: char,
+    /// The time the process has been started at in seconds since the Unix
+    /// epoch
+    pub start_time: Option<u64>,
+    /// The usage of every GPU the process uses, keyed by the GPU's PCI slot
+    pub gpu_usages: BTreeMap<String, GpuUsage>,
+    /// The highest usage of all GPUs from 0.0 to 1.0
+    pub gpu_usage: f32,
+    /// The video memory used on all GPUs in bytes
+    pub gpu_memory: u64,
+}
+
+/// The usage of a single GPU by a process
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GpuUsage {
+    /// The utilization of the process' busiest engine from 0.0 to 1.0
+    pub usage: f32,
+    /// The video memory used by the process in bytes
+    pub memory: u64,
+    /// The utilization of the video encoder from 0.0 to 1.0, `None` if the
+    /// driver doesn't report it separately
+    pub encoder: Option<f32>,
+    /// The utilization of the video decoder from 0.0 to 1.0, `None` if the
+    /// driver doesn't report it separately
+    pub decoder: Option<f32>,
+}
+
+/// Convenience struct for displaying the threads of a process
+#[derive(Debug, Clone)]
+pub struct ThreadItem {
+    pub tid: i32,
+    pub name: String,
+    pub state: char,
+    pub cpu_time_ratio: f32,
+    pub last_cpu: u32,
 }
 
 impl Process {
-    /// Returns a `Vec` containing all currently running processes.
+    /// Returns a `Vec` containing all currently running processes, `options`
+    /// decides what is read about them in addition to their usage.
     ///
     /// # Errors
     ///
     /// Will return `Err` if there are problems traversing and
     /// parsing procfs
-    pub async fn all() -> Result<Vec<Self>> {
+    pub async fn all(options: &ScanOptions) -> Result<Vec<Self>> {
         if *IS_FLATPAK {
-            let proxy_path = format!(
-                "{}/libexec/resources/resources-processes",
-                FLATPAK_APP_PATH.as_str()
+            return Ok(
+                ProcessesHelper::send::<Vec<ProcessData>>(Request::Processes(options.clone()))
+                    .await?
+                    .drain(..)
+                    .map(Self::from_process_data)
+                    .collect(),
             );
-            let command = async_process::Command::new(FLATPAK_SPAWN)
-                .args(["--host", proxy_path.as_str()])
-                .output()
-                .await?;
-            let output = command.stdout;
-
-            return Ok(rmp_serde::from_slice::<Vec<ProcessData>>(&output)?
+        } else {
+            return Ok(PROCESS_SCANNER
+                .lock()
+                .await
+                .scan(options)
+                .await?
                 .drain(..)
                 .map(Self::from_process_data)
                 .collect());
-        } else {
-            let vec: Arc<Mutex<Vec<ProcessData>>> = Arc::new(Mutex::new(Vec::new()));
-
-            let mut handles = vec![];
-            for entry in glob("/proc/[0-9]*/").context("unable to glob")?.flatten() {
-                let vec = Arc::clone(&vec);
-
-                let handle = async_std::task::spawn(async move {
-                    if let Ok(process_data) = ProcessData::try_from_path(entry).await {
-                        vec.lock().await.push(process_data);
-                    }
-                });
-
-                handles.push(handle);
-            }
-            join_all(handles).await;
+        }
+    }
 
-            let mut vec = vec.lock().await;
-            return Ok(vec.drain(..).map(Self::from_process_data).collect());
+    /// Returns the open file descriptors of the process with the PID `pid`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the process does not exist anymore or if we're
+    /// not permitted to look at its file descriptors
+    pub async fn open_files(pid: i32) -> Result<Vec<OpenFile>> {
+        if *IS_FLATPAK {
+            ProcessesHelper::send::<Result<Vec<OpenFile>, String>>(Request::OpenFiles(pid))
+                .await?
+                .map_err(|error| anyhow!(error))
+        } else {
+            OpenFile::all_of(pid).await
         }
     }
 
@@ -136,122 +385,364 @@ impl Process {
             read_bytes_last_timestamp,
             write_bytes_last,
             write_bytes_last_timestamp,
+            threads_last: Vec::new(),
+            gpu_usage_stats_last: BTreeMap::new(),
+            gpu_usage_stats_last_timestamp: 0,
+            nvidia_usages: BTreeMap::new(),
         }
     }
 
+    /// Sends the signal of `action` to this process. `resources-kill` makes
+    /// sure that the PID still belongs to this process by comparing start
+    /// times and sends the signal through a pidfd.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the signal could not be sent. If the PID now
+    /// belongs to another process, the error will be a
+    /// [`ProcessIdentityChanged`].
     pub fn execute_process_action(&self, action: ProcessAction) -> Result<()> {
-        let action_str = match action {
-            ProcessAction::TERM => "TERM",
-            ProcessAction::STOP => "STOP",
-            ProcessAction::KILL => "KILL",
-            ProcessAction::CONT => "CONT",
+        Self::execute_process_action_batch([self], action)
+            .pop()
+            .map_or_else(
+                || Err(anyhow!("no result for {}", self.data.pid)),
+                |(_, result)| result,
+            )
+    }
+
+    /// Sends the signal of `action` to all of `processes` using a single call
+    /// of `resources-kill`. Processes we're not permitted to signal are then
+    /// retried in a second call with elevated privileges, so that the user is
+    /// asked for authorization at most once.
+    ///
+    /// Returns the result for each PID, see
+    /// [`Process::execute_process_action`] for the possible errors.
+    pub fn execute_process_action_batch<'a, I: IntoIterator<Item = &'a Process>>(
+        processes: I,
+        action: ProcessAction,
+    ) -> Vec<(i32, Result<()>)> {
+        let processes: Vec<&Process> = processes.into_iter().collect();
+
+        let mut status_codes = match Self::run_kill_batch(&processes, action, false) {
+            Ok(status_codes) => status_codes,
+            Err(e) => {
+                return processes
+                    .iter()
+                    .map(|process| (process.data.pid, Err(anyhow!("{e:#}"))))
+                    .collect()
+            }
         };
 
-        // TODO: tidy this mess up
+        // 1 := no permissions
+        let unpermitted: Vec<&Process> = processes
+            .iter()
+            .filter(|process| status_codes.get(&process.data.pid) == Some(&1))
+            .copied()
+            .collect();
 
-        let kill_path = if *IS_FLATPAK {
-            format!(
-                "{}/libexec/resources/resources-kill",
-                FLATPAK_APP_PATH.as_str()
+        let mut elevated_error = None;
+        if !unpermitted.is_empty() {
+            match Self::run_kill_batch(&unpermitted, action, true) {
+                Ok(elevated_status_codes) => status_codes.extend(elevated_status_codes),
+                Err(e) => elevated_error = Some(e),
+            }
+        }
+
+        processes
+            .iter()
+            .map(|process| {
+                let pid = process.data.pid;
+                let result = match status_codes.get(&pid).copied() {
+                    // 0 := successful; 3 := process not found which we don't
+                    // care about because that might happen because we killed
+                    // the process' parent first, killing the child before we
+                    // explicitly did
+                    Some(0 | 3) => Ok(()),
+                    Some(IDENTITY_CHANGED_STATUS_CODE) => Err(ProcessIdentityChanged(pid).into()),
+                    Some(1) => match &elevated_error {
+                        Some(e) => Err(anyhow!(
+                            "couldn't kill {pid} with elevated privileges: {e:#}"
+                        )),
+                        None => Err(anyhow!("couldn't kill {pid} even with elevated privileges")),
+                    },
+                    Some(status_code) => Err(anyhow!(
+                        "couldn't kill {pid} due to unknown reasons, status code: {status_code}"
+                    )),
+                    None => Err(anyhow!("resources-kill didn't return a result for {pid}")),
+                };
+                (pid, result)
+            })
+            .collect()
+    }
+
+    /// Calls `resources-kill` in batch mode (through `pkexec` if `elevated`)
+    /// and returns its status code for each PID
+    fn run_kill_batch(
+        processes: &[&Process],
+        action: ProcessAction,
+        elevated: bool,
+    ) -> Result<HashMap<i32, i32>> {
+        let output = helper_command("resources-kill", elevated)
+            .args([action.signal_name(), "--batch".to_string()])
+            .args(
+                processes
+                    .iter()
+                    .map(|process| format!("{}:{}", process.data.pid, process.data.starttime)),
             )
-        } else {
-            format!("{LIBEXECDIR}/resources-kill")
+            .output()?;
+
+        if !output.status.success() {
+            bail!(
+                "resources-kill failed, status code: {}",
+                output
+                    .status
+                    .code()
+                    .map_or_else(|| "none".to_string(), |code| code.to_string())
+            );
+        }
+
+        let status_codes: Vec<(i32, i32)> = serde_json::from_slice(&output.stdout)
+            .context("unable to parse the output of resources-kill")?;
+
+        Ok(status_codes.into_iter().collect())
+    }
+
+    /// Changes the nice value, the I/O priority or the CPU affinity of this
+    /// process using `resources-adjust`, retrying with elevated privileges if
+    /// we're not permitted to do so.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the priority could not be changed. If the PID now
+    /// belongs to another process, the error will be a
+    /// [`ProcessIdentityChanged`].
+    pub fn adjust(&self, adjustment: ProcessAdjustment) -> Result<()> {
+        let mut args = match adjustment {
+            ProcessAdjustment::Nice(nice) => vec!["nice".to_string(), nice.to_string()],
+            ProcessAdjustment::SchedulingPolicy(policy) => vec![
+                "sched".to_string(),
+                match policy {
+                    1 => "fifo",
+                    2 => "rr",
+                    3 => "batch",
+                    5 => "idle",
+                    _ => "other",
+                }
+                .to_string(),
+            ],
+            ProcessAdjustment::IoPriority(io_priority) => vec![
+                "ioprio".to_string(),
+                match io_priority.class {
+                    IoPriorityClass::None => "none",
+                    IoPriorityClass::RealTime => "realtime",
+                    IoPriorityClass::BestEffort => "best-effort",
+                    IoPriorityClass::Idle => "idle",
+                }
+                .to_string(),
+                io_priority.level.to_string(),
+            ],
+            ProcessAdjustment::Affinity(cpus) => {
+                vec!["affinity".to_string(), format_cpu_list(&cpus)]
+            }
         };
+        args.extend([self.data.pid.to_string(), self.data.starttime.to_string()]);
 
-        let status_code = if *IS_FLATPAK {
-            Command::new(FLATPAK_SPAWN)
-                .args([
-                    "--host",
-                    kill_path.as_str(),
-                    action_str,
-                    self.data.pid.to_string().as_str(),
-                ])
-                .output()?
-                .status
-                .code()
-                .with_context(|| "no status code?")?
-        } else {
-            Command::new(kill_path.as_str())
-                .args([action_str, self.data.pid.to_string().as_str()])
+        let mut status_code = helper_command("resources-adjust", false)
+            .args(&args)
+            .output()?
+            .status
+            .code()
+            .context("no status code?")?;
+
+        // 1 := no permissions; 13 := not permitted to raise the priority
+        if status_code == 1 || status_code == 13 {
+            status_code = helper_command("resources-adjust", true)
+                .args(&args)
                 .output()?
                 .status
                 .code()
-                .with_context(|| "no status code?")?
-        };
+                .context("no status code?")?;
+        }
 
-        if status_code == 0 || status_code == 3 {
-            // 0 := successful; 3 := process not found which we don't care
-            // about because that might happen because we killed the
-            // process' parent first, killing the child before we explicitly
-            // did
-            Ok(())
-        } else if status_code == 1 {
-            // 1 := no permissions
-            self.pkexec_execute_process_action(action_str, &kill_path)
-        } else {
-            bail!(
-                "couldn't kill {} due to unknown reasons, status code: {}",
+        match status_code {
+            0 => Ok(()),
+            IDENTITY_CHANGED_STATUS_CODE => Err(ProcessIdentityChanged(self.data.pid).into()),
+            _ => bail!(
+                "couldn't adjust the priority of {}, status code: {}",
                 self.data.pid,
                 status_code
-            )
+            ),
         }
     }
 
-    fn pkexec_execute_process_action(&self, action: &str, kill_path: &str) -> Result<()> {
-        let status_code = if *IS_FLATPAK {
-            Command::new(FLATPAK_SPAWN)
-                .args([
-                    "--host",
-                    "pkexec",
-                    "--disable-internal-agent",
-                    kill_path,
-                    action,
-                    self.data.pid.to_string().as_str(),
-                ])
-                .output()?
-                .status
-                .code()
-                .with_context(|| "no status code?")?
-        } else {
-            Command::new("pkexec")
-                .args([
-                    "--disable-internal-agent",
-                    kill_path,
-                    action,
-                    self.data.pid.to_string().as_str(),
-                ])
-                .output()?
-                .status
-                .code()
-                .with_context(|| "no status code?")?
+    /// Returns the memory usage of this process according to `metric`. Falls
+    /// back to resident memory without shared memory if `smaps_rollup` could
+    /// not be read.
+    #[must_use]
+    pub fn memory_usage(&self, metric: MemoryMetric) -> usize {
+        let Some(memory_details) = self.data.memory_details else {
+            return self.data.memory_usage;
         };
 
-        if status_code == 0 || status_code == 3 {
-            // 0 := successful; 3 := process not found which we don't care
-            // about because that might happen because we killed the
-            // process' parent first, killing the child before we explicitly do
-            Ok(())
-        } else {
-            bail!(
-                "couldn't kill {} with elevated privileges due to unknown reasons, status code: {}",
-                self.data.pid,
-                status_code
-            )
+        match metric {
+            MemoryMetric::Pss => memory_details.pss,
+            MemoryMetric::Uss => memory_details.uss,
+            MemoryMetric::Rss => memory_details.rss,
+            MemoryMetric::ResidentWithoutShared => self.data.memory_usage,
         }
     }
 
+    /// Returns the ratio of CPU time this process has used since the last
+    /// refresh according to `mode`.
     #[must_use]
-    pub fn cpu_time_ratio(&self) -> f32 {
+    pub fn cpu_time_ratio(&self, mode: CpuUsageMode) -> f32 {
         if self.cpu_time_last == 0 {
             0.0
         } else {
-            (self.data.cpu_time.saturating_sub(self.cpu_time_last) as f32
-                / (self
-                    .data
+            Self::ticks_to_ratio(
+                self.data.cpu_time.saturating_sub(self.cpu_time_last),
+                self.data
                     .cpu_time_timestamp
-                    .saturating_sub(self.cpu_time_last_timestamp)) as f32)
-                .clamp(0.0, 1.0)
+                    .saturating_sub(self.cpu_time_last_timestamp),
+                mode,
+            )
+        }
+    }
+
+    /// Converts `ticks` clock ticks of CPU time used within `delta_ms`
+    /// milliseconds into a ratio according to `mode`
+    fn ticks_to_ratio(ticks: u64, delta_ms: u64, mode: CpuUsageMode) -> f32 {
+        mode.ratio(
+            ticks as f64 / *CLK_TCK as f64,
+            delta_ms as f64 / 1000.0,
+            *LOGICAL_CPUS,
+        )
+    }
+
+    /// Returns the time this process has been started at in seconds since the
+    /// Unix epoch, `None` if the boot time of the system is unknown.
+    #[must_use]
+    pub fn start_time(&self) -> Option<u64> {
+        BOOT_TIME.map(|boot_time| boot_time + self.data.starttime / *CLK_TCK)
+    }
+
+    /// Returns the threads of this process along with the ratio of CPU time
+    /// they've used since the last refresh according to `mode`.
+    #[must_use]
+    pub fn thread_items(&self, mode: CpuUsageMode) -> Vec<ThreadItem> {
+        self.data
+            .threads
+            .iter()
+            .map(|thread| {
+                let cpu_time_ratio = self
+                    .threads_last
+                    .iter()
+                    .find(|thread_last| thread_last.tid == thread.tid)
+                    .map_or(0.0, |thread_last| {
+                        Self::ticks_to_ratio(
+                            thread.cpu_time.saturating_sub(thread_last.cpu_time),
+                            thread
+                                .cpu_time_timestamp
+                                .saturating_sub(thread_last.cpu_time_timestamp),
+                            mode,
+                        )
+                    });
+
+                ThreadItem {
+                    tid: thread.tid,
+                    name: thread.comm.clone(),
+                    state: thread.state,
+                    cpu_time_ratio,
+                    last_cpu: thread.last_cpu,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the usage of every GPU this process uses since the last
+    /// refresh, keyed by the GPU's PCI slot. The usage of a GPU is that of its
+    /// busiest engine. NVIDIA GPUs are taken from NVML instead of DRM.
+    #[must_use]
+    pub fn gpu_usages(&self) -> BTreeMap<String, GpuUsage> {
+        // in ns
+        let time_delta = self
+            .data
+            .gpu_usage_stats_timestamp
+            .saturating_sub(self.gpu_usage_stats_last_timestamp)
+            * 1_000_000;
+
+        self.data
+            .gpu_usage_stats
+            .iter()
+            .map(|(pci_slot, stats)| {
+                let usage = match self.gpu_usage_stats_last.get(pci_slot) {
+                    Some(last) if self.gpu_usage_stats_last_timestamp != 0 && time_delta != 0 => {
+                        let engines = stats.engines.iter().filter_map(|(engine, time)| {
+                            last.engines.get(engine).map(|time_last| {
+                                time.saturating_sub(*time_last) as f32 / time_delta as f32
+                            })
+                        });
+
+                        let cycles = stats.cycles.iter().filter_map(|(engine, (busy, total))| {
+                            let (busy_last, total_last) = last.cycles.get(engine)?;
+                            let total_delta = total.saturating_sub(*total_last);
+                            (total_delta != 0).then(|| {
+                                busy.saturating_sub(*busy_last) as f32 / total_delta as f32
+                            })
+                        });
+
+                        engines.chain(cycles).fold(0.0, f32::max).clamp(0.0, 1.0)
+                    }
+                    _ => 0.0,
+                };
+
+                (
+                    pci_slot.clone(),
+                    GpuUsage {
+                        usage,
+                        memory: stats.memory,
+                        encoder: None,
+                        decoder: None,
+                    },
+                )
+            })
+            .chain(self.nvidia_usages.iter().map(|(pci_slot, nvidia_usage)| {
+                let (sm, encoder, decoder) = (
+                    nvidia_usage.sm as f32 / 100.0,
+                    nvidia_usage.encoder as f32 / 100.0,
+                    nvidia_usage.decoder as f32 / 100.0,
+                );
+                (
+                    pci_slot.clone(),
+                    GpuUsage {
+                        usage: sm.max(encoder).max(decoder).clamp(0.0, 1.0),
+                        memory: nvidia_usage.memory,
+                        encoder: Some(encoder),
+                        decoder: Some(decoder),
+                    },
+                )
+            }))
+            .collect()
+    }
+
+    /// Returns the combined usage of the busiest GPU from 0.0 to 1.0 and the
+    /// video memory used on all GPUs in bytes by all of `processes`
+    #[must_use]
+    pub fn combined_gpu_usage<'a, I: IntoIterator<Item = &'a Process>>(processes: I) -> (f32, u64) {
+        let mut usages: BTreeMap<String, f32> = BTreeMap::new();
+        let mut memory = 0;
+
+        for process in processes {
+            for (pci_slot, gpu_usage) in process.gpu_usages() {
+                *usages.entry(pci_slot).or_default() += gpu_usage.usage;
+                memory += gpu_usage.memory;
+            }
         }
+
+        (
+            usages.into_values().fold(0.0, f32::max).clamp(0.0, 1.0),
+            memory,
+        )
     }
 
     #[must_use]
@@ -315,3 +806,125 @@ impl Process {
         }
     }
 }
+
+/// Returns a `Command` that runs our helper binary `helper` on the host, through
+/// `pkexec` if `elevated`
+fn helper_command(helper: &str, elevated: bool) -> Command {
+    let helper_path = if *IS_FLATPAK {
+        format!("{}/libexec/resources/{helper}", FLATPAK_APP_PATH.as_str())
+    } else {
+        format!("{LIBEXECDIR}/{helper}")
+    };
+
+    let mut command_line = Vec::new();
+    if *IS_FLATPAK {
+        command_line.extend([FLATPAK_SPAWN.to_string(), "--host".to_string()]);
+    }
+    if elevated {
+        command_line.extend(["pkexec".to_string(), "--disable-internal-agent".to_string()]);
+    }
+    command_line.push(helper_path);
+
+    let mut command = Command::new(&command_line[0]);
+    command.args(&command_line[1..]);
+    command
+}
+
+/// Returns a human-readable name for a process or thread state as found in
+/// `/proc/<pid>/stat`.
+#[must_use]
+pub fn state_name(state: char) -> String {
+    match state {
+        'R' => i18n("Running"),
+        'S' => i18n("Sleeping"),
+        'D' => i18n("Uninterruptible Sleep"),
+        'Z' => i18n("Zombie"),
+        'T' => i18n("Stopped"),
+        't' => i18n("Tracing Stop"),
+        'X' | 'x' => i18n("Dead"),
+        'I' => i18n("Idle"),
+        'P' => i18n("Parked"),
+        'K' => i18n("Wakekill"),
+        'W' => i18n("Waking"),
+        _ => i18n("Unknown"),
+    }
+}
+
+/// Returns a short human-readable name for the way a process is containerized,
+/// `None` if it isn't containerized at all.
+#[must_use]
+pub fn containerization_name(containerization: &Containerization) -> Option<String> {
+    match containerization {
+        Containerization::None => None,
+        Containerization::Flatpak => Some(i18n("Flatpak")),
+        Containerization::Snap(_) => Some(i18n("Snap")),
+        Containerization::AppImage(_) => Some(i18n("AppImage")),
+        Containerization::Docker(_) => Some(i18n("Docker")),
+        Containerization::Podman(_) => Some(i18n("Podman")),
+        Containerization::Containerd(_) => Some(i18n("containerd")),
+        Containerization::Lxc(_) => Some(i18n("LXC")),
+        Containerization::Nspawn(_) => Some(i18n("systemd-nspawn")),
+    }
+}
+
+/// Returns the name of the way a process is containerized along with the
+/// identifier of its app or container, e. g. "Docker (4f1c2e3d5a6b)".
+#[must_use]
+pub fn containerization_description(containerization: &Containerization) -> Option<String> {
+    let name = containerization_name(containerization)?;
+    let description = match container::short_identifier(containerization) {
+        Some(identifier) => i18n_f("{} ({})", &[&name, identifier]),
+        None => name,
+    };
+    Some(description)
+}
+
+/// Returns an explanation for processes in states that usually hint at a
+/// problem, i. e. zombies and processes in uninterruptible sleep, or an empty
+/// string for all other states.
+#[must_use]
+pub fn state_flag(state: char) -> String {
+    match state {
+        'Z' => i18n("This process has ended but has not been reaped by its parent process yet"),
+        'D' => {
+            i18n("This process is waiting for I/O and can't be interrupted, not even by signals")
+        }
+        _ => String::new(),
+    }
+}
+
+/// The scheduling policies that can be set through `resources-adjust`, in the
+/// order they're offered in
+pub const SCHEDULING_POLICIES: [u32; 5] = [0, 3, 5, 1, 2];
+
+/// Returns a human-readable name for a scheduling policy as found in
+/// `/proc/<pid>/stat`.
+#[must_use]
+pub fn scheduling_policy_name(policy: u32) -> String {
+    match policy {
+        0 => i18n("Normal"),
+        1 => i18n("FIFO"),
+        2 => i18n("Round Robin"),
+        3 => i18n("Batch"),
+        5 => i18n("Idle"),
+        6 => i18n("Deadline"),
+        _ => i18n("Unknown"),
+    }
+}
+
+/// Returns a human-readable description of an I/O priority, e. g. "Best
+/// Effort (4)".
+#[must_use]
+pub fn io_priority_name(io_priority: Option<IoPriority>) -> String {
+    match io_priority {
+        None => i18n("N/A"),
+        Some(io_priority) => match io_priority.class {
+            IoPriorityClass::None => i18n("Default"),
+            IoPriorityClass::RealTime => i18n_f("Realtime ({})", &[&io_priority.level.to_string()]),
+            IoPriorityClass::BestEffort => {
+                i18n_f("Best Effort ({})", &[&io_priority.level.to_string()])
+            }
+            IoPriorityClass::Idle => i18n("Idle"),
+        },
+    }
+}
@@ -0,0 +1,482 @@
+use anyhow::{bail, Context, Result};
+use gtk::prelude::*;
+use regex::{Regex, RegexBuilder};
+
+use super::settings::{Base, SETTINGS};
+use crate::i18n::{i18n, i18n_f};
+
+/// A field of an app or process that can be searched, e. g. `user` in
+/// `user:root`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    Name,
+    Commandline,
+    User,
+    Pid,
+    Cgroup,
+    Container,
+    Id,
+    Description,
+    /// In percent
+    Cpu,
+    /// In bytes
+    Memory,
+    /// In bytes per second
+    Read,
+    /// In bytes per second
+    Write,
+    /// In percent
+    Gpu,
+    /// In bytes
+    VideoMemory,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "name" => Some(Field::Name),
+            "cmd" | "command" | "commandline" => Some(Field::Commandline),
+            "user" => Some(Field::User),
+            "pid" => Some(Field::Pid),
+            "cgroup" => Some(Field::Cgroup),
+            "container" => Some(Field::Container),
+            "id" => Some(Field::Id),
+            "desc" | "description" => Some(Field::Description),
+            "cpu" => Some(Field::Cpu),
+            "mem" | "memory" => Some(Field::Memory),
+            "read" => Some(Field::Read),
+            "write" => Some(Field::Write),
+            "gpu" => Some(Field::Gpu),
+            "vram" => Some(Field::VideoMemory),
+            _ => None,
+        }
+    }
+
+    fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            Field::Pid
+                | Field::Cpu
+                | Field::Memory
+                | Field::Read
+                | Field::Write
+                | Field::Gpu
+                | Field::VideoMemory
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(self, left: f64, right: f64) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => (left - right).abs() < f64::EPSILON,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+/// An item of the Processes or Applications page that can be matched against
+/// a `Query`
+pub trait Searchable {
+    /// The fields that search terms without an explicit field are matched
+    /// against
+    fn default_fields(&self) -> &'static [Field];
+
+    /// The value of a textual field, `None` if the item doesn't have it
+    fn text(&self, field: Field) -> Option<String>;
+
+    /// The value of a numeric field in the unit documented at `Field`, `None`
+    /// if the item doesn't have it
+    fn number(&self, field: Field) -> Option<f64>;
+}
+
+/// A parsed search query like `firefox or (user:root and cpu>20)`.
+///
+/// Terms are either words that are searched for in the default fields of an
+/// item, `field:value` to search for `value` in a certain field or numeric
+/// comparisons like `mem>1G` or `read>=10M/s`. Values may be put in double
+/// quotes and written as `/regex/`. Terms can be combined using `and`, `or`,
+/// `not` and parentheses, adjacent terms are implicitly combined using `and`.
+#[derive(Debug, Clone)]
+pub enum Query {
+    /// A case-insensitive substring, matched against the default fields if
+    /// the field is `None`
+    Contains(Option<Field>, String),
+    /// A case-insensitive regex, matched against the default fields if the
+    /// field is `None`
+    Regex(Option<Field>, Regex),
+    Compare(Field, Comparison, f64),
+    Not(Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    /// Matches no item at all, stands in for a malformed query
+    Nothing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    OpenParen,
+    CloseParen,
+    Word(String),
+}
+
+impl Query {
+    /// Parses `query`, returns `None` if it doesn't contain any terms. Amounts
+    /// like `1G` are interpreted according to `base`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a translated message that can be shown to the
+    /// user if the query is malformed
+    pub fn parse<S: AsRef<str>>(query: S, base: Base) -> Result<Option<Self>> {
+        let tokens = Self::tokenize(query.as_ref())?;
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+
+        let mut position = 0;
+        let query = Self::parse_or(&tokens, &mut position, base)?;
+
+        if let Some(token) = tokens.get(position) {
+            bail!(i18n_f("Unexpected “{}”", &[&token.to_string()]));
+        }
+
+        Ok(Some(query))
+    }
+
+    /// Returns whether `item` matches this query
+    pub fn matches<S: Searchable>(&self, item: &S) -> bool {
+        match self {
+            Query::Contains(field, needle) => Self::texts(item, *field)
+                .iter()
+                .any(|text| text.to_lowercase().contains(needle)),
+            Query::Regex(field, regex) => Self::texts(item, *field)
+                .iter()
+                .any(|text| regex.is_match(text)),
+            Query::Compare(field, comparison, value) => item
+                .number(*field)
+                .is_some_and(|number| comparison.holds(number, *value)),
+            Query::Not(query) => !query.matches(item),
+            Query::And(left, right) => left.matches(item) && right.matches(item),
+            Query::Or(left, right) => left.matches(item) || right.matches(item),
+            Query::Nothing => false,
+        }
+    }
+
+    fn texts<S: Searchable>(item: &S, field: Option<Field>) -> Vec<String> {
+        match field {
+            Some(field) => item.text(field).into_iter().collect(),
+            None => item
+                .default_fields()
+                .iter()
+                .filter_map(|field| item.text(*field))
+                .collect(),
+        }
+    }
+
+    fn tokenize(query: &str) -> Result<Vec<Token>> {
+        let chars: Vec<char> = query.chars().collect();
+        let mut tokens = Vec::new();
+        let mut word = String::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            // quotes and regexes may contain whitespace and parentheses,
+            // regexes only start at the beginning of a value and a slash
+            // without a closing one is just a slash, e. g. in `/usr`
+            let closing = match c {
+                '"' => Some(Self::find_closing(&chars, i).with_context(|| {
+                    i18n_f(
+                        "Missing closing quote in “{}”",
+                        &[&chars[i..].iter().collect::<String>()],
+                    )
+                })?),
+                '/' if word.is_empty() || word.ends_with(':') => Self::find_closing(&chars, i),
+                _ => None,
+            };
+
+            if let Some(closing) = closing {
+                word.extend(&chars[i..=closing]);
+                i = closing + 1;
+                continue;
+            }
+
+            if c.is_whitespace() || c == '(' || c == ')' {
+                if !word.is_empty() {
+                    tokens.push(Token::Word(std::mem::take(&mut word)));
+                }
+                if c == '(' {
+                    tokens.push(Token::OpenParen);
+                } else if c == ')' {
+                    tokens.push(Token::CloseParen);
+                }
+            } else {
+                word.push(c);
+            }
+
+            i += 1;
+        }
+
+        if !word.is_empty() {
+            tokens.push(Token::Word(word));
+        }
+
+        Ok(tokens)
+    }
+
+    /// Returns the index of the character closing the quote or regex that is
+    /// opened at `start`, skipping escaped characters
+    fn find_closing(chars: &[char], start: usize) -> Option<usize> {
+        let delimiter = chars[start];
+        let mut i = start + 1;
+        while i < chars.len() {
+            if chars[i] == '\\' {
+                i += 2;
+            } else if chars[i] == delimiter {
+                return Some(i);
+            } else {
+                i += 1;
+            }
+        }
+        None
+    }
+
+    fn parse_or(tokens: &[Token], position: &mut usize, base: Base) -> Result<Self> {
+        let mut query = Self::parse_and(tokens, position, base)?;
+
+        while Self::is_keyword(tokens.get(*position), "or") {
+            *position += 1;
+            let right = Self::parse_and(tokens, position, base)?;
+            query = Query::Or(Box::new(query), Box::new(right));
+        }
+
+        Ok(query)
+    }
+
+    fn parse_and(tokens: &[Token], position: &mut usize, base: Base) -> Result<Self> {
+        let mut query = Self::parse_not(tokens, position, base)?;
+
+        loop {
+            match tokens.get(*position) {
+                None | Some(Token::CloseParen) => break,
+                token if Self::is_keyword(token, "or") => break,
+                token => {
+                    // `and` is optional between two terms
+                    if Self::is_keyword(token, "and") {
+                        *position += 1;
+                    }
+                    let right = Self::parse_not(tokens, position, base)?;
+                    query = Query::And(Box::new(query), Box::new(right));
+                }
+            }
+        }
+
+        Ok(query)
+    }
+
+    fn parse_not(tokens: &[Token], position: &mut usize, base: Base) -> Result<Self> {
+        if Self::is_keyword(tokens.get(*position), "not") {
+            *position += 1;
+            return Ok(Query::Not(Box::new(Self::parse_not(
+                tokens, position, base,
+            )?)));
+        }
+
+        Self::parse_primary(tokens, position, base)
+    }
+
+    fn parse_primary(tokens: &[Token], position: &mut usize, base: Base) -> Result<Self> {
+        let token = tokens
+            .get(*position)
+            .with_context(|| i18n("Expected a search term at the end"))?;
+        *position += 1;
+
+        match token {
+            Token::OpenParen => {
+                let query = Self::parse_or(tokens, position, base)?;
+                if tokens.get(*position) != Some(&Token::CloseParen) {
+                    bail!(i18n("Missing closing parenthesis"));
+                }
+                *position += 1;
+                Ok(query)
+            }
+            Token::CloseParen => bail!(i18n_f("Unexpected “{}”", &[")"])),
+            Token::Word(word) => {
+                if ["and", "or", "not"]
+                    .iter()
+                    .any(|keyword| word.eq_ignore_ascii_case(keyword))
+                {
+                    bail!(i18n_f("Expected a search term after “{}”", &[word]));
+                }
+                Self::parse_term(word, base)
+            }
+        }
+    }
+
+    fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+        matches!(token, Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn parse_term(term: &str, base: Base) -> Result<Self> {
+        // anything that looks like `key:value` is meant to be a field filter,
+        // so an unknown key is a mistake rather than something to search for
+        if let Some((key, _)) = term.split_once(':') {
+            if !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+                && Field::from_name(key).is_none()
+            {
+                bail!(i18n_f("Unknown field “{}”", &[key]));
+            }
+        }
+
+        let field_name_length = term
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(term.len());
+        let (field_name, rest) = term.split_at(field_name_length);
+
+        let (comparison, value) = if let Some(value) = rest.strip_prefix(':') {
+            (Comparison::Equal, value)
+        } else if let Some(value) = rest.strip_prefix(">=") {
+            (Comparison::GreaterOrEqual, value)
+        } else if let Some(value) = rest.strip_prefix("<=") {
+            (Comparison::LessOrEqual, value)
+        } else if let Some(value) = rest.strip_prefix('>') {
+            (Comparison::Greater, value)
+        } else if let Some(value) = rest.strip_prefix('<') {
+            (Comparison::Less, value)
+        } else if let Some(value) = rest.strip_prefix('=') {
+            (Comparison::Equal, value)
+        } else {
+            return Self::parse_value(None, term);
+        };
+
+        if field_name.is_empty() {
+            return Self::parse_value(None, term);
+        }
+
+        let field = Field::from_name(field_name)
+            .with_context(|| i18n_f("Unknown field “{}”", &[field_name]))?;
+
+        if value.is_empty() {
+            bail!(i18n_f("Expected a value after “{}”", &[term]));
+        }
+
+        if field.is_numeric() {
+            let amount = parse_amount(value, base)
+                .with_context(|| i18n_f("“{}” is not a valid number", &[value]))?;
+            Ok(Query::Compare(field, comparison, amount))
+        } else if comparison == Comparison::Equal {
+            Self::parse_value(Some(field), value)
+        } else {
+            bail!(i18n_f("“{}” can't be compared", &[field_name]))
+        }
+    }
+
+    fn parse_value(field: Option<Field>, value: &str) -> Result<Self> {
+        if let Some(pattern) = value
+            .strip_prefix('/')
+            .and_then(|value| value.strip_suffix('/'))
+            .filter(|_| value.len() > 1)
+        {
+            let regex = RegexBuilder::new(&pattern.replace("\\/", "/"))
+                .case_insensitive(true)
+                .build()
+                .with_context(|| i18n_f("“{}” is not a valid regular expression", &[value]))?;
+            Ok(Query::Regex(field, regex))
+        } else {
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            Ok(Query::Contains(field, value.to_lowercase()))
+        }
+    }
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::OpenParen => write!(f, "("),
+            Token::CloseParen => write!(f, ")"),
+            Token::Word(word) => write!(f, "{word}"),
+        }
+    }
+}
+
+/// Parses the text of `entry` as a `Query`. If the text is malformed, `entry`
+/// is highlighted and explains the error in its tooltip, nothing matches the
+/// text then.
+pub fn parse_search_entry(entry: &gtk::SearchEntry) -> Option<Query> {
+    let text = entry.text();
+    match Query::parse(&text, SETTINGS.base()) {
+        Ok(query) => {
+            entry.remove_css_class("error");
+            entry.set_tooltip_text(None);
+            query
+        }
+        Err(error) => {
+            entry.add_css_class("error");
+            entry.set_tooltip_text(Some(&error.to_string()));
+            Some(Query::Nothing)
+        }
+    }
+}
+
+/// Parses amounts like `20`, `1.5G`, `512KiB` or `10M/s`. Prefixes like `K`
+/// or `M` follow `base`, `Ki` or `Mi` are always binary. A trailing `B`, `%`
+/// or `/s` is ignored.
+pub fn parse_amount<S: AsRef<str>>(amount: S, base: Base) -> Option<f64> {
+    let amount = amount.as_ref().trim();
+    let amount = amount.strip_suffix("/s").unwrap_or(amount);
+    let amount = amount
+        .strip_suffix(['B', 'b', '%'])
+        .unwrap_or(amount)
+        .trim_end();
+
+    let (amount, factor) = if let Some(amount) = amount.strip_suffix('i') {
+        let (amount, exponent) = split_prefix(amount)?;
+        (amount, 1024_f64.powi(exponent))
+    } else {
+        let prefix_base = match base {
+            Base::Decimal => 1000_f64,
+            Base::Binary => 1024_f64,
+        };
+        split_prefix(amount).map_or((amount, 1.0), |(amount, exponent)| {
+            (amount, prefix_base.powi(exponent))
+        })
+    };
+
+    amount
+        .trim_end()
+        .parse::<f64>()
+        .ok()
+        .filter(|amount| amount.is_finite())
+        .map(|amount| amount * factor)
+}
+
+fn split_prefix(amount: &str) -> Option<(&str, i32)> {
+    let exponent = match amount.chars().last()? {
+        'k' | 'K' => 1,
+        'M' => 2,
+        'G' => 3,
+        'T' => 4,
+        _ => return None,
+    };
+    Some((&amount[..amount.len() - 1], exponent))
+}
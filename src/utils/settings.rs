@@ -54,6 +54,55 @@ impl RefreshSpeed {
     }
 }
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Default, EnumString, Display, Hash, FromRepr)]
+pub enum MemoryMetric {
+    Pss,
+    Uss,
+    Rss,
+    #[default]
+    ResidentWithoutShared,
+}
+
+/// How the CPU usage of apps and processes is expressed
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Default, EnumString, Display, Hash, FromRepr)]
+pub enum CpuUsageMode {
+    /// Relative to a single core, so a process using four cores fully is at
+    /// 400 %
+    Irix,
+    /// Relative to all cores of the machine, so nothing exceeds 100 %
+    #[default]
+    Solaris,
+}
+
+impl CpuUsageMode {
+    /// The highest ratio that can be reached on a machine with
+    /// `logical_cpus` CPUs
+    pub fn max_ratio(&self, logical_cpus: usize) -> f32 {
+        match self {
+            CpuUsageMode::Irix => logical_cpus.max(1) as f32,
+            CpuUsageMode::Solaris => 1.0,
+        }
+    }
+
+    /// Converts `busy_secs` seconds of CPU time used within `elapsed_secs`
+    /// seconds into a ratio where 1.0 means 100 %
+    pub fn ratio(&self, busy_secs: f64, elapsed_secs: f64, logical_cpus: usize) -> f32 {
+        if elapsed_secs <= 0.0 {
+            return 0.0;
+        }
+
+        let cores = busy_secs / elapsed_secs;
+        let ratio = match self {
+            CpuUsageMode::Irix => cores,
+            CpuUsageMode::Solaris => cores / logical_cpus.max(1) as f64,
+        };
+
+        (ratio as f32).clamp(0.0, self.max_ratio(logical_cpus))
+    }
+}
+
 #[derive(Clone, Debug, Hash)]
 pub struct Settings(gio::Settings);
 
@@ -115,6 +164,46 @@ impl Settings {
         })
     }
 
+    pub fn memory_metric(&self) -> MemoryMetric {
+        MemoryMetric::from_str(self.string("memory-metric").as_str()).unwrap_or_default()
+    }
+
+    pub fn set_memory_metric(&self, value: MemoryMetric) -> Result<(), glib::error::BoolError> {
+        self.set_string("memory-metric", &value.to_string())
+    }
+
+    pub fn connect_memory_metric<F: Fn(MemoryMetric) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("memory-metric"), move |settings, _key| {
+            f(
+                MemoryMetric::from_str(settings.string("memory-metric").as_str())
+                    .unwrap_or_default(),
+            )
+        })
+    }
+
+    pub fn cpu_usage_mode(&self) -> CpuUsageMode {
+        CpuUsageMode::from_str(self.string("cpu-usage-mode").as_str()).unwrap_or_default()
+    }
+
+    pub fn set_cpu_usage_mode(&self, value: CpuUsageMode) -> Result<(), glib::error::BoolError> {
+        self.set_string("cpu-usage-mode", &value.to_string())
+    }
+
+    pub fn connect_cpu_usage_mode<F: Fn(CpuUsageMode) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("cpu-usage-mode"), move |settings, _key| {
+            f(
+                CpuUsageMode::from_str(settings.string("cpu-usage-mode").as_str())
+                    .unwrap_or_default(),
+            )
+        })
+    }
+
     pub fn window_width(&self) -> i32 {
         self.int("window-width")
     }
@@ -350,6 +439,51 @@ impl Settings {
         )
     }
 
+    pub fn apps_show_gpu(&self) -> bool {
+        self.boolean("apps-show-gpu")
+    }
+
+    pub fn set_apps_show_gpu(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("apps-show-gpu", value)
+    }
+
+    pub fn connect_apps_show_gpu<F: Fn(bool) + 'static>(&self, f: F) -> glib::SignalHandlerId {
+        self.connect_changed(Some("apps-show-gpu"), move |settings, _key| {
+            f(settings.boolean("apps-show-gpu"))
+        })
+    }
+
+    pub fn apps_show_gpu_memory(&self) -> bool {
+        self.boolean("apps-show-gpu-memory")
+    }
+
+    pub fn set_apps_show_gpu_memory(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("apps-show-gpu-memory", value)
+    }
+
+    pub fn connect_apps_show_gpu_memory<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("apps-show-gpu-memory"), move |settings, _key| {
+            f(settings.boolean("apps-show-gpu-memory"))
+        })
+    }
+
+    pub fn apps_show_pressure(&self) -> bool {
+        self.boolean("apps-show-pressure")
+    }
+
+    pub fn set_apps_show_pressure(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("apps-show-pressure", value)
+    }
+
+    pub fn connect_apps_show_pressure<F: Fn(bool) + 'static>(&self, f: F) -> glib::SignalHandlerId {
+        self.connect_changed(Some("apps-show-pressure"), move |settings, _key| {
+            f(settings.boolean("apps-show-pressure"))
+        })
+    }
+
     pub fn processes_show_id(&self) -> bool {
         self.boolean("processes-show-id")
     }
@@ -496,6 +630,184 @@ impl Settings {
         )
     }
 
+    pub fn processes_show_gpu(&self) -> bool {
+        self.boolean("processes-show-gpu")
+    }
+
+    pub fn set_processes_show_gpu(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-gpu", value)
+    }
+
+    pub fn connect_processes_show_gpu<F: Fn(bool) + 'static>(&self, f: F) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-show-gpu"), move |settings, _key| {
+            f(settings.boolean("processes-show-gpu"))
+        })
+    }
+
+    pub fn processes_show_gpu_memory(&self) -> bool {
+        self.boolean("processes-show-gpu-memory")
+    }
+
+    pub fn set_processes_show_gpu_memory(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-gpu-memory", value)
+    }
+
+    pub fn connect_processes_show_gpu_memory<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-show-gpu-memory"), move |settings, _key| {
+            f(settings.boolean("processes-show-gpu-memory"))
+        })
+    }
+
+    pub fn processes_show_threads(&self) -> bool {
+        self.boolean("processes-show-threads")
+    }
+
+    pub fn set_processes_show_threads(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-threads", value)
+    }
+
+    pub fn connect_processes_show_threads<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-show-threads"), move |settings, _key| {
+            f(settings.boolean("processes-show-threads"))
+        })
+    }
+
+    pub fn processes_show_nice(&self) -> bool {
+        self.boolean("processes-show-nice")
+    }
+
+    pub fn set_processes_show_nice(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-nice", value)
+    }
+
+    pub fn connect_processes_show_nice<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-show-nice"), move |settings, _key| {
+            f(settings.boolean("processes-show-nice"))
+        })
+    }
+
+    pub fn processes_show_scheduling_policy(&self) -> bool {
+        self.boolean("processes-show-scheduling-policy")
+    }
+
+    pub fn set_processes_show_scheduling_policy(
+        &self,
+        value: bool,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-scheduling-policy", value)
+    }
+
+    pub fn connect_processes_show_scheduling_policy<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(
+            Some("processes-show-scheduling-policy"),
+            move |settings, _key| f(settings.boolean("processes-show-scheduling-policy")),
+        )
+    }
+
+    pub fn processes_show_io_priority(&self) -> bool {
+        self.boolean("processes-show-io-priority")
+    }
+
+    pub fn set_processes_show_io_priority(
+        &self,
+        value: bool,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-io-priority", value)
+    }
+
+    pub fn connect_processes_show_io_priority<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-show-io-priority"), move |settings, _key| {
+            f(settings.boolean("processes-show-io-priority"))
+        })
+    }
+
+    pub fn processes_show_state(&self) -> bool {
+        self.boolean("processes-show-state")
+    }
+
+    pub fn set_processes_show_state(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-state", value)
+    }
+
+    pub fn connect_processes_show_state<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-show-state"), move |settings, _key| {
+            f(settings.boolean("processes-show-state"))
+        })
+    }
+
+    pub fn processes_show_start_time(&self) -> bool {
+        self.boolean("processes-show-start-time")
+    }
+
+    pub fn set_processes_show_start_time(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-start-time", value)
+    }
+
+    pub fn connect_processes_show_start_time<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-show-start-time"), move |settings, _key| {
+            f(settings.boolean("processes-show-start-time"))
+        })
+    }
+
+    pub fn processes_show_running_time(&self) -> bool {
+        self.boolean("processes-show-running-time")
+    }
+
+    pub fn set_processes_show_running_time(
+        &self,
+        value: bool,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-show-running-time", value)
+    }
+
+    pub fn connect_processes_show_running_time<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(
+            Some("processes-show-running-time"),
+            move |settings, _key| f(settings.boolean("processes-show-running-time")),
+        )
+    }
+
+    pub fn processes_tree_view(&self) -> bool {
+        self.boolean("processes-tree-view")
+    }
+
+    pub fn set_processes_tree_view(&self, value: bool) -> Result<(), glib::error::BoolError> {
+        self.set_boolean("processes-tree-view", value)
+    }
+
+    pub fn connect_processes_tree_view<F: Fn(bool) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-tree-view"), move |settings, _key| {
+            f(settings.boolean("processes-tree-view"))
+        })
+    }
+
     pub fn show_logical_cpus(&self) -> bool {
         self.boolean("show-logical-cpus")
     }
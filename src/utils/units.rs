@@ -253,3 +253,24 @@ pub fn convert_power(watts: f64) -> String {
         Prefix::Quetta => i18n_f("{} QW", &[&format!("{:.2}", number)]),
     }
 }
+
+/// Formats a duration of `seconds` using its two most significant units, e. g.
+/// "2 d 5 h" or "3 min 12 s"
+pub fn convert_duration(seconds: u64) -> String {
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        (seconds % 86400) / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60,
+    );
+
+    if days > 0 {
+        i18n_f("{} d {} h", &[&days.to_string(), &hours.to_string()])
+    } else if hours > 0 {
+        i18n_f("{} h {} min", &[&hours.to_string(), &minutes.to_string()])
+    } else if minutes > 0 {
+        i18n_f("{} min {} s", &[&minutes.to_string(), &seconds.to_string()])
+    } else {
+        i18n_f("{} s", &[&seconds.to_string()])
+    }
+}
@@ -0,0 +1,356 @@
+use std::path::PathBuf;
+
+use resources::utils::{
+    cgroup::{CgroupLimits, CgroupStats},
+    container, cpu,
+    drive::{Drive, DriveType},
+    gpu, memory,
+    network::NetworkInterface,
+    pressure::Pressure,
+    search::{self, Field, Query, Searchable},
+    settings::{Base, CpuUsageMode},
+};
+
+fn fixture<S: AsRef<str>>(path: S) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(path.as_ref())
+}
+
+fn read_fixture<S: AsRef<str>>(path: S) -> String {
+    std::fs::read_to_string(fixture(path)).unwrap()
+}
+
+#[test]
+fn proc_stat_total() {
+    let proc_stat = read_fixture("desktop/proc/stat");
+    let line = cpu::select_proc_stat_line(&proc_stat, None).unwrap();
+    assert!(line.starts_with("cpu  "));
+
+    let (idle, total) = cpu::parse_proc_stat_line(line.as_bytes()).unwrap();
+    assert_eq!(idle, 18_890_231 + 23451);
+    assert_eq!(
+        total,
+        1_209_823 + 3412 + 398_112 + 18_890_231 + 23451 + 8712
+    );
+}
+
+#[test]
+fn proc_stat_cores() {
+    let proc_stat = read_fixture("desktop/proc/stat");
+
+    let line = cpu::select_proc_stat_line(&proc_stat, Some(3)).unwrap();
+    assert!(line.starts_with("cpu3 "));
+    let (idle, _) = cpu::parse_proc_stat_line(line.as_bytes()).unwrap();
+    assert_eq!(idle, 4_727_426 + 5716);
+
+    assert!(cpu::select_proc_stat_line(&proc_stat, Some(4)).is_err());
+}
+
+#[test]
+fn meminfo() {
+    let meminfo = memory::parse_meminfo(read_fixture("desktop/proc/meminfo")).unwrap();
+    assert_eq!(meminfo["MemTotal"].as_str(), Some("16318480 kB"));
+    assert_eq!(meminfo["SwapFree"].as_str(), Some("8123456 kB"));
+}
+
+#[test]
+fn dmidecode() {
+    let devices = memory::parse_dmidecode(&read_fixture("dmidecode.txt"));
+    assert_eq!(devices.len(), 2);
+
+    assert!(devices[0].installed);
+    assert_eq!(devices[0].speed, Some(3200));
+    assert_eq!(devices[0].form_factor, "SODIMM");
+    assert_eq!(devices[0].r#type, "DDR4");
+    assert_eq!(
+        devices[0].type_detail,
+        "Synchronous Unbuffered (Unregistered)"
+    );
+
+    assert!(!devices[1].installed);
+    assert_eq!(devices[1].speed, None);
+    assert_eq!(devices[1].r#type, "Unknown");
+}
+
+#[async_std::test]
+async fn nvme_drive() {
+    let drive = Drive::from_sysfs(fixture("desktop/sys/block/nvme0n1"))
+        .await
+        .unwrap();
+
+    assert_eq!(drive.block_device, "nvme0n1");
+    assert_eq!(drive.drive_type, DriveType::Nvme);
+    assert_eq!(drive.model.as_deref(), Some("Samsung SSD 980 PRO 250GB"));
+    assert_eq!(drive.capacity().await.unwrap(), 500_118_192 * 512);
+    assert!(drive.writable().await.unwrap());
+    assert!(!drive.removable().await.unwrap());
+    assert!(!drive.is_virtual().await);
+
+    let stats = drive.sys_stats().await.unwrap();
+    assert_eq!(stats.get("read_sectors"), Some(&9_876_543));
+    assert_eq!(stats.get("write_sectors"), Some(&87_654_321));
+}
+
+#[async_std::test]
+async fn wlan_interface() {
+    let interface = NetworkInterface::from_sysfs(&fixture("desktop/sys/class/net/wlp3s0"))
+        .await
+        .unwrap();
+
+    assert_eq!(interface.interface_name, "wlp3s0");
+    assert_eq!(interface.driver_name.as_deref(), Some("iwlwifi"));
+    assert_eq!(interface.hw_address.as_deref(), Some("a4:c3:f0:12:34:56"));
+    assert_eq!(interface.received_bytes().await.unwrap(), 1_234_567_890);
+    assert_eq!(interface.sent_bytes().await.unwrap(), 987_654_321);
+    assert!(!interface.is_virtual());
+}
+
+#[test]
+fn docker_container_name() {
+    assert_eq!(
+        container::parse_docker_name(r#"{"ID":"4f1c2e3d5a6b","Name":"/nginx-proxy","State":{}}"#)
+            .as_deref(),
+        Some("nginx-proxy")
+    );
+    assert_eq!(container::parse_docker_name(r#"{"Name":""}"#), None);
+    assert_eq!(container::parse_docker_name("not json"), None);
+}
+
+#[test]
+fn podman_container_name() {
+    let containers = r#"[
+        {"id":"9e8d7c6b","names":["postgres"],"image":"1a2b"},
+        {"id":"5f4e3d2c","names":["redis","cache"],"image":"3c4d"}
+    ]"#;
+
+    assert_eq!(
+        container::parse_podman_name(containers, "5f4e3d2c").as_deref(),
+        Some("redis")
+    );
+    assert_eq!(container::parse_podman_name(containers, "0000"), None);
+}
+
+#[async_std::test]
+async fn cgroup_stats() {
+    let stats = CgroupStats::from_dir(fixture(
+        "desktop/sys/fs/cgroup/user.slice/user-1000.slice/user@1000.service/app.slice/app-flatpak-org.mozilla.firefox-12345.scope",
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(stats.memory_current, 734_003_200);
+    assert_eq!(stats.memory_swap_current, Some(16_777_216));
+    assert_eq!(stats.cpu_usage_usec, 52_341_876);
+    assert_eq!(stats.read_bytes, Some(104_861_696));
+    assert_eq!(stats.write_bytes, Some(52_428_800));
+    assert!(!stats.frozen);
+}
+
+#[test]
+fn cgroup_io_stat() {
+    assert_eq!(CgroupStats::parse_io_stat(""), (0, 0));
+    assert_eq!(
+        CgroupStats::parse_io_stat(
+            "8:0 rbytes=10 wbytes=20 rios=1 wios=2\n8:16 rbytes=5 wbytes=0\n"
+        ),
+        (15, 20)
+    );
+    assert!(CgroupStats::parse_cpu_stat("user_usec 12\n").is_err());
+    assert!(CgroupStats::parse_frozen("populated 1\nfrozen 1\n"));
+    assert!(!CgroupStats::parse_frozen("populated 0\n"));
+}
+
+#[test]
+fn cgroup_cpu_time_ratio() {
+    let last = CgroupStats {
+        cpu_usage_usec: 1_000_000,
+        timestamp: 10_000,
+        ..Default::default()
+    };
+    let current = CgroupStats {
+        cpu_usage_usec: 1_500_000,
+        timestamp: 11_000,
+        ..Default::default()
+    };
+
+    // half a second of CPU time in one second is half a core, which is an
+    // eighth of a machine with four cores
+    assert!((current.cpu_time_ratio(&last, CpuUsageMode::Irix, 4) - 0.5).abs() < f32::EPSILON);
+    assert!((current.cpu_time_ratio(&last, CpuUsageMode::Solaris, 4) - 0.125).abs() < f32::EPSILON);
+    assert_eq!(current.cpu_time_ratio(&current, CpuUsageMode::Irix, 4), 0.0);
+}
+
+#[test]
+fn cpu_usage_modes() {
+    // four fully used cores
+    assert_eq!(CpuUsageMode::Irix.ratio(4.0, 1.0, 8), 4.0);
+    assert_eq!(CpuUsageMode::Solaris.ratio(4.0, 1.0, 8), 0.5);
+
+    // can't use more than all cores
+    assert_eq!(CpuUsageMode::Irix.ratio(16.0, 1.0, 8), 8.0);
+    assert_eq!(CpuUsageMode::Solaris.ratio(16.0, 1.0, 8), 1.0);
+
+    assert_eq!(CpuUsageMode::Irix.ratio(1.0, 0.0, 8), 0.0);
+}
+
+#[test]
+fn cgroup_limits() {
+    let limits = CgroupLimits::from_dir(fixture(
+        "desktop/sys/fs/cgroup/user.slice/user-1000.slice/user@1000.service/app.slice/app-flatpak-org.mozilla.firefox-12345.scope",
+    ));
+
+    assert_eq!(
+        limits,
+        CgroupLimits {
+            cpu_weight: 200,
+            memory_high: None,
+            memory_max: Some(4_294_967_296),
+            io_weight: 100,
+        }
+    );
+    assert_eq!(
+        CgroupLimits::from_dir(fixture("desktop/sys/fs/cgroup/nonexistent")),
+        CgroupLimits::default()
+    );
+    assert_eq!(CgroupLimits::parse_io_weight("259:0 500\n"), None);
+}
+
+#[test]
+fn pressure() {
+    let pressure = Pressure::parse(
+        "some avg10=1.53 avg60=0.87 avg300=0.25 total=123456\nfull avg10=0.50 avg60=0.20 avg300=0.05 total=65432\n",
+    )
+    .unwrap();
+
+    assert!((pressure.some.avg10 - 1.53).abs() < f64::EPSILON);
+    assert!((pressure.some.avg300 - 0.25).abs() < f64::EPSILON);
+    assert_eq!(pressure.some.total, 123_456);
+    assert_eq!(pressure.full.map(|full| full.total), Some(65_432));
+
+    // kernels before 5.13 don't report full CPU pressure
+    let cpu_pressure = Pressure::parse("some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n").unwrap();
+    assert_eq!(cpu_pressure.full, None);
+
+    assert!(Pressure::parse("").is_err());
+}
+
+#[test]
+fn nvml_pci_bus_id() {
+    assert_eq!(
+        gpu::normalize_pci_bus_id("00000000:0A:00.0"),
+        "0000:0a:00.0"
+    );
+    assert_eq!(gpu::normalize_pci_bus_id("0000:01:00.0"), "0000:01:00.0");
+}
+
+struct SearchItem {
+    name: &'static str,
+    user: &'static str,
+    pid: i32,
+    cpu: f64,
+    memory: f64,
+}
+
+impl Searchable for SearchItem {
+    fn default_fields(&self) -> &'static [Field] {
+        &[Field::Name]
+    }
+
+    fn text(&self, field: Field) -> Option<String> {
+        match field {
+            Field::Name => Some(self.name.to_string()),
+            Field::User => Some(self.user.to_string()),
+            _ => None,
+        }
+    }
+
+    fn number(&self, field: Field) -> Option<f64> {
+        match field {
+            Field::Pid => Some(f64::from(self.pid)),
+            Field::Cpu => Some(self.cpu),
+            Field::Memory => Some(self.memory),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn search_queries() {
+    let firefox = SearchItem {
+        name: "Firefox Web Browser",
+        user: "alice",
+        pid: 2417,
+        cpu: 35.0,
+        memory: 1.5e9,
+    };
+    let systemd = SearchItem {
+        name: "systemd",
+        user: "root",
+        pid: 1,
+        cpu: 0.5,
+        memory: 12e6,
+    };
+
+    let matches = |query: &str| -> Vec<i32> {
+        let query = Query::parse(query, Base::Decimal).unwrap().unwrap();
+        [&firefox, &systemd]
+            .into_iter()
+            .filter(|item| query.matches(*item))
+            .map(|item| item.pid)
+            .collect()
+    };
+
+    assert_eq!(matches("fire"), vec![2417]);
+    assert_eq!(matches("user:root"), vec![1]);
+    assert_eq!(matches("pid:1"), vec![1]);
+    assert_eq!(matches("cpu>20"), vec![2417]);
+    assert_eq!(matches("mem>1G"), vec![2417]);
+    assert_eq!(matches("mem<=12M"), vec![1]);
+    assert_eq!(matches("name:/^sys/"), vec![1]);
+    assert_eq!(matches("name:\"web browser\""), vec![2417]);
+    assert_eq!(matches("not user:root"), vec![2417]);
+    assert_eq!(matches("user:root or cpu>20"), vec![2417, 1]);
+    assert_eq!(matches("fire and user:root"), Vec::<i32>::new());
+    assert_eq!(matches("(fire or systemd) cpu<1"), vec![1]);
+    assert_eq!(matches("/usr"), Vec::<i32>::new());
+
+    assert!(Query::parse("  ", Base::Decimal).unwrap().is_none());
+    assert!(Query::parse("foo:bar", Base::Decimal).is_err());
+    assert!(Query::parse("foo2:bar", Base::Decimal).is_err());
+    assert!(Query::parse("cpu-usage:5", Base::Decimal).is_err());
+    assert!(Query::parse("cpu>lots", Base::Decimal).is_err());
+    assert!(Query::parse("name>5", Base::Decimal).is_err());
+    assert!(Query::parse("(fire", Base::Decimal).is_err());
+    assert!(Query::parse("fire)", Base::Decimal).is_err());
+    assert!(Query::parse("fire and", Base::Decimal).is_err());
+    assert!(Query::parse("name:\"web", Base::Decimal).is_err());
+    assert!(Query::parse("name:/(/", Base::Decimal).is_err());
+}
+
+#[test]
+fn search_amounts() {
+    assert_eq!(search::parse_amount("20", Base::Decimal), Some(20.0));
+    assert_eq!(search::parse_amount("1.5k", Base::Decimal), Some(1500.0));
+    assert_eq!(search::parse_amount("1G", Base::Decimal), Some(1e9));
+    assert_eq!(
+        search::parse_amount("2MiB", Base::Decimal),
+        Some(2.0 * 1024.0 * 1024.0)
+    );
+    assert_eq!(search::parse_amount("10M/s", Base::Decimal), Some(1e7));
+    assert_eq!(search::parse_amount("50%", Base::Decimal), Some(50.0));
+    assert_eq!(search::parse_amount("many", Base::Decimal), None);
+
+    assert_eq!(
+        search::parse_amount("1.5k", Base::Binary),
+        Some(1.5 * 1024.0)
+    );
+    assert_eq!(
+        search::parse_amount("1G", Base::Binary),
+        Some(1024.0 * 1024.0 * 1024.0)
+    );
+    assert_eq!(
+        search::parse_amount("2MiB", Base::Binary),
+        Some(2.0 * 1024.0 * 1024.0)
+    );
+}